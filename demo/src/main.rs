@@ -14,6 +14,7 @@ struct Chair {
     width: Length,
     depth: Length,
     height: Length,
+    grid: DynamicUnit<Length>,
 }
 
 impl Assembly for Chair {
@@ -22,72 +23,93 @@ impl Assembly for Chair {
             width,
             depth,
             height,
+            ref grid,
         } = *self;
+        let beam = Beam::from_grid(grid);
+        let one = beam.grid_unit;
+        let two = num!(2) * one;
 
         vec![
-            Beam::z(qty!(0 m), qty!(0 m), (qty!(0 m), height)),
-            Beam::z(width - qty!(1 m), qty!(0 m), (qty!(0 m), height)),
-            Beam::z(qty!(0 m), depth - qty!(1 m), (qty!(0 m), height)),
-            Beam::z(width - qty!(1 m), depth - qty!(1 m), (qty!(0 m), height)),
-            Beam::x((qty!(0 m), width), qty!(1 m), height - qty!(2 m)),
-            Beam::x((qty!(0 m), width), depth - qty!(2 m), height - qty!(2 m)),
-            Beam::y(qty!(1 m), (qty!(0 m), depth), height - qty!(1 m)),
-            Beam::y(width - qty!(2 m), (qty!(0 m), depth), height - qty!(1 m)),
+            beam.z(qty!(0 m), qty!(0 m), Span::new(qty!(0 m), height)),
+            beam.z(width - one, qty!(0 m), Span::new(qty!(0 m), height)),
+            beam.z(qty!(0 m), depth - one, Span::new(qty!(0 m), height)),
+            beam.z(width - one, depth - one, Span::new(qty!(0 m), height)),
+            beam.x(Span::new(qty!(0 m), width), one, height - two),
+            beam.x(Span::new(qty!(0 m), width), depth - two, height - two),
+            beam.y(one, Span::new(qty!(0 m), depth), height - one),
+            beam.y(width - two, Span::new(qty!(0 m), depth), height - one),
         ]
     }
 }
 
-// TODO Add support for variants with custom grid units.
+/// A grid beam, with a square cross-section of one grid unit.
 #[derive(Clone)]
 struct Beam {
     length: Length,
+    grid_unit: Length,
 }
 
 impl Beam {
-    fn x(x: (Length, Length), y: Length, z: Length) -> Product {
-        let length = (x.0 - x.1).abs();
+    /// A beam one grid unit long, to be sized as it is placed.
+    fn from_grid(grid: &DynamicUnit<Length>) -> Self {
+        let grid_unit = grid.size();
+        Self {
+            length: grid_unit,
+            grid_unit,
+        }
+    }
+
+    fn with_length(&self, length: Length) -> Self {
+        Self {
+            length,
+            grid_unit: self.grid_unit,
+        }
+    }
+
+    fn x(&self, x: Span<Length>, y: Length, z: Length) -> Product {
+        let length = x.abs_length();
 
-        let mut beam = Self { length }.place();
+        let mut beam = self.with_length(length).place();
 
-        if x.0 > x.1 {
+        if x.is_reversed() {
             beam = beam.rotate(Y_AXIS, Rotations::HALF, None)
         }
 
-        beam.translate(x.0, y, z)
+        beam.translate(x.start, y, z)
     }
 
-    fn y(x: Length, y: (Length, Length), z: Length) -> Product {
-        let length = (y.0 - y.1).abs();
+    fn y(&self, x: Length, y: Span<Length>, z: Length) -> Product {
+        let length = y.abs_length();
 
-        let mut beam = Self { length }.place();
+        let mut beam = self.with_length(length).place();
 
         beam = beam.rotate(Z_AXIS, Rotations::QUARTER, None);
 
-        if y.0 > y.1 {
+        if y.is_reversed() {
             beam = beam.rotate(X_AXIS, Rotations::HALF, None)
         }
 
-        beam.translate(x, y.0, z)
+        beam.translate(x, y.start, z)
     }
 
-    fn z(x: Length, y: Length, z: (Length, Length)) -> Product {
-        let length = (z.0 - z.1).abs();
+    fn z(&self, x: Length, y: Length, z: Span<Length>) -> Product {
+        let length = z.abs_length();
 
-        let mut beam = Self { length }.place();
+        let mut beam = self.with_length(length).place();
 
         beam = beam.rotate(Y_AXIS, -Rotations::QUARTER, None);
 
-        if z.0 > z.1 {
+        if z.is_reversed() {
             beam = beam.rotate(X_AXIS, Rotations::HALF, None)
         }
 
-        beam.translate(x, y, z.0)
+        beam.translate(x, y, z.start)
     }
 }
 
 impl Stock for Beam {
     fn render(&self) -> Renderable {
-        let grid_unit: Length = qty!(1 m);
+        let grid_unit = self.grid_unit;
 
         Renderable::default()
             .insert_mesh(
@@ -123,10 +145,12 @@ impl Stock for Beam {
 }
 
 fn setup_model(mut commands: Commands, sandbox: Query<Entity, With<Sandbox>>) {
+    let grid = DynamicUnit::new("grid unit", "gu", qty!(1 m)).unwrap();
     let test = Chair {
-        width: qty!(10 m),
-        depth: qty!(10 m),
-        height: qty!(10 m),
+        width: Length::from_dynamic(num!(10), &grid),
+        depth: Length::from_dynamic(num!(10), &grid),
+        height: Length::from_dynamic(num!(10), &grid),
+        grid,
     };
     spawn_product(sandbox.single(), test.place(), &mut commands);
 }
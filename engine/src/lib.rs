@@ -31,6 +31,7 @@ impl Plugin for EnginePlugin {
         ))
         .insert_resource(AssetStore::<RenderableMesh, Mesh>::new())
         .insert_resource(AssetStore::<RenderableMaterial, StandardMaterial>::new())
+        .init_resource::<EvaluationMode>()
         .add_systems(Startup, setup_sandbox)
         .add_systems(Update, process_products)
         .add_systems(Update, process_renderables);
@@ -9,6 +9,17 @@ use crate::spawn_renderable;
 #[require(Transform, Visibility)]
 pub struct ProductObject(ProductKind);
 
+/// How products are evaluated.
+///
+/// In strict mode, a product with an invalid number (e.g. from a division by
+/// zero) is logged and skipped instead of spawned as garbage geometry.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EvaluationMode {
+    #[default]
+    Lenient,
+    Strict,
+}
+
 pub fn spawn_product(parent: Entity, product: Product, commands: &mut Commands) {
     let Product { kind, transform } = product;
     let transform: Transform = transform.into();
@@ -19,17 +30,36 @@ pub fn spawn_product(parent: Entity, product: Product, commands: &mut Commands)
 
 pub(crate) fn process_products(
     mut commands: Commands,
+    mode: Res<EvaluationMode>,
     query: Query<(Entity, &ProductObject), Added<ProductObject>>,
 ) {
     for (entity, object) in query.iter() {
         let kind: &ProductKind = &object.0;
         match kind {
             ProductKind::Stock(stock) => {
-                let renderable = stock.render();
+                let renderable = match *mode {
+                    EvaluationMode::Lenient => stock.render(),
+                    EvaluationMode::Strict => match stock.try_render() {
+                        Ok(renderable) => renderable,
+                        Err(error) => {
+                            error!("Unable to render stock: {}", error);
+                            continue;
+                        }
+                    },
+                };
                 spawn_renderable(entity, renderable, &mut commands);
             }
             ProductKind::Assembly(assembly) => {
-                let products = assembly.products();
+                let products = match *mode {
+                    EvaluationMode::Lenient => assembly.products(),
+                    EvaluationMode::Strict => match assembly.try_products() {
+                        Ok(products) => products,
+                        Err(error) => {
+                            error!("Unable to evaluate assembly: {}", error);
+                            continue;
+                        }
+                    },
+                };
                 for product in products {
                     spawn_product(entity, product, &mut commands);
                 }
@@ -0,0 +1,287 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::{Add, Mul, Sub};
+use villagekit_number::{
+    traits::{Abs, ApproxEq, Validate},
+    Number, NumberError,
+};
+use villagekit_unit::with_units::{SerdeUnits, WithUnits};
+
+use crate::{Quaternion, Vector3, X_AXIS, Y_AXIS, Z_AXIS};
+
+/// An axis-aligned bounding box, from the corner with the lowest value along each axis to
+/// the corner with the highest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Aabb<N> {
+    pub min: Vector3<N>,
+    pub max: Vector3<N>,
+}
+
+impl<N> Aabb<N> {
+    pub const fn new(min: Vector3<N>, max: Vector3<N>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<N> Aabb<N>
+where
+    N: Copy + PartialOrd,
+{
+    /// Creates the box spanning from one corner to the opposite corner, in any order.
+    pub fn from_corners(a: Vector3<N>, b: Vector3<N>) -> Self {
+        Self::new(min(a, b), max(a, b))
+    }
+
+    /// Returns the smallest box containing all of the points, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Vector3<N>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|point| Self::new(point, point))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(min(self.min, other.min), max(self.max, other.max))
+    }
+
+    /// Returns the box where both boxes overlap, or `None` if they don't. Boxes which only
+    /// touch overlap in a flat box.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(max(self.min, other.min), min(self.max, other.max));
+        let Self { min, max } = intersection;
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(intersection)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether the point is inside the box or on its surface.
+    pub fn contains(&self, point: Vector3<N>) -> bool {
+        let Self { min, max } = self;
+        (min.x <= point.x && point.x <= max.x)
+            && (min.y <= point.y && point.y <= max.y)
+            && (min.z <= point.z && point.z <= max.z)
+    }
+
+    pub fn contains_box(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// The eight corners of the box.
+    pub fn corners(&self) -> [Vector3<N>; 8] {
+        let Self { min, max } = *self;
+        [
+            Vector3::new(min.x, min.y, min.z),
+            Vector3::new(max.x, min.y, min.z),
+            Vector3::new(min.x, max.y, min.z),
+            Vector3::new(max.x, max.y, min.z),
+            Vector3::new(min.x, min.y, max.z),
+            Vector3::new(max.x, min.y, max.z),
+            Vector3::new(min.x, max.y, max.z),
+            Vector3::new(max.x, max.y, max.z),
+        ]
+    }
+}
+
+impl<N> Aabb<N>
+where
+    N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Number, Output = N>,
+{
+    /// Creates the box of the given size around a center.
+    pub fn from_center_size(center: Vector3<N>, size: Vector3<N>) -> Self {
+        let half_size = size * Number::HALF;
+        Self::new(center - half_size, center + half_size)
+    }
+
+    pub fn center(&self) -> Vector3<N> {
+        (self.min + self.max) * Number::HALF
+    }
+
+    /// The length of the box along each axis.
+    pub fn size(&self) -> Vector3<N> {
+        self.max - self.min
+    }
+
+    /// Grows the box by a margin on every side, or shrinks it for a negative margin.
+    pub fn expand(&self, margin: N) -> Self {
+        let margin = Vector3::new(margin, margin, margin);
+        Self::new(self.min - margin, self.max + margin)
+    }
+
+    /// Returns the smallest axis-aligned box containing this box once rotated about the
+    /// origin, then translated.
+    pub fn transform(&self, rotation: Quaternion, translation: Vector3<N>) -> Self {
+        // from https://www.realtimerendering.com/resources/GraphicsGems/gems/TransBox.c
+        let [x_axis, y_axis, z_axis] = [X_AXIS, Y_AXIS, Z_AXIS].map(|axis| rotation * axis);
+        let rotate = |v: Vector3<N>| {
+            Vector3::new(
+                v.x * x_axis.x + v.y * y_axis.x + v.z * z_axis.x,
+                v.x * x_axis.y + v.y * y_axis.y + v.z * z_axis.y,
+                v.x * x_axis.z + v.y * y_axis.z + v.z * z_axis.z,
+            )
+        };
+        // Each half of the size adds to the new half size however the axes turn.
+        let [x_axis, y_axis, z_axis] = [x_axis, y_axis, z_axis].map(|axis| axis.map(Abs::abs));
+        let half_size = self.size() * Number::HALF;
+        let half_size = Vector3::new(
+            half_size.x * x_axis.x + half_size.y * y_axis.x + half_size.z * z_axis.x,
+            half_size.x * x_axis.y + half_size.y * y_axis.y + half_size.z * z_axis.y,
+            half_size.x * x_axis.z + half_size.y * y_axis.z + half_size.z * z_axis.z,
+        );
+        let center = rotate(self.center()) + translation;
+        Self::new(center - half_size, center + half_size)
+    }
+}
+
+/// Moves the box by an offset.
+impl<N> Add<Vector3<N>> for Aabb<N>
+where
+    N: Copy + Add<Output = N>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Vector3<N>) -> Self::Output {
+        Self::new(self.min + rhs, self.max + rhs)
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Aabb<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.min.approx_eq(&rhs.min) && self.max.approx_eq(&rhs.max)
+    }
+}
+
+impl<N: Validate> Validate for Aabb<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.min.validate()?;
+        self.max.validate()
+    }
+}
+
+impl<N> SerdeUnits for Aabb<N>
+where
+    N: SerdeUnits + Copy,
+{
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Aabb::new(self.min.map(WithUnits), self.max.map(WithUnits)).serialize(serializer)
+    }
+
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Aabb { min, max } = Aabb::<WithUnits<N>>::deserialize(deserializer)?;
+        let unwrap = |WithUnits(value)| value;
+        Ok(Self::new(min.map(unwrap), max.map(unwrap)))
+    }
+}
+
+fn min<N: Copy + PartialOrd>(a: Vector3<N>, b: Vector3<N>) -> Vector3<N> {
+    let pick = |a: N, b: N| if b < a { b } else { a };
+    Vector3::new(pick(a.x, b.x), pick(a.y, b.y), pick(a.z, b.z))
+}
+
+fn max<N: Copy + PartialOrd>(a: Vector3<N>, b: Vector3<N>) -> Vector3<N> {
+    let pick = |a: N, b: N| if b > a { b } else { a };
+    Vector3::new(pick(a.x, b.x), pick(a.y, b.y), pick(a.z, b.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::{num, traits::Sqrt};
+    use villagekit_unit::{qty, Length};
+
+    use super::*;
+
+    #[test]
+    fn boxes() {
+        let beam: Aabb<Length> = Aabb::from_corners(
+            Vector3::new(qty!(2 m), qty!(40 mm), qty!(40 mm)),
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(80 mm)),
+        );
+        assert_eq!(beam.min, Vector3::new(qty!(0 m), qty!(0 m), qty!(40 mm)));
+        assert_eq!(
+            beam.size(),
+            Vector3::new(qty!(2 m), qty!(40 mm), qty!(40 mm))
+        );
+        assert_eq!(
+            beam.center(),
+            Vector3::new(qty!(1 m), qty!(20 mm), qty!(60 mm))
+        );
+        assert!(beam.contains(Vector3::new(qty!(2 m), qty!(20 mm), qty!(60 mm))));
+        assert!(!beam.contains(Vector3::new(qty!(2.5 m), qty!(20 mm), qty!(60 mm))));
+
+        let post = Aabb::from_center_size(
+            Vector3::new(qty!(1 m), qty!(20 mm), qty!(0.5 m)),
+            Vector3::new(qty!(40 mm), qty!(40 mm), qty!(1 m)),
+        );
+        assert_eq!(
+            beam.intersection(&post),
+            Some(Aabb::new(
+                Vector3::new(qty!(980 mm), qty!(0 m), qty!(40 mm)),
+                Vector3::new(qty!(1020 mm), qty!(40 mm), qty!(80 mm)),
+            ))
+        );
+        let moved = post + Vector3::new(qty!(0 m), qty!(1 m), qty!(0 m));
+        assert!(!beam.intersects(&moved));
+        let both = beam.union(&moved);
+        assert!(both.contains_box(&beam) && both.contains_box(&moved));
+        assert_eq!(
+            Aabb::from_points(beam.corners().into_iter().chain(moved.corners())),
+            Some(both)
+        );
+        assert!(beam.expand(qty!(10 mm)).size().approx_eq(&Vector3::new(
+            qty!(2020 mm),
+            qty!(60 mm),
+            qty!(60 mm)
+        )));
+    }
+
+    #[test]
+    fn differentiated_bounds() {
+        use villagekit_unit::Dual;
+
+        // How the bounds of a seat and its back change per centimetre of seat width.
+        let width = Dual::new(qty!(500 mm), qty!(1 cm));
+        let constant = |value: Length| Dual::constant(value);
+        let corner = |x, y: Length, z: Length| Vector3::new(x, constant(y), constant(z));
+        let seat = Aabb::from_corners(
+            corner(constant(qty!(0 m)), qty!(0 m), qty!(450 mm)),
+            corner(width, qty!(400 mm), qty!(470 mm)),
+        );
+        let back = Aabb::from_corners(
+            corner(constant(qty!(0 m)), qty!(380 mm), qty!(470 mm)),
+            corner(width, qty!(400 mm), qty!(900 mm)),
+        );
+        let bounds = seat.union(&back);
+        assert_eq!(bounds.size().x, width);
+        assert_eq!(bounds.size().z, constant(qty!(450 mm)));
+        assert_eq!(bounds.center().x.derivative, qty!(5 mm));
+    }
+
+    #[test]
+    fn transform() {
+        let beam: Aabb<Length> = Aabb::new(
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(2 m), qty!(1 m), qty!(1 m)),
+        );
+        let upright = beam.transform(
+            Quaternion::from_axis_angle(Y_AXIS, qty!(-90 deg)),
+            Vector3::new(qty!(1 m), qty!(0 m), qty!(0 m)),
+        );
+        assert!(upright.approx_eq(&Aabb::new(
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(1 m), qty!(1 m), qty!(2 m)),
+        )));
+
+        // A square turned an eighth is as wide as its diagonal.
+        let turned = beam.transform(
+            Quaternion::from_axis_angle(Z_AXIS, qty!(45 deg)),
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+        );
+        let diagonal = qty!(1 m) * num!(2).sqrt();
+        let corners = beam
+            .corners()
+            .map(|corner| Quaternion::from_axis_angle(Z_AXIS, qty!(45 deg)) * corner);
+        assert!(turned.approx_eq(&Aabb::from_points(corners).unwrap()));
+        assert!(turned.size().x.approx_eq(&(diagonal * num!(1.5))));
+    }
+}
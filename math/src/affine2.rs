@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Neg, Sub};
+use villagekit_number::{
+    num,
+    traits::{ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::Angle;
+
+use crate::{Rotation2, Vector2};
+
+/// A 2D affine transform of points in `N`, e.g. `Length`: a linear map of rotation, scale
+/// and shear, followed by a translation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Affine2<N> {
+    /// Where the linear map takes the x axis.
+    pub x_axis: Vector2<Number>,
+    /// Where the linear map takes the y axis.
+    pub y_axis: Vector2<Number>,
+    pub translation: Vector2<N>,
+}
+
+impl<N> Affine2<N> {
+    const X_AXIS: Vector2<Number> = Vector2::new(num!(1), num!(0));
+    const Y_AXIS: Vector2<Number> = Vector2::new(num!(0), num!(1));
+
+    pub fn from_translation(translation: Vector2<N>) -> Self {
+        Self {
+            x_axis: Self::X_AXIS,
+            y_axis: Self::Y_AXIS,
+            translation,
+        }
+    }
+
+    /// The determinant of the linear map: the factor it scales areas by, negative if it
+    /// mirrors.
+    pub fn determinant(&self) -> Number {
+        self.x_axis.cross(&self.y_axis)
+    }
+}
+
+impl<N: Zero> Affine2<N> {
+    pub fn identity() -> Self {
+        Self::from_translation(Vector2::new(N::zero(), N::zero()))
+    }
+
+    pub fn from_rotation(rotation: Rotation2) -> Self {
+        Self {
+            x_axis: rotation * Self::X_AXIS,
+            y_axis: rotation * Self::Y_AXIS,
+            ..Self::identity()
+        }
+    }
+
+    pub fn from_angle(angle: Angle) -> Self {
+        Self::from_rotation(Rotation2::from_angle(angle))
+    }
+
+    /// Scales along each axis, mirroring for negative factors.
+    pub fn from_scale(scale: Vector2<Number>) -> Self {
+        Self {
+            x_axis: Vector2::new(scale.x, Number::ZERO),
+            y_axis: Vector2::new(Number::ZERO, scale.y),
+            ..Self::identity()
+        }
+    }
+}
+
+impl<N> Affine2<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N>,
+{
+    /// Applies the linear map but not the translation, e.g. to a direction or offset.
+    pub fn transform_vector(&self, v: Vector2<N>) -> Vector2<N> {
+        Vector2::new(
+            v.x * self.x_axis.x + v.y * self.y_axis.x,
+            v.x * self.x_axis.y + v.y * self.y_axis.y,
+        )
+    }
+
+    pub fn transform_point(&self, p: Vector2<N>) -> Vector2<N> {
+        self.transform_vector(p) + self.translation
+    }
+
+    /// Returns the transform by both transforms, `self` then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            x_axis: other.transform_linear(self.x_axis),
+            y_axis: other.transform_linear(self.y_axis),
+            translation: other.transform_point(self.translation),
+        }
+    }
+
+    /// Rotates about a point, after this transform.
+    pub fn rotate(&self, angle: Angle, origin: Vector2<N>) -> Self
+    where
+        N: Zero + Sub<Output = N>,
+    {
+        let rotation = Self::from_angle(angle);
+        let pivot = origin - rotation.transform_vector(origin);
+        self.then(&Affine2 {
+            translation: pivot,
+            ..rotation
+        })
+    }
+
+    pub fn translate(&self, offset: Vector2<N>) -> Self {
+        Self {
+            translation: self.translation + offset,
+            ..*self
+        }
+    }
+
+    fn transform_linear(&self, v: Vector2<Number>) -> Vector2<Number> {
+        Vector2::new(
+            self.x_axis.x * v.x + self.y_axis.x * v.y,
+            self.x_axis.y * v.x + self.y_axis.y * v.y,
+        )
+    }
+}
+
+impl<N> Affine2<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N> + Neg<Output = N>,
+{
+    /// Returns the transform which undoes this one, or `None` if it collapses the plane
+    /// onto a line or point.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == Number::ZERO {
+            return None;
+        }
+        let x_axis = Vector2::new(self.y_axis.y, -self.x_axis.y) / determinant;
+        let y_axis = Vector2::new(-self.y_axis.x, self.x_axis.x) / determinant;
+        let linear = Self {
+            x_axis,
+            y_axis,
+            translation: self.translation,
+        };
+        Some(Self {
+            translation: -linear.transform_vector(self.translation),
+            ..linear
+        })
+    }
+}
+
+impl<N: Zero> Default for Affine2<N> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Composes transforms like matrices: `a * b` applies `b` then `a`.
+impl<N> Mul for Affine2<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        rhs.then(&self)
+    }
+}
+
+impl<N> Mul<Vector2<N>> for Affine2<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N>,
+{
+    type Output = Vector2<N>;
+
+    fn mul(self, rhs: Vector2<N>) -> Self::Output {
+        self.transform_point(rhs)
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Affine2<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.x_axis.approx_eq(&rhs.x_axis)
+            && self.y_axis.approx_eq(&rhs.y_axis)
+            && self.translation.approx_eq(&rhs.translation)
+    }
+}
+
+impl<N: Validate> Validate for Affine2<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.x_axis.validate()?;
+        self.y_axis.validate()?;
+        self.translation.validate()
+    }
+}
+
+impl<N> From<Affine2<N>> for glam::Affine2
+where
+    N: Into<f32>,
+{
+    fn from(value: Affine2<N>) -> Self {
+        glam::Affine2::from_cols(
+            value.x_axis.into(),
+            value.y_axis.into(),
+            value.translation.into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::{qty, Length};
+
+    use super::*;
+
+    #[test]
+    fn sheet_profile() {
+        // A 2 m × 1 m sheet, turned a quarter about its corner and moved along.
+        let corner = Vector2::new(qty!(2 m), qty!(1 m));
+        let transform = Affine2::<Length>::identity()
+            .rotate(qty!(90 deg), Vector2::new(qty!(2 m), qty!(0 m)))
+            .translate(Vector2::new(qty!(0 m), qty!(3 m)));
+        assert!(transform
+            .transform_point(corner)
+            .approx_eq(&Vector2::new(qty!(1 m), qty!(3 m))));
+        assert!(transform
+            .transform_vector(corner)
+            .approx_eq(&Vector2::new(qty!(-1 m), qty!(2 m))));
+
+        let inverse = transform.inverse().unwrap();
+        assert!((inverse * transform).approx_eq(&Affine2::identity()));
+        assert!((inverse * (transform * corner)).approx_eq(&corner));
+    }
+
+    #[test]
+    fn scale_and_determinant() {
+        let mirror = Affine2::<Length>::from_scale(Vector2::new(num!(-1), num!(2)));
+        assert_eq!(mirror.determinant(), num!(-2));
+        assert_eq!(
+            mirror * Vector2::new(qty!(1 m), qty!(1 m)),
+            Vector2::new(qty!(-1 m), qty!(2 m))
+        );
+        let flat = Affine2::<Length>::from_scale(Vector2::new(num!(1), num!(0)));
+        assert_eq!(flat.inverse(), None);
+    }
+}
@@ -0,0 +1,339 @@
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Neg};
+use villagekit_number::{
+    traits::{ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::Angle;
+
+use crate::{Quaternion, Vector3, X_AXIS, Y_AXIS, Z_AXIS};
+
+/// A 3D affine transform of points in `N`, e.g. `Length`: a linear map of rotation, scale
+/// and shear, followed by a translation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Affine3<N> {
+    /// Where the linear map takes the x axis.
+    pub x_axis: Vector3<Number>,
+    /// Where the linear map takes the y axis.
+    pub y_axis: Vector3<Number>,
+    /// Where the linear map takes the z axis.
+    pub z_axis: Vector3<Number>,
+    pub translation: Vector3<N>,
+}
+
+impl<N> Affine3<N> {
+    pub fn from_translation(translation: Vector3<N>) -> Self {
+        Self {
+            x_axis: X_AXIS,
+            y_axis: Y_AXIS,
+            z_axis: Z_AXIS,
+            translation,
+        }
+    }
+
+    /// Scales, then rotates, then translates.
+    pub fn from_scale_rotation_translation(
+        scale: Vector3<Number>,
+        rotation: Quaternion,
+        translation: Vector3<N>,
+    ) -> Self {
+        Self {
+            x_axis: rotation * (X_AXIS * scale.x),
+            y_axis: rotation * (Y_AXIS * scale.y),
+            z_axis: rotation * (Z_AXIS * scale.z),
+            translation,
+        }
+    }
+
+    /// The determinant of the linear map: the factor it scales volumes by, negative if it
+    /// mirrors.
+    pub fn determinant(&self) -> Number {
+        self.x_axis.dot(&self.y_axis.cross(&self.z_axis))
+    }
+
+    /// Splits the transform into the scale, rotation and translation it is made of, with a
+    /// mirror as a negative scale along x, or `None` if it collapses space onto a plane,
+    /// line or point. Shear is lost.
+    pub fn to_scale_rotation_translation(
+        self,
+    ) -> Option<(Vector3<Number>, Quaternion, Vector3<N>)> {
+        let determinant = self.determinant();
+        if determinant == Number::ZERO {
+            return None;
+        }
+        let sign = if determinant < Number::ZERO {
+            -Number::ONE
+        } else {
+            Number::ONE
+        };
+        let scale = Vector3::new(
+            self.x_axis.length() * sign,
+            self.y_axis.length(),
+            self.z_axis.length(),
+        );
+        let rotation = Quaternion::from_axes(
+            self.x_axis / scale.x,
+            self.y_axis / scale.y,
+            self.z_axis / scale.z,
+        );
+        Some((scale, rotation, self.translation))
+    }
+
+    pub fn map_translation<M>(self, mapper: impl Fn(N) -> M) -> Affine3<M> {
+        Affine3 {
+            x_axis: self.x_axis,
+            y_axis: self.y_axis,
+            z_axis: self.z_axis,
+            translation: self.translation.map(mapper),
+        }
+    }
+}
+
+impl<N: Zero> Affine3<N> {
+    pub fn identity() -> Self {
+        Self::from_translation(Vector3::new(N::zero(), N::zero(), N::zero()))
+    }
+
+    pub fn from_rotation(rotation: Quaternion) -> Self {
+        Self {
+            x_axis: rotation * X_AXIS,
+            y_axis: rotation * Y_AXIS,
+            z_axis: rotation * Z_AXIS,
+            ..Self::identity()
+        }
+    }
+
+    pub fn from_axis_angle(axis: Vector3<Number>, angle: Angle) -> Self {
+        Self::from_rotation(Quaternion::from_axis_angle(axis, angle))
+    }
+
+    /// Scales along each axis, mirroring for negative factors.
+    pub fn from_scale(scale: Vector3<Number>) -> Self {
+        Self {
+            x_axis: X_AXIS * scale.x,
+            y_axis: Y_AXIS * scale.y,
+            z_axis: Z_AXIS * scale.z,
+            ..Self::identity()
+        }
+    }
+}
+
+impl<N> Affine3<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N>,
+{
+    /// Applies the linear map but not the translation, e.g. to a direction or offset.
+    pub fn transform_vector(&self, v: Vector3<N>) -> Vector3<N> {
+        Vector3::new(
+            v.x * self.x_axis.x + v.y * self.y_axis.x + v.z * self.z_axis.x,
+            v.x * self.x_axis.y + v.y * self.y_axis.y + v.z * self.z_axis.y,
+            v.x * self.x_axis.z + v.y * self.y_axis.z + v.z * self.z_axis.z,
+        )
+    }
+
+    pub fn transform_point(&self, p: Vector3<N>) -> Vector3<N> {
+        self.transform_vector(p) + self.translation
+    }
+
+    /// Returns the transform by both transforms, `self` then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            x_axis: other.transform_linear(self.x_axis),
+            y_axis: other.transform_linear(self.y_axis),
+            z_axis: other.transform_linear(self.z_axis),
+            translation: other.transform_point(self.translation),
+        }
+    }
+
+    fn transform_linear(&self, v: Vector3<Number>) -> Vector3<Number> {
+        self.x_axis * v.x + self.y_axis * v.y + self.z_axis * v.z
+    }
+}
+
+impl<N> Affine3<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N> + Neg<Output = N>,
+{
+    /// Returns the transform which undoes this one, or `None` if it collapses space onto a
+    /// plane, line or point.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == Number::ZERO {
+            return None;
+        }
+        // The rows of the inverse are the cross products of the columns.
+        let rows = [
+            self.y_axis.cross(&self.z_axis) / determinant,
+            self.z_axis.cross(&self.x_axis) / determinant,
+            self.x_axis.cross(&self.y_axis) / determinant,
+        ];
+        let linear = Self {
+            x_axis: Vector3::new(rows[0].x, rows[1].x, rows[2].x),
+            y_axis: Vector3::new(rows[0].y, rows[1].y, rows[2].y),
+            z_axis: Vector3::new(rows[0].z, rows[1].z, rows[2].z),
+            translation: self.translation,
+        };
+        let translation = linear.transform_vector(self.translation);
+        Some(Self {
+            translation: Vector3::new(-translation.x, -translation.y, -translation.z),
+            ..linear
+        })
+    }
+}
+
+impl<N: Zero> Default for Affine3<N> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Composes transforms like matrices: `a * b` applies `b` then `a`.
+impl<N> Mul for Affine3<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        rhs.then(&self)
+    }
+}
+
+impl<N> Mul<Vector3<N>> for Affine3<N>
+where
+    N: Copy + Add<Output = N> + Mul<Number, Output = N>,
+{
+    type Output = Vector3<N>;
+
+    fn mul(self, rhs: Vector3<N>) -> Self::Output {
+        self.transform_point(rhs)
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Affine3<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.x_axis.approx_eq(&rhs.x_axis)
+            && self.y_axis.approx_eq(&rhs.y_axis)
+            && self.z_axis.approx_eq(&rhs.z_axis)
+            && self.translation.approx_eq(&rhs.translation)
+    }
+}
+
+impl<N: Validate> Validate for Affine3<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.x_axis.validate()?;
+        self.y_axis.validate()?;
+        self.z_axis.validate()?;
+        self.translation.validate()
+    }
+}
+
+impl<N> From<Affine3<N>> for glam::Affine3A
+where
+    N: Into<f32>,
+{
+    fn from(value: Affine3<N>) -> Self {
+        glam::Affine3A::from_cols(
+            glam::Vec3::from(value.x_axis).into(),
+            glam::Vec3::from(value.y_axis).into(),
+            glam::Vec3::from(value.z_axis).into(),
+            glam::Vec3::from(value.translation).into(),
+        )
+    }
+}
+
+impl<N> From<Affine3<N>> for glam::Mat4
+where
+    N: Into<f32>,
+{
+    fn from(value: Affine3<N>) -> Self {
+        glam::Mat4::from(glam::Affine3A::from(value))
+    }
+}
+
+impl TryFrom<glam::Affine3A> for Affine3<Number> {
+    type Error = NumberError;
+
+    fn try_from(value: glam::Affine3A) -> Result<Self, Self::Error> {
+        let vector = |v: glam::Vec3A| -> Result<Vector3<Number>, NumberError> {
+            Ok(Vector3::new(
+                v.x.try_into()?,
+                v.y.try_into()?,
+                v.z.try_into()?,
+            ))
+        };
+        Ok(Self {
+            x_axis: vector(value.matrix3.x_axis)?,
+            y_axis: vector(value.matrix3.y_axis)?,
+            z_axis: vector(value.matrix3.z_axis)?,
+            translation: vector(value.translation)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::num;
+    use villagekit_unit::{qty, Length};
+
+    use super::*;
+
+    #[test]
+    fn compose_and_invert() {
+        let turn = Affine3::<Length>::from_axis_angle(Z_AXIS, qty!(90 deg));
+        let lift = Affine3::from_translation(Vector3::new(qty!(0 m), qty!(0 m), qty!(1 m)));
+        let transform = lift * turn;
+        let point = Vector3::new(qty!(2 m), qty!(0 m), qty!(0 m));
+        assert!((transform * point).approx_eq(&Vector3::new(qty!(0 m), qty!(2 m), qty!(1 m))));
+        assert!(transform.transform_vector(point).approx_eq(&Vector3::new(
+            qty!(0 m),
+            qty!(2 m),
+            qty!(0 m)
+        )));
+
+        let inverse = transform.inverse().unwrap();
+        assert!((inverse * transform).approx_eq(&Affine3::identity()));
+        assert!((inverse * (transform * point)).approx_eq(&point));
+        assert_eq!(
+            Affine3::<Length>::from_scale(Vector3::new(num!(1), num!(0), num!(1))).inverse(),
+            None
+        );
+    }
+
+    #[test]
+    fn decompose() {
+        let scale = Vector3::new(num!(-2), num!(3), num!(0.5));
+        let rotation =
+            Quaternion::from_axis_angle(Vector3::new(num!(1), num!(2), num!(3)), qty!(40 deg));
+        let translation = Vector3::new(qty!(1 m), qty!(-2 m), qty!(3 m));
+        let transform = Affine3::from_scale_rotation_translation(scale, rotation, translation);
+        assert!(transform.determinant().approx_eq(&num!(-3)));
+
+        let (s, r, t) = transform.to_scale_rotation_translation().unwrap();
+        assert!(s.approx_eq(&scale));
+        assert!(r.approx_eq(&rotation));
+        assert_eq!(t, translation);
+
+        let flat = Affine3::from_scale_rotation_translation(
+            Vector3::new(num!(1), num!(0), num!(1)),
+            rotation,
+            translation,
+        );
+        assert_eq!(flat.to_scale_rotation_translation(), None);
+    }
+
+    #[test]
+    fn glam_round_trip() {
+        let transform = Affine3::<Length>::from_axis_angle(X_AXIS, qty!(90 deg)).then(
+            &Affine3::from_translation(Vector3::new(qty!(1 m), qty!(0 m), qty!(2 m))),
+        );
+        let glam: glam::Affine3A = transform.map_translation(|v| v.canonical()).into();
+        assert!(glam
+            .transform_point3(glam::Vec3::Y)
+            .abs_diff_eq(glam::Vec3::new(1.0, 0.0, 3.0), 1e-6));
+        let back = Affine3::try_from(glam)
+            .unwrap()
+            .map_translation(Length::from_canonical);
+        assert!(back.translation.approx_eq(&transform.translation));
+    }
+}
@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{Abs, ApproxEq, Euclid, Pow, Round, Trig, TrigInv, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{Angle, Area, Length};
+
+use crate::{curve::MAX_DEPTH, Curve, Polyline, Vector2};
+
+/// Part of a circle, e.g. a rounded corner: from `start` around the center by `sweep`,
+/// anticlockwise for a positive sweep.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Arc<N> {
+    pub center: Vector2<N>,
+    pub radius: N,
+    pub start: Angle,
+    pub sweep: Angle,
+}
+
+impl<N> Arc<N> {
+    pub const fn new(center: Vector2<N>, radius: N, start: Angle, sweep: Angle) -> Self {
+        Self {
+            center,
+            radius,
+            start,
+            sweep,
+        }
+    }
+}
+
+impl Arc<Length> {
+    /// Creates the arc from `start` through `through` to `end`, or `None` if they are in a
+    /// line.
+    pub fn from_three_points(
+        start: Vector2<Length>,
+        through: Vector2<Length>,
+        end: Vector2<Length>,
+    ) -> Option<Self> {
+        let (b, c) = (through - start, end - start);
+        let twice_area = b.cross(&c);
+        if twice_area == Area::zero() {
+            return None;
+        }
+        // The circumcenter, relative to `start`.
+        let (bb, cc) = (b.dot(&b), c.dot(&c));
+        let offset = Vector2::new(
+            (c.y * bb - b.y * cc) / (twice_area * Number::TWO),
+            (b.x * cc - c.x * bb) / (twice_area * Number::TWO),
+        );
+        let center = start + offset;
+        let angle_of = |point: Vector2<Length>| {
+            let offset = point - center;
+            Angle::from_canonical(offset.y.canonical().atan2(offset.x.canonical()))
+        };
+        let start_angle = angle_of(start);
+        let full_turn = Number::PI * Number::TWO;
+        // Anticlockwise when `through` is to the left of the way from start to end.
+        let anticlockwise = (angle_of(end) - start_angle)
+            .canonical()
+            .rem_euclid(full_turn);
+        let sweep = if twice_area > Area::zero() {
+            anticlockwise
+        } else {
+            anticlockwise - full_turn
+        };
+        Some(Self::new(
+            center,
+            offset.length(),
+            start_angle,
+            Angle::from_canonical(sweep),
+        ))
+    }
+
+    /// The point at an angle around the center.
+    fn point_at_angle(&self, angle: Angle) -> Vector2<Length> {
+        let (sin, cos) = angle.sin_cos();
+        self.center + Vector2::new(self.radius * cos, self.radius * sin)
+    }
+}
+
+impl Curve for Arc<Length> {
+    fn at(&self, t: Number) -> Vector2<Length> {
+        self.point_at_angle(self.start + self.sweep * t)
+    }
+
+    fn derivative(&self, t: Number) -> Vector2<Length> {
+        let (sin, cos) = (self.start + self.sweep * t).sin_cos();
+        let speed = self.radius * self.sweep.canonical();
+        Vector2::new(-speed * sin, speed * cos)
+    }
+
+    fn split(&self, t: Number) -> (Self, Self) {
+        let before = self.sweep * t;
+        (
+            Self::new(self.center, self.radius, self.start, before),
+            Self::new(
+                self.center,
+                self.radius,
+                self.start + before,
+                self.sweep - before,
+            ),
+        )
+    }
+
+    fn length(&self) -> Length {
+        self.radius * self.sweep.canonical().abs()
+    }
+
+    /// Splits the arc into equal steps, each as long as the tolerance allows, but no more
+    /// steps than [`Curve::flatten`] would split a curve into.
+    fn flatten(&self, tolerance: Length) -> Polyline<Length> {
+        let max_steps = Number::TWO.powi(MAX_DEPTH as i32);
+        let radius = self.radius.abs();
+        let steps = if radius == Length::zero() {
+            Number::ONE
+        } else {
+            // A step of angle θ strays r(1 - cos(θ/2)) from the arc.
+            let ratio = (Number::ONE - tolerance / radius).clamp(Number::ZERO, Number::ONE);
+            let step = ratio.acos() * Number::TWO;
+            if step == Number::ZERO {
+                max_steps
+            } else {
+                (self.sweep.canonical().abs() / step)
+                    .ceil()
+                    .clamp(Number::ONE, max_steps)
+            }
+        };
+        let mut points = vec![self.at(Number::ZERO)];
+        let mut i = Number::ZERO;
+        while i < steps {
+            i = i + Number::ONE;
+            points.push(self.at(i / steps));
+        }
+        Polyline::new(points)
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Arc<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.center.approx_eq(&rhs.center)
+            && self.radius.approx_eq(&rhs.radius)
+            && self.start.approx_eq(&rhs.start)
+            && self.sweep.approx_eq(&rhs.sweep)
+    }
+}
+
+impl<N: Validate> Validate for Arc<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.center.validate()?;
+        self.radius.validate()?;
+        self.start.validate()?;
+        self.sweep.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::num;
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    #[test]
+    fn rounded_corner() {
+        // A quarter circle from +x round to +y.
+        let corner = Arc::new(
+            Vector2::new(qty!(1 m), qty!(1 m)),
+            qty!(0.5 m),
+            qty!(0 deg),
+            qty!(90 deg),
+        );
+        assert_eq!(corner.at(num!(0)), Vector2::new(qty!(1.5 m), qty!(1 m)));
+        assert!(corner
+            .at(num!(1))
+            .approx_eq(&Vector2::new(qty!(1 m), qty!(1.5 m))));
+        assert!(corner
+            .tangent(num!(0))
+            .approx_eq(&Vector2::new(num!(0), num!(1))));
+        assert!(corner.length().approx_eq(&(qty!(0.25 m) * Number::PI)));
+
+        let (first, second) = corner.split(num!(0.5));
+        assert!(first.sweep.approx_eq(&qty!(45 deg)));
+        assert!(second.start.approx_eq(&qty!(45 deg)));
+        assert!(first.at(num!(1)).approx_eq(&second.at(num!(0))));
+
+        let flat = corner.flatten(qty!(1 mm));
+        assert!(flat.points.len() > 2);
+        assert!(flat.points.last().unwrap().approx_eq(&corner.at(num!(1))));
+        assert!(flat
+            .points
+            .iter()
+            .all(|point| (*point - corner.center).length().approx_eq(&corner.radius)));
+        assert!(flat.length() < corner.length());
+        assert!(flat.length() > corner.length() * num!(0.99));
+    }
+
+    #[test]
+    fn flatten_degenerate() {
+        let corner = Arc::new(
+            Vector2::new(qty!(1 m), qty!(1 m)),
+            qty!(0.5 m),
+            qty!(0 deg),
+            qty!(90 deg),
+        );
+        // Less than no tolerance takes as many steps as none, capped rather than looping forever.
+        assert_eq!(corner.flatten(qty!(-1 mm)).points.len(), 2usize.pow(16) + 1);
+        // A tolerance wider than the circle needs a single step.
+        assert_eq!(corner.flatten(qty!(1 m)).points.len(), 2);
+
+        let point = Arc::new(
+            Vector2::new(qty!(1 m), qty!(1 m)),
+            qty!(0 m),
+            qty!(0 deg),
+            qty!(90 deg),
+        );
+        assert_eq!(
+            point.flatten(qty!(1 mm)).points,
+            vec![point.center, point.center]
+        );
+    }
+
+    #[test]
+    fn through_three_points() {
+        let arc = Arc::from_three_points(
+            Vector2::new(qty!(1 m), qty!(0 m)),
+            Vector2::new(qty!(0 m), qty!(1 m)),
+            Vector2::new(qty!(-1 m), qty!(0 m)),
+        )
+        .unwrap();
+        assert!(arc.approx_eq(&Arc::new(
+            Vector2::new(qty!(0 m), qty!(0 m)),
+            qty!(1 m),
+            qty!(0 deg),
+            qty!(180 deg),
+        )));
+
+        let clockwise = Arc::from_three_points(
+            Vector2::new(qty!(1 m), qty!(0 m)),
+            Vector2::new(qty!(0 m), qty!(-1 m)),
+            Vector2::new(qty!(-1 m), qty!(0 m)),
+        )
+        .unwrap();
+        assert!(clockwise.sweep.approx_eq(&qty!(-180 deg)));
+
+        let line = Arc::from_three_points(
+            Vector2::new(qty!(0 m), qty!(0 m)),
+            Vector2::new(qty!(1 m), qty!(0 m)),
+            Vector2::new(qty!(2 m), qty!(0 m)),
+        );
+        assert_eq!(line, None);
+    }
+}
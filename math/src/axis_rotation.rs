@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+use std::ops::{Mul, Neg};
+use villagekit_number::{
+    num,
+    traits::{Abs, ApproxEq, Euclid, Round, Sqrt},
+    Number,
+};
+use villagekit_unit::{Angle, Rotations};
+
+use crate::{Quaternion, Vector3, X_AXIS, Y_AXIS, Z_AXIS};
+
+/// One of the six directions along the axes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AxisDirection {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl AxisDirection {
+    pub const ALL: [Self; 6] = [
+        Self::PosX,
+        Self::NegX,
+        Self::PosY,
+        Self::NegY,
+        Self::PosZ,
+        Self::NegZ,
+    ];
+
+    fn new(index: usize, negative: bool) -> Self {
+        match (index, negative) {
+            (0, false) => Self::PosX,
+            (0, true) => Self::NegX,
+            (1, false) => Self::PosY,
+            (1, true) => Self::NegY,
+            (2, false) => Self::PosZ,
+            (2, true) => Self::NegZ,
+            _ => unreachable!("axis index {index} out of range"),
+        }
+    }
+
+    /// Returns the direction of a vector along an axis, or `None` for any other vector.
+    pub fn from_vector(v: Vector3<Number>) -> Option<Self> {
+        let components = [v.x, v.y, v.z];
+        let mut nonzero = (0..3).filter(|&i| components[i] != Number::ZERO);
+        let index = nonzero.next()?;
+        if nonzero.next().is_some() {
+            return None;
+        }
+        Some(Self::new(index, components[index] < Number::ZERO))
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::PosX | Self::NegX => 0,
+            Self::PosY | Self::NegY => 1,
+            Self::PosZ | Self::NegZ => 2,
+        }
+    }
+
+    pub fn is_negative(self) -> bool {
+        matches!(self, Self::NegX | Self::NegY | Self::NegZ)
+    }
+
+    pub fn to_vector(self) -> Vector3<Number> {
+        let axis = [X_AXIS, Y_AXIS, Z_AXIS][self.index()];
+        if self.is_negative() {
+            -Number::ONE * axis
+        } else {
+            axis
+        }
+    }
+
+    /// The component of `v` along this direction.
+    fn component<N: Copy + Neg<Output = N>>(self, v: Vector3<N>) -> N {
+        let value = [v.x, v.y, v.z][self.index()];
+        if self.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+impl Neg for AxisDirection {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.index(), !self.is_negative())
+    }
+}
+
+/// A rotation by quarter turns about the axes, perhaps mirrored: one of the 48 ways to
+/// turn a grid onto itself.
+///
+/// Unlike a [`Quaternion`], it is exact, so rotations compose and apply with no rounding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AxisRotation {
+    x_axis: AxisDirection,
+    y_axis: AxisDirection,
+    z_axis: AxisDirection,
+}
+
+impl AxisRotation {
+    pub const IDENTITY: Self = Self {
+        x_axis: AxisDirection::PosX,
+        y_axis: AxisDirection::PosY,
+        z_axis: AxisDirection::PosZ,
+    };
+
+    /// Creates the rotation which takes the x, y and z axes to the given directions, or
+    /// `None` if two of them are along the same axis.
+    pub fn from_axes(
+        x_axis: AxisDirection,
+        y_axis: AxisDirection,
+        z_axis: AxisDirection,
+    ) -> Option<Self> {
+        let distinct = x_axis.index() != y_axis.index()
+            && y_axis.index() != z_axis.index()
+            && z_axis.index() != x_axis.index();
+        distinct.then_some(Self {
+            x_axis,
+            y_axis,
+            z_axis,
+        })
+    }
+
+    /// All 48 rotations: the 24 orientations of a cube and their mirror images.
+    pub fn all() -> impl Iterator<Item = Self> {
+        AxisDirection::ALL.into_iter().flat_map(|x_axis| {
+            AxisDirection::ALL.into_iter().flat_map(move |y_axis| {
+                AxisDirection::ALL
+                    .into_iter()
+                    .filter_map(move |z_axis| Self::from_axes(x_axis, y_axis, z_axis))
+            })
+        })
+    }
+
+    /// Creates the rotation a quarter turn anticlockwise about `axis`, looking back along it.
+    pub fn quarter_turn(axis: AxisDirection) -> Self {
+        let i = axis.index();
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        let mut axes = [AxisDirection::PosX; 3];
+        axes[i] = AxisDirection::new(i, false);
+        axes[j] = AxisDirection::new(k, false);
+        axes[k] = AxisDirection::new(j, true);
+        let turn = Self::from_columns(axes);
+        if axis.is_negative() {
+            turn.inverse()
+        } else {
+            turn
+        }
+    }
+
+    /// Creates the mirror across the plane square to `axis`.
+    pub fn mirror(axis: AxisDirection) -> Self {
+        let mut axes = Self::IDENTITY.columns();
+        axes[axis.index()] = -axes[axis.index()];
+        Self::from_columns(axes)
+    }
+
+    /// Creates the rotation about an axis by a whole number of quarter turns, or `None` if
+    /// the axis isn't along x, y or z or the angle isn't (approximately) a quarter turn
+    /// multiple.
+    pub fn from_axis_angle(axis: Vector3<Number>, angle: Angle) -> Option<Self> {
+        let axis = AxisDirection::from_vector(axis)?;
+        let quarter_turns = angle.to::<Rotations>() * num!(4);
+        let whole_turns = quarter_turns.round();
+        if !whole_turns.approx_eq(&quarter_turns) {
+            return None;
+        }
+        let turn = Self::quarter_turn(axis);
+        let mut rotation = Self::IDENTITY;
+        let mut remaining = whole_turns.rem_euclid(num!(4));
+        while remaining > Number::ZERO {
+            rotation = rotation * turn;
+            remaining = remaining - Number::ONE;
+        }
+        Some(rotation)
+    }
+
+    /// Snaps a quaternion to the rotation it is (approximately), or `None` if it isn't one
+    /// of quarter turns.
+    pub fn from_quaternion(rotation: Quaternion) -> Option<Self> {
+        let snap = |axis: Vector3<Number>| {
+            let v = rotation * axis;
+            let snapped = v.map(Round::round);
+            if snapped.approx_eq(&v) {
+                AxisDirection::from_vector(snapped)
+            } else {
+                None
+            }
+        };
+        Self::from_axes(snap(X_AXIS)?, snap(Y_AXIS)?, snap(Z_AXIS)?)
+    }
+
+    pub fn x_axis(self) -> AxisDirection {
+        self.x_axis
+    }
+
+    pub fn y_axis(self) -> AxisDirection {
+        self.y_axis
+    }
+
+    pub fn z_axis(self) -> AxisDirection {
+        self.z_axis
+    }
+
+    /// Whether the rotation turns the grid into its mirror image, i.e. it is not a proper
+    /// rotation.
+    pub fn is_mirror(self) -> bool {
+        self.determinant() < Number::ZERO
+    }
+
+    /// The determinant of the rotation: `1`, or `-1` if it mirrors.
+    pub fn determinant(self) -> Number {
+        let [x_axis, y_axis, z_axis] = self.columns().map(AxisDirection::to_vector);
+        x_axis.dot(&y_axis.cross(&z_axis))
+    }
+
+    /// Returns the rotation by both rotations, `other` then `self`.
+    pub fn multiply(self, other: AxisRotation) -> Self {
+        Self::from_columns(other.columns().map(|axis| self.rotate_direction(axis)))
+    }
+
+    /// Returns the rotation back by the same turns.
+    pub fn inverse(self) -> Self {
+        let mut axes = [AxisDirection::PosX; 3];
+        for (index, axis) in self.columns().into_iter().enumerate() {
+            axes[axis.index()] = AxisDirection::new(index, axis.is_negative());
+        }
+        Self::from_columns(axes)
+    }
+
+    pub fn rotate<N: Copy + Neg<Output = N>>(self, v: Vector3<N>) -> Vector3<N> {
+        let [x, y, z] = self.inverse().columns().map(|axis| axis.component(v));
+        Vector3::new(x, y, z)
+    }
+
+    pub fn rotate_direction(self, direction: AxisDirection) -> AxisDirection {
+        let axis = self.columns()[direction.index()];
+        if direction.is_negative() {
+            -axis
+        } else {
+            axis
+        }
+    }
+
+    /// Returns the same rotation as a quaternion, or `None` if it mirrors.
+    ///
+    /// Each rotation always gives the same quaternion, with components of `0`, `±½`, `±√½`
+    /// or `±1`, and the first non-zero of `w`, `x`, `y`, `z` positive.
+    pub fn to_quaternion(self) -> Option<Quaternion> {
+        if self.is_mirror() {
+            return None;
+        }
+        let [x_axis, y_axis, z_axis] = self.columns().map(AxisDirection::to_vector);
+        let Quaternion { x, y, z, w } = Quaternion::from_axes(x_axis, y_axis, z_axis);
+        let magnitudes = [Number::ZERO, Number::HALF, Number::HALF.sqrt(), Number::ONE];
+        let snap = |value: Number| {
+            let magnitude = magnitudes
+                .into_iter()
+                .min_by_key(|magnitude| (value.abs() - *magnitude).abs())
+                .unwrap();
+            if value < Number::ZERO {
+                -magnitude
+            } else {
+                magnitude
+            }
+        };
+        let [w, x, y, z] = [w, x, y, z].map(snap);
+        let first = [w, x, y, z].into_iter().find(|c| *c != Number::ZERO);
+        let sign = if first.is_some_and(|c| c < Number::ZERO) {
+            -Number::ONE
+        } else {
+            Number::ONE
+        };
+        Some(Quaternion::new(x, y, z, w).multiply_scalar(sign))
+    }
+
+    fn columns(self) -> [AxisDirection; 3] {
+        [self.x_axis, self.y_axis, self.z_axis]
+    }
+
+    fn from_columns([x_axis, y_axis, z_axis]: [AxisDirection; 3]) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            z_axis,
+        }
+    }
+}
+
+impl Default for AxisRotation {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Composes rotations like matrices: `a * b` applies `b` then `a`.
+impl Mul for AxisRotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.multiply(rhs)
+    }
+}
+
+impl<N: Copy + Neg<Output = N>> Mul<Vector3<N>> for AxisRotation {
+    type Output = Vector3<N>;
+
+    fn mul(self, rhs: Vector3<N>) -> Self::Output {
+        self.rotate(rhs)
+    }
+}
+
+impl From<AxisRotation> for glam::Mat3 {
+    fn from(value: AxisRotation) -> Self {
+        let [x_axis, y_axis, z_axis] = value
+            .columns()
+            .map(|axis| glam::Vec3::from(axis.to_vector()));
+        glam::Mat3::from_cols(x_axis, y_axis, z_axis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use villagekit_unit::{qty, Length};
+
+    use super::*;
+
+    #[test]
+    fn group() {
+        let all: Vec<_> = AxisRotation::all().collect();
+        assert_eq!(all.len(), 48);
+        assert_eq!(all.iter().filter(|r| !r.is_mirror()).count(), 24);
+        for &a in &all {
+            assert_eq!(a * a.inverse(), AxisRotation::IDENTITY);
+            for &b in &all {
+                assert!(all.contains(&(a * b)));
+                assert_eq!(a.is_mirror() != b.is_mirror(), (a * b).is_mirror());
+            }
+        }
+
+        let turn = AxisRotation::quarter_turn(AxisDirection::PosZ);
+        assert_eq!(
+            turn.rotate_direction(AxisDirection::PosX),
+            AxisDirection::PosY
+        );
+        assert_eq!(turn * turn * turn * turn, AxisRotation::IDENTITY);
+        assert_eq!(
+            turn.inverse(),
+            AxisRotation::quarter_turn(AxisDirection::NegZ)
+        );
+        let mirror = AxisRotation::mirror(AxisDirection::NegY);
+        assert!(mirror.is_mirror());
+        assert_eq!(mirror * mirror, AxisRotation::IDENTITY);
+        assert_eq!(mirror.to_quaternion(), None);
+    }
+
+    #[test]
+    fn exact_points() {
+        let point: Vector3<Length> = Vector3::new(qty!(0.1 m), qty!(0.2 m), qty!(0.3 m));
+        let turn = AxisRotation::from_axis_angle(Y_AXIS, -Rotations::QUARTER).unwrap();
+        assert_eq!(
+            turn * point,
+            Vector3::new(qty!(-0.3 m), qty!(0.2 m), qty!(0.1 m))
+        );
+        assert_eq!(turn.inverse() * (turn * point), point);
+        assert_eq!(
+            AxisRotation::from_axis_angle(-Number::ONE * Z_AXIS, qty!(270 deg)),
+            Some(AxisRotation::quarter_turn(AxisDirection::PosZ))
+        );
+        assert_eq!(
+            AxisRotation::from_axis_angle(Z_AXIS, qty!(0 deg)),
+            Some(AxisRotation::IDENTITY)
+        );
+        assert_eq!(AxisRotation::from_axis_angle(Z_AXIS, qty!(45 deg)), None);
+        assert_eq!(
+            AxisRotation::from_axis_angle(Vector3::new(num!(1), num!(1), num!(0)), qty!(90 deg)),
+            None
+        );
+    }
+
+    #[test]
+    fn quaternions() {
+        let mut quaternions = BTreeSet::new();
+        for rotation in AxisRotation::all().filter(|r| !r.is_mirror()) {
+            let quaternion = rotation.to_quaternion().unwrap();
+            for axis in [X_AXIS, Y_AXIS, Z_AXIS] {
+                assert!((quaternion * axis).approx_eq(&(rotation * axis)));
+            }
+            assert_eq!(AxisRotation::from_quaternion(quaternion), Some(rotation));
+            quaternions.insert(quaternion);
+        }
+        assert_eq!(quaternions.len(), 24);
+
+        let turn = AxisRotation::quarter_turn(AxisDirection::PosX);
+        let quaternion = turn.to_quaternion().unwrap();
+        assert_eq!(quaternion.x, quaternion.w);
+        assert_eq!(
+            (turn * turn).to_quaternion(),
+            Some(Quaternion::new(num!(1), num!(0), num!(0), num!(0)))
+        );
+        assert_eq!(
+            AxisRotation::from_quaternion(Quaternion::from_axis_angle(X_AXIS, qty!(30 deg))),
+            None
+        );
+
+        let glam: glam::Mat3 = AxisRotation::quarter_turn(AxisDirection::PosZ).into();
+        assert_eq!(glam * glam::Vec3::X, glam::Vec3::Y);
+        let glam = glam::Quat::from(quaternion);
+        assert!((glam * glam::Vec3::Y).abs_diff_eq(glam::Vec3::Z, 1e-6));
+    }
+}
@@ -0,0 +1,397 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::Length;
+
+use crate::{curve::length_between, Curve, Vector2};
+
+/// A smooth curve pulled towards a list of control points, passing through only the first
+/// and last, e.g. a long flowing outline.
+///
+/// Each piece of the curve is a polynomial of `degree` in the knots between it and the next,
+/// and `t` runs from `0` to `1` over the knots from `knots[degree]` to
+/// `knots[points.len()]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Fields<N>")]
+pub struct BSpline<N> {
+    degree: usize,
+    points: Vec<Vector2<N>>,
+    knots: Vec<Number>,
+}
+
+impl<N> BSpline<N> {
+    /// Creates the spline with evenly spaced knots, clamped so it starts and ends at the first
+    /// and last points, or `None` if there are not more points than the degree.
+    pub fn new(degree: usize, points: Vec<Vector2<N>>) -> Option<Self> {
+        if points.len() <= degree {
+            return None;
+        }
+        let pieces = points.len() - degree;
+        let spacing = Number::ONE / Number::from(pieces);
+        let mut knots = vec![Number::ZERO; degree + 1];
+        let mut knot = Number::ZERO;
+        for _ in 1..pieces {
+            knot = knot + spacing;
+            knots.push(knot);
+        }
+        knots.extend(vec![Number::ONE; degree + 1]);
+        Some(Self {
+            degree,
+            points,
+            knots,
+        })
+    }
+
+    /// Creates the spline with the given knots, or `None` unless there are more points than
+    /// the degree and the knots are in order, one more of them than points and degree.
+    pub fn with_knots(degree: usize, points: Vec<Vector2<N>>, knots: Vec<Number>) -> Option<Self> {
+        valid_knots(degree, points.len(), &knots).then_some(Self {
+            degree,
+            points,
+            knots,
+        })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    pub fn points(&self) -> &[Vector2<N>] {
+        &self.points
+    }
+
+    pub fn knots(&self) -> &[Number] {
+        &self.knots
+    }
+}
+
+/// Whether the knots fit the degree and number of points, see [`BSpline::with_knots`].
+fn valid_knots(degree: usize, points: usize, knots: &[Number]) -> bool {
+    points > degree
+        && knots.len() == points + degree + 1
+        && knots.windows(2).all(|pair| pair[0] <= pair[1])
+        && knots[degree] < knots[points]
+}
+
+/// The fields of a [`BSpline`] as deserialized, before their knots are checked.
+#[derive(Deserialize)]
+struct Fields<N> {
+    degree: usize,
+    points: Vec<Vector2<N>>,
+    knots: Vec<Number>,
+}
+
+impl<N> TryFrom<Fields<N>> for BSpline<N> {
+    type Error = &'static str;
+
+    fn try_from(fields: Fields<N>) -> Result<Self, Self::Error> {
+        Self::with_knots(fields.degree, fields.points, fields.knots)
+            .ok_or("the knots do not fit the degree and points")
+    }
+}
+
+impl BSpline<Length> {
+    /// The knot value of the parameter `t`.
+    fn knot_at(&self, t: Number) -> Number {
+        let (start, end) = (self.knots[self.degree], self.knots[self.points.len()]);
+        start + (end - start) * t
+    }
+
+    /// The index of the last knot at or before `u`, among those starting a piece.
+    fn span(&self, u: Number) -> usize {
+        (self.degree..self.points.len())
+            .rev()
+            .find(|&i| self.knots[i] <= u)
+            .unwrap_or(self.degree)
+    }
+
+    /// The point at the knot value `u`, by de Boor's algorithm.
+    fn point_at_knot(&self, u: Number) -> Vector2<Length> {
+        let (degree, span) = (self.degree, self.span(u));
+        let mut points: Vec<_> = self.points[span - degree..=span].to_vec();
+        for r in 1..=degree {
+            for j in (r..=degree).rev() {
+                let i = j + span - degree;
+                let alpha = ratio(
+                    u - self.knots[i],
+                    self.knots[i + degree + 1 - r] - self.knots[i],
+                );
+                points[j] = points[j - 1] + (points[j] - points[j - 1]) * alpha;
+            }
+        }
+        points[degree]
+    }
+
+    /// Inserts the knot `u` without changing the shape of the curve, by Boehm's algorithm.
+    fn insert_knot(&mut self, u: Number) {
+        let (degree, span) = (self.degree, self.span(u));
+        let mut points = Vec::with_capacity(self.points.len() + 1);
+        points.extend_from_slice(&self.points[..=span - degree]);
+        for i in span - degree + 1..=span {
+            let alpha = ratio(u - self.knots[i], self.knots[i + degree] - self.knots[i]);
+            points.push(self.points[i - 1] + (self.points[i] - self.points[i - 1]) * alpha);
+        }
+        points.extend_from_slice(&self.points[span..]);
+        self.points = points;
+        self.knots.insert(span + 1, u);
+    }
+
+    /// The spline which stays at one point, e.g. the part before the start.
+    fn point(degree: usize, point: Vector2<Length>) -> Self {
+        let mut knots = vec![Number::ZERO; degree + 1];
+        knots.extend(vec![Number::ONE; degree + 1]);
+        Self {
+            degree,
+            points: vec![point; degree + 1],
+            knots,
+        }
+    }
+}
+
+impl Curve for BSpline<Length> {
+    fn at(&self, t: Number) -> Vector2<Length> {
+        self.point_at_knot(self.knot_at(t))
+    }
+
+    fn derivative(&self, t: Number) -> Vector2<Length> {
+        if self.degree == 0 {
+            return Vector2::new(Length::zero(), Length::zero());
+        }
+        // The derivative of a spline is a spline of one degree less.
+        let degree = self.degree;
+        let points = (0..self.points.len() - 1)
+            .map(|i| {
+                let spacing = self.knots[i + degree + 1] - self.knots[i + 1];
+                (self.points[i + 1] - self.points[i]) * ratio(Number::from(degree), spacing)
+            })
+            .collect();
+        let derivative = Self {
+            degree: degree - 1,
+            points,
+            knots: self.knots[1..self.knots.len() - 1].to_vec(),
+        };
+        let (start, end) = (self.knots[degree], self.knots[self.points.len()]);
+        derivative.point_at_knot(self.knot_at(t)) * (end - start)
+    }
+
+    fn split(&self, t: Number) -> (Self, Self) {
+        let u = self.knot_at(t);
+        let (start, end) = (self.knots[self.degree], self.knots[self.points.len()]);
+        if u <= start {
+            return (Self::point(self.degree, self.at(t)), self.clone());
+        }
+        if u >= end {
+            return (self.clone(), Self::point(self.degree, self.at(t)));
+        }
+        // With the knot repeated for every degree, the curve passes through the control
+        // point there, and the points either side of it make up each part.
+        let mut split = self.clone();
+        let existing = self.knots.iter().filter(|&&knot| knot == u).count();
+        for _ in existing..self.degree {
+            split.insert_knot(u);
+        }
+        let first = split.knots.iter().position(|&knot| knot == u).unwrap();
+        let degree = self.degree;
+
+        let mut before_knots = split.knots[..first + degree].to_vec();
+        before_knots.push(u);
+        let before = Self {
+            degree,
+            points: split.points[..first].to_vec(),
+            knots: before_knots,
+        };
+        let mut after_knots = vec![u];
+        after_knots.extend_from_slice(&split.knots[first..]);
+        let after = Self {
+            degree,
+            points: split.points[first - 1..].to_vec(),
+            knots: after_knots,
+        };
+        (before, after)
+    }
+
+    /// The length of each piece in turn, as the curve may bend sharply where they meet.
+    fn length(&self) -> Length {
+        let (start, end) = (self.knots[self.degree], self.knots[self.points.len()]);
+        let to_t = |knot: Number| (knot - start) / (end - start);
+        self.knots[self.degree..=self.points.len()]
+            .windows(2)
+            .filter(|pair| pair[0] < pair[1])
+            .fold(Length::zero(), |sum, pair| {
+                sum + length_between(self, to_t(pair[0]), to_t(pair[1]))
+            })
+    }
+}
+
+/// `numerator / denominator`, or zero between repeated knots.
+fn ratio(numerator: Number, denominator: Number) -> Number {
+    if denominator == Number::ZERO {
+        Number::ZERO
+    } else {
+        numerator / denominator
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for BSpline<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.degree == rhs.degree
+            && self.points.len() == rhs.points.len()
+            && self.knots.len() == rhs.knots.len()
+            && self
+                .points
+                .iter()
+                .zip(&rhs.points)
+                .all(|(a, b)| a.approx_eq(b))
+            && self
+                .knots
+                .iter()
+                .zip(&rhs.knots)
+                .all(|(a, b)| a.approx_eq(b))
+    }
+}
+
+impl<N: Validate> Validate for BSpline<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.points.iter().try_for_each(Validate::validate)?;
+        self.knots.iter().try_for_each(Validate::validate)?;
+        if valid_knots(self.degree, self.points.len(), &self.knots) {
+            Ok(())
+        } else {
+            Err(NumberError::Domain)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::num;
+    use villagekit_unit::qty;
+
+    use super::*;
+    use crate::{CubicBezier, QuadraticBezier};
+
+    macro_rules! point {
+        ($x:literal, $y:literal) => {
+            Vector2::new(qty!($x m), qty!($y m))
+        };
+    }
+
+    #[test]
+    fn matches_bezier() {
+        // With as many points as one more than the degree, a clamped spline is a Bézier.
+        let points = vec![point!(0, 0), point!(0, 1), point!(1, 2), point!(2, 2)];
+        let spline = BSpline::new(3, points.clone()).unwrap();
+        assert_eq!(
+            spline.knots,
+            [
+                num!(0),
+                num!(0),
+                num!(0),
+                num!(0),
+                num!(1),
+                num!(1),
+                num!(1),
+                num!(1)
+            ]
+        );
+        let bezier = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        for t in [num!(0), num!(0.25), num!(0.5), num!(0.8), num!(1)] {
+            assert!(spline.at(t).approx_eq(&bezier.at(t)));
+            assert!(spline.derivative(t).approx_eq(&bezier.derivative(t)));
+        }
+        assert!(spline.length().approx_eq(&bezier.length()));
+
+        let (before, after) = spline.split(num!(0.25));
+        let (bezier_before, bezier_after) = bezier.split(num!(0.25));
+        assert!(before.at(num!(0.5)).approx_eq(&bezier_before.at(num!(0.5))));
+        assert!(after.at(num!(0.5)).approx_eq(&bezier_after.at(num!(0.5))));
+
+        assert_eq!(BSpline::new(3, points[..3].to_vec()), None);
+    }
+
+    #[test]
+    fn pieces() {
+        // A quadratic spline through the middle of each leg of its control polygon.
+        let spline = BSpline::new(
+            2,
+            vec![point!(0, 0), point!(2, 2), point!(4, 0), point!(6, 2)],
+        )
+        .unwrap();
+        assert_eq!(
+            spline.knots,
+            [
+                num!(0),
+                num!(0),
+                num!(0),
+                num!(0.5),
+                num!(1),
+                num!(1),
+                num!(1)
+            ]
+        );
+        assert_eq!(spline.at(num!(0)), point!(0, 0));
+        assert_eq!(spline.at(num!(0.5)), point!(3, 1));
+        assert_eq!(spline.at(num!(1)), point!(6, 2));
+        let first = QuadraticBezier::new(point!(0, 0), point!(2, 2), point!(3, 1));
+        assert!(spline.at(num!(0.25)).approx_eq(&first.at(num!(0.5))));
+        assert!(spline
+            .tangent(num!(0.5))
+            .approx_eq(&Vector2::new(num!(1), num!(-1)).normalize()));
+
+        for t in [num!(0), num!(0.25), num!(0.5), num!(0.75), num!(1)] {
+            let (before, after) = spline.split(t);
+            assert_eq!(before.validate(), Ok(()));
+            assert_eq!(after.validate(), Ok(()));
+            assert!(before.at(num!(1)).approx_eq(&spline.at(t)));
+            assert!(after.at(num!(0)).approx_eq(&spline.at(t)));
+            assert!((before.length() + after.length()).approx_eq(&spline.length()));
+        }
+        let (whole, end) = spline.split(num!(1));
+        assert_eq!(whole, spline);
+        assert_eq!(end.at(num!(0.5)), point!(6, 2));
+        assert_eq!(end.length(), qty!(0 m));
+        let json = serde_json::to_string(&end).unwrap();
+        assert_eq!(serde_json::from_str::<BSpline<Length>>(&json).unwrap(), end);
+        let (start, whole) = spline.split(num!(0));
+        assert_eq!(whole, spline);
+        assert_eq!(start.at(num!(0.5)), point!(0, 0));
+
+        let flat = spline.flatten(qty!(1 mm));
+        assert_eq!(flat.points.first(), Some(&point!(0, 0)));
+        assert_eq!(flat.points.last(), Some(&point!(6, 2)));
+        assert!(flat.length() <= spline.length());
+    }
+
+    #[test]
+    fn knots_checked() {
+        let points = vec![point!(0, 0), point!(2, 2), point!(4, 0)];
+        let knots = vec![num!(0), num!(0), num!(0), num!(1), num!(1), num!(1)];
+        let spline = BSpline::with_knots(2, points.clone(), knots.clone()).unwrap();
+        assert_eq!(spline.validate(), Ok(()));
+
+        let json = serde_json::to_string(&spline).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BSpline<Length>>(&json).unwrap(),
+            spline
+        );
+        let backwards = json.replace(
+            r#"["0","0","0","1","1","1"]"#,
+            r#"["1","1","1","0","0","0"]"#,
+        );
+        assert_ne!(backwards, json);
+        assert!(serde_json::from_str::<BSpline<Length>>(&backwards).is_err());
+
+        assert_eq!(
+            BSpline::with_knots(2, points.clone(), knots[1..].to_vec()),
+            None
+        );
+        let missing = BSpline {
+            degree: 2,
+            points,
+            knots: vec![],
+        };
+        assert_eq!(missing.validate(), Err(NumberError::Domain));
+    }
+}
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    num,
+    traits::{ApproxEq, Validate},
+    Number, NumberError,
+};
+use villagekit_unit::Length;
+
+use crate::{Curve, Vector2};
+
+/// A curve from `start` to `end`, pulled towards one control point, e.g. a simple bend.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct QuadraticBezier<N> {
+    pub start: Vector2<N>,
+    pub control: Vector2<N>,
+    pub end: Vector2<N>,
+}
+
+impl<N> QuadraticBezier<N> {
+    pub const fn new(start: Vector2<N>, control: Vector2<N>, end: Vector2<N>) -> Self {
+        Self {
+            start,
+            control,
+            end,
+        }
+    }
+}
+
+impl QuadraticBezier<Length> {
+    /// The same curve as a cubic Bézier.
+    pub fn to_cubic(&self) -> CubicBezier<Length> {
+        let third = Number::ONE / num!(3);
+        CubicBezier::new(
+            self.start,
+            self.start + (self.control - self.start) * (third * Number::TWO),
+            self.end + (self.control - self.end) * (third * Number::TWO),
+            self.end,
+        )
+    }
+}
+
+impl Curve for QuadraticBezier<Length> {
+    fn at(&self, t: Number) -> Vector2<Length> {
+        let (start, end) = (
+            lerp(self.start, self.control, t),
+            lerp(self.control, self.end, t),
+        );
+        lerp(start, end, t)
+    }
+
+    fn derivative(&self, t: Number) -> Vector2<Length> {
+        lerp(self.control - self.start, self.end - self.control, t) * Number::TWO
+    }
+
+    fn split(&self, t: Number) -> (Self, Self) {
+        // de Casteljau's algorithm: the points between the points make up both halves.
+        let (a, b) = (
+            lerp(self.start, self.control, t),
+            lerp(self.control, self.end, t),
+        );
+        let middle = lerp(a, b, t);
+        (
+            Self::new(self.start, a, middle),
+            Self::new(middle, b, self.end),
+        )
+    }
+}
+
+/// A curve from `start` to `end`, leaving towards the first control point and arriving from
+/// the second, e.g. the curve of a chair back.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CubicBezier<N> {
+    pub start: Vector2<N>,
+    pub control1: Vector2<N>,
+    pub control2: Vector2<N>,
+    pub end: Vector2<N>,
+}
+
+impl<N> CubicBezier<N> {
+    pub const fn new(
+        start: Vector2<N>,
+        control1: Vector2<N>,
+        control2: Vector2<N>,
+        end: Vector2<N>,
+    ) -> Self {
+        Self {
+            start,
+            control1,
+            control2,
+            end,
+        }
+    }
+}
+
+impl Curve for CubicBezier<Length> {
+    fn at(&self, t: Number) -> Vector2<Length> {
+        QuadraticBezier::new(
+            lerp(self.start, self.control1, t),
+            lerp(self.control1, self.control2, t),
+            lerp(self.control2, self.end, t),
+        )
+        .at(t)
+    }
+
+    fn derivative(&self, t: Number) -> Vector2<Length> {
+        let hull = QuadraticBezier::new(
+            self.control1 - self.start,
+            self.control2 - self.control1,
+            self.end - self.control2,
+        );
+        hull.at(t) * num!(3)
+    }
+
+    fn split(&self, t: Number) -> (Self, Self) {
+        let (a, b, c) = (
+            lerp(self.start, self.control1, t),
+            lerp(self.control1, self.control2, t),
+            lerp(self.control2, self.end, t),
+        );
+        let (d, e) = (lerp(a, b, t), lerp(b, c, t));
+        let middle = lerp(d, e, t);
+        (
+            Self::new(self.start, a, d, middle),
+            Self::new(middle, e, c, self.end),
+        )
+    }
+}
+
+/// The point a fraction `t` of the way from `a` to `b`.
+fn lerp(a: Vector2<Length>, b: Vector2<Length>, t: Number) -> Vector2<Length> {
+    a + (b - a) * t
+}
+
+impl<N: ApproxEq> ApproxEq for QuadraticBezier<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.start.approx_eq(&rhs.start)
+            && self.control.approx_eq(&rhs.control)
+            && self.end.approx_eq(&rhs.end)
+    }
+}
+
+impl<N: Validate> Validate for QuadraticBezier<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.start.validate()?;
+        self.control.validate()?;
+        self.end.validate()
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for CubicBezier<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.start.approx_eq(&rhs.start)
+            && self.control1.approx_eq(&rhs.control1)
+            && self.control2.approx_eq(&rhs.control2)
+            && self.end.approx_eq(&rhs.end)
+    }
+}
+
+impl<N: Validate> Validate for CubicBezier<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.start.validate()?;
+        self.control1.validate()?;
+        self.control2.validate()?;
+        self.end.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::traits::{Abs, Log, Sqrt};
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    macro_rules! point {
+        ($x:literal, $y:literal) => {
+            Vector2::new(qty!($x m), qty!($y m))
+        };
+    }
+
+    #[test]
+    fn quadratic() {
+        let bend = QuadraticBezier::new(point!(0, 0), point!(1, 2), point!(2, 0));
+        assert_eq!(bend.at(num!(0)), bend.start);
+        assert_eq!(bend.at(num!(0.5)), point!(1, 1));
+        assert_eq!(bend.at(num!(1)), bend.end);
+        assert!(bend
+            .tangent(num!(0))
+            .approx_eq(&Vector2::new(num!(1), num!(2)).normalize()));
+        assert_eq!(bend.tangent(num!(0.5)), Vector2::new(num!(1), num!(0)));
+
+        // The arc length of a parabola, from its closed form, up to the quadrature's tolerance.
+        let expected = qty!(1 m) * (num!(5).sqrt() + (num!(2) + num!(5).sqrt()).ln() / num!(2));
+        assert!((bend.length() - expected).abs() < qty!(1e-12 m));
+
+        let (first, second) = bend.split(num!(0.5));
+        assert_eq!(
+            first,
+            QuadraticBezier::new(point!(0, 0), point!(0.5, 1), point!(1, 1))
+        );
+        assert_eq!(second.at(num!(0.5)), bend.at(num!(0.75)));
+        assert!(bend
+            .to_cubic()
+            .at(num!(0.25))
+            .approx_eq(&bend.at(num!(0.25))));
+    }
+
+    #[test]
+    fn cubic() {
+        let back = CubicBezier::new(point!(0, 0), point!(0, 1), point!(1, 2), point!(2, 2));
+        assert_eq!(back.at(num!(0.5)), point!(0.625, 1.375));
+        assert_eq!(back.derivative(num!(0)), point!(0, 3));
+        assert_eq!(back.tangent(num!(1)), Vector2::new(num!(1), num!(0)));
+
+        let (first, second) = back.split(num!(0.25));
+        assert!(first.at(num!(1)).approx_eq(&back.at(num!(0.25))));
+        assert!(second.at(num!(0.5)).approx_eq(&back.at(num!(0.625))));
+        assert!((first.length() + second.length() - back.length()).abs() < qty!(1e-12 m));
+
+        // A straight line needs no points in between, however tight the tolerance.
+        let straight = CubicBezier::new(point!(0, 0), point!(1, 1), point!(2, 2), point!(3, 3));
+        assert_eq!(straight.flatten(qty!(0.001 mm)).points.len(), 2);
+
+        let tolerance = qty!(1 mm);
+        let flat = back.flatten(tolerance);
+        assert_eq!(flat.points.first(), Some(&back.start));
+        assert_eq!(flat.points.last(), Some(&back.end));
+        assert!(flat.points.len() > 8);
+        // Every point of the curve is close to one of the lines.
+        let mut t = Number::ZERO;
+        while t <= Number::ONE {
+            let point = back.at(t);
+            let closest = flat
+                .points
+                .windows(2)
+                .map(|pair| distance_to_line(point, pair[0], pair[1]))
+                .min()
+                .unwrap();
+            assert!(closest <= tolerance);
+            t = t + num!(0.015625);
+        }
+    }
+
+    fn distance_to_line(point: Vector2<Length>, a: Vector2<Length>, b: Vector2<Length>) -> Length {
+        let line = b - a;
+        let t = ((point - a).dot(&line) / line.dot(&line)).clamp(Number::ZERO, Number::ONE);
+        (point - (a + line * t)).length()
+    }
+}
@@ -0,0 +1,118 @@
+use villagekit_number::{
+    num,
+    traits::{Abs, Zero},
+    Number,
+};
+use villagekit_unit::Length;
+
+use crate::{Polyline, Vector2};
+
+/// The deepest [`Curve::flatten`] and [`Curve::length`] halve a curve, so they always end.
+pub(crate) const MAX_DEPTH: u32 = 16;
+
+/// A smooth path through points in the plane, from `at(0)` to `at(1)`.
+pub trait Curve {
+    /// The point a fraction `t` of the way along, by parameter rather than by length.
+    fn at(&self, t: Number) -> Vector2<Length>;
+
+    /// How fast the point moves as `t` goes from `0` to `1`, i.e. the derivative of
+    /// [`Curve::at`].
+    fn derivative(&self, t: Number) -> Vector2<Length>;
+
+    /// Splits the curve at `t` into the part before and the part after, each running from
+    /// `0` to `1` again.
+    fn split(&self, t: Number) -> (Self, Self)
+    where
+        Self: Sized;
+
+    /// The unit vector along the curve at `t`, or zero where the curve stops.
+    fn tangent(&self, t: Number) -> Vector2<Number> {
+        self.derivative(t).map(|v| v.canonical()).normalize()
+    }
+
+    /// The length along the curve.
+    fn length(&self) -> Length {
+        length_between(self, Number::ZERO, Number::ONE)
+    }
+
+    /// Approximates the curve by straight lines, none of which strays further than
+    /// `tolerance` from the curve.
+    fn flatten(&self, tolerance: Length) -> Polyline<Length> {
+        fn subdivide(
+            curve: &(impl Curve + ?Sized),
+            (from, to): (Number, Number),
+            tolerance: Length,
+            depth: u32,
+            points: &mut Vec<Vector2<Length>>,
+        ) {
+            let (start, end) = (curve.at(from), curve.at(to));
+            // Checking the quarters as well as the middle catches curves which cross the
+            // chord halfway.
+            let flat = [num!(0.25), Number::HALF, num!(0.75)]
+                .into_iter()
+                .all(|fraction| {
+                    let point = curve.at(from + (to - from) * fraction);
+                    distance_to_chord(point, start, end) <= tolerance
+                });
+            if flat || depth >= MAX_DEPTH {
+                points.push(end);
+            } else {
+                let middle = (from + to) * Number::HALF;
+                subdivide(curve, (from, middle), tolerance, depth + 1, points);
+                subdivide(curve, (middle, to), tolerance, depth + 1, points);
+            }
+        }
+        let mut points = vec![self.at(Number::ZERO)];
+        subdivide(self, (Number::ZERO, Number::ONE), tolerance, 0, &mut points);
+        Polyline::new(points)
+    }
+}
+
+/// The length of the curve between two parameters, by Gauss-Legendre quadrature of its
+/// speed, halving until the halves agree.
+pub(crate) fn length_between(curve: &(impl Curve + ?Sized), from: Number, to: Number) -> Length {
+    fn integrate(curve: &(impl Curve + ?Sized), from: Number, to: Number, depth: u32) -> Length {
+        let middle = (from + to) * Number::HALF;
+        let whole = gauss_legendre(curve, from, to);
+        let halves = gauss_legendre(curve, from, middle) + gauss_legendre(curve, middle, to);
+        if depth >= MAX_DEPTH || (whole - halves).abs() <= halves * num!(0.0000000000001) {
+            halves
+        } else {
+            integrate(curve, from, middle, depth + 1) + integrate(curve, middle, to, depth + 1)
+        }
+    }
+    integrate(curve, from, to, 0)
+}
+
+/// The length of the curve between two parameters, by five point Gauss-Legendre quadrature.
+fn gauss_legendre(curve: &(impl Curve + ?Sized), from: Number, to: Number) -> Length {
+    const NODES: [(Number, Number); 5] = [
+        (num!(0), num!(0.5688888888888889)),
+        (num!(0.5384693101056831), num!(0.4786286704993665)),
+        (num!(-0.5384693101056831), num!(0.4786286704993665)),
+        (num!(0.9061798459386640), num!(0.2369268850561891)),
+        (num!(-0.9061798459386640), num!(0.2369268850561891)),
+    ];
+    let half = (to - from) * Number::HALF;
+    let middle = from + half;
+    NODES
+        .into_iter()
+        .fold(Length::zero(), |sum, (node, weight)| {
+            sum + curve.derivative(middle + half * node).length() * (weight * half)
+        })
+}
+
+/// The distance from the point to the closest point on the chord from `start` to `end`.
+fn distance_to_chord(
+    point: Vector2<Length>,
+    start: Vector2<Length>,
+    end: Vector2<Length>,
+) -> Length {
+    let chord = end - start;
+    let square = chord.dot(&chord);
+    if square == Zero::zero() {
+        return (point - start).length();
+    }
+    let t = ((point - start).dot(&chord) / square).clamp(Number::ZERO, Number::ONE);
+    (point - (start + chord * t)).length()
+}
@@ -1,15 +1,56 @@
+mod aabb;
+mod affine2;
+mod affine3;
+mod arc;
+mod axis_rotation;
+mod b_spline;
+mod bezier;
 mod constants;
+mod curve;
+mod line_segment;
+mod plane;
+mod polygon;
+mod polyline;
 mod quaternion;
+mod ray;
+mod region;
+mod rotation2;
+mod span_box;
+mod sphere;
+mod triangle;
+mod vector2;
 mod vector3;
 
+pub use aabb::*;
+pub use affine2::*;
+pub use affine3::*;
+pub use arc::*;
+pub use axis_rotation::*;
+pub use b_spline::*;
+pub use bezier::*;
 pub use constants::*;
+pub use curve::*;
+pub use line_segment::*;
+pub use plane::*;
+pub use polygon::*;
+pub use polyline::*;
 pub use quaternion::*;
+pub use ray::*;
+pub use region::*;
+pub use rotation2::*;
+pub use span_box::*;
+pub use sphere::*;
+pub use triangle::*;
+pub use vector2::*;
 pub use vector3::*;
 
 #[cfg(test)]
 mod tests {
-    use villagekit_number::{num, traits::Sqrt};
-    use villagekit_unit::{qty, Length};
+    use villagekit_number::{
+        num,
+        traits::{ApproxEq, Sqrt},
+    };
+    use villagekit_unit::qty;
 
     use super::*;
 
@@ -18,6 +59,6 @@ mod tests {
         let expected = qty!(10 m) * num!(3).sqrt();
         let v = Vector3::new(qty!(10 m), qty!(10 m), qty!(10 m));
         let actual = v.length();
-        assert_eq!(expected, actual);
+        assert!(expected.approx_eq(&actual));
     }
 }
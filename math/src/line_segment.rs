@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{Area, Length};
+
+use crate::Vector3;
+
+/// The straight line between two points, e.g. the axis of a bolt.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LineSegment<N> {
+    pub start: Vector3<N>,
+    pub end: Vector3<N>,
+}
+
+impl<N> LineSegment<N> {
+    pub const fn new(start: Vector3<N>, end: Vector3<N>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl LineSegment<Length> {
+    /// The offset from the start to the end.
+    pub fn vector(&self) -> Vector3<Length> {
+        self.end - self.start
+    }
+
+    pub fn length(&self) -> Length {
+        self.vector().length()
+    }
+
+    pub fn midpoint(&self) -> Vector3<Length> {
+        self.at(Number::HALF)
+    }
+
+    /// The point a fraction `t` of the way along, from the start at `0` to the end at `1`.
+    pub fn at(&self, t: Number) -> Vector3<Length> {
+        self.start + self.vector() * t
+    }
+
+    pub fn closest_point(&self, point: Vector3<Length>) -> Vector3<Length> {
+        let vector = self.vector();
+        let length_squared = vector.dot(&vector);
+        if length_squared == Area::zero() {
+            return self.start;
+        }
+        let t = (point - self.start).dot(&vector) / length_squared;
+        self.at(t.clamp(Number::ZERO, Number::ONE))
+    }
+
+    pub fn distance_to_point(&self, point: Vector3<Length>) -> Length {
+        (point - self.closest_point(point)).length()
+    }
+
+    /// Returns the closest points between the segments, on `self` then on `other`.
+    pub fn closest_points(&self, other: &Self) -> (Vector3<Length>, Vector3<Length>) {
+        // from Ericson, Real-Time Collision Detection, 5.1.9
+        let (d1, d2) = (self.vector(), other.vector());
+        let r = self.start - other.start;
+        let (a, e, f) = (d1.dot(&d1), d2.dot(&d2), d2.dot(&r));
+        let zero = Area::zero();
+        let unit = |t: Number| t.clamp(Number::ZERO, Number::ONE);
+        let (s, t) = if a == zero && e == zero {
+            (Number::ZERO, Number::ZERO)
+        } else if a == zero {
+            (Number::ZERO, unit(f / e))
+        } else {
+            let c = d1.dot(&r);
+            if e == zero {
+                (unit(-c / a), Number::ZERO)
+            } else {
+                let b = d1.dot(&d2);
+                let denominator = a * e - b * b;
+                // Parallel segments have a line of closest points: any will do.
+                let s = if denominator == Zero::zero() {
+                    Number::ZERO
+                } else {
+                    unit((b * f - c * e) / denominator)
+                };
+                let t = (b * s + f) / e;
+                if t < Number::ZERO {
+                    (unit(-c / a), Number::ZERO)
+                } else if t > Number::ONE {
+                    (unit((b - c) / a), Number::ONE)
+                } else {
+                    (s, t)
+                }
+            }
+        };
+        (self.at(s), other.at(t))
+    }
+
+    pub fn distance_to_segment(&self, other: &Self) -> Length {
+        let (a, b) = self.closest_points(other);
+        (b - a).length()
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for LineSegment<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.start.approx_eq(&rhs.start) && self.end.approx_eq(&rhs.end)
+    }
+}
+
+impl<N: Validate> Validate for LineSegment<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.start.validate()?;
+        self.end.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::{num, traits::Sqrt};
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    #[test]
+    fn closest_points() {
+        let segment = LineSegment::new(
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(2 m), qty!(0 m), qty!(0 m)),
+        );
+        assert_eq!(segment.length(), qty!(2 m));
+        assert_eq!(
+            segment.closest_point(Vector3::new(qty!(1.5 m), qty!(1 m), qty!(0 m))),
+            Vector3::new(qty!(1.5 m), qty!(0 m), qty!(0 m))
+        );
+        assert_eq!(
+            segment.distance_to_point(Vector3::new(qty!(-3 m), qty!(4 m), qty!(0 m))),
+            qty!(5 m)
+        );
+
+        // Crossing over, one above the other.
+        let over = LineSegment::new(
+            Vector3::new(qty!(1 m), qty!(-1 m), qty!(0.5 m)),
+            Vector3::new(qty!(1 m), qty!(1 m), qty!(0.5 m)),
+        );
+        assert_eq!(
+            segment.closest_points(&over),
+            (
+                Vector3::new(qty!(1 m), qty!(0 m), qty!(0 m)),
+                Vector3::new(qty!(1 m), qty!(0 m), qty!(0.5 m))
+            )
+        );
+        assert_eq!(segment.distance_to_segment(&over), qty!(0.5 m));
+
+        // Past the end, and parallel.
+        let beyond = LineSegment::new(
+            Vector3::new(qty!(3 m), qty!(1 m), qty!(0 m)),
+            Vector3::new(qty!(3 m), qty!(2 m), qty!(0 m)),
+        );
+        assert!(segment
+            .distance_to_segment(&beyond)
+            .approx_eq(&(qty!(1 m) * num!(2).sqrt())));
+        let parallel = LineSegment::new(
+            Vector3::new(qty!(1 m), qty!(0 m), qty!(1 m)),
+            Vector3::new(qty!(4 m), qty!(0 m), qty!(1 m)),
+        );
+        assert_eq!(segment.distance_to_segment(&parallel), qty!(1 m));
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::Length;
+
+use crate::{LineSegment, Ray, Triangle, Vector3};
+
+/// An infinite flat plane, e.g. a section through a product: the points whose distance
+/// along `normal` from the origin is `distance`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Plane<N> {
+    /// The unit vector square to the plane, towards its front.
+    pub normal: Vector3<Number>,
+    pub distance: N,
+}
+
+impl Plane<Length> {
+    pub fn new(normal: Vector3<Number>, distance: Length) -> Self {
+        Self {
+            normal: normal.normalize(),
+            distance,
+        }
+    }
+
+    /// Creates the plane through a point, square to `normal`.
+    pub fn from_point_normal(point: Vector3<Length>, normal: Vector3<Number>) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            distance: point.dot(&normal),
+        }
+    }
+
+    /// Creates the plane through three points, facing the side they wind anticlockwise
+    /// around, or `None` if they are in a line.
+    pub fn from_points(a: Vector3<Length>, b: Vector3<Length>, c: Vector3<Length>) -> Option<Self> {
+        Triangle::new(a, b, c).plane()
+    }
+
+    /// The distance from the plane to the point, negative behind it.
+    pub fn signed_distance(&self, point: Vector3<Length>) -> Length {
+        point.dot(&self.normal) - self.distance
+    }
+
+    /// The point on the plane closest to `point`, i.e. its projection onto the plane.
+    pub fn closest_point(&self, point: Vector3<Length>) -> Vector3<Length> {
+        let distance = self.signed_distance(point);
+        point - self.normal.map(|n| distance * n)
+    }
+
+    /// Returns where the segment crosses the plane, or `None` if it doesn't reach it. A
+    /// segment lying in the plane crosses at its start.
+    pub fn intersect_segment(&self, segment: &LineSegment<Length>) -> Option<Vector3<Length>> {
+        let start = self.signed_distance(segment.start);
+        let end = self.signed_distance(segment.end);
+        let zero = Length::zero();
+        if (start > zero && end > zero) || (start < zero && end < zero) {
+            return None;
+        }
+        if start == end {
+            return Some(segment.start);
+        }
+        Some(segment.at(start / (start - end)))
+    }
+
+    /// Returns the distance along the ray to where it hits the plane, from either side, or
+    /// `None` if it misses.
+    pub fn intersect_ray(&self, ray: &Ray<Length>) -> Option<Length> {
+        let facing = ray.direction.dot(&self.normal);
+        if facing.approx_eq(&Number::ZERO) {
+            return None;
+        }
+        let distance = -self.signed_distance(ray.origin) / facing;
+        (distance >= Length::zero()).then_some(distance)
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Plane<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.normal.approx_eq(&rhs.normal) && self.distance.approx_eq(&rhs.distance)
+    }
+}
+
+impl<N: Validate> Validate for Plane<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.normal.validate()?;
+        self.distance.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::{num, traits::Sqrt};
+    use villagekit_unit::qty;
+
+    use super::*;
+    use crate::Z_AXIS;
+
+    #[test]
+    fn section_plane() {
+        // A cut 1 m up, along a post.
+        let plane = Plane::from_point_normal(Vector3::new(qty!(3 m), qty!(2 m), qty!(1 m)), Z_AXIS);
+        assert_eq!(plane.distance, qty!(1 m));
+        let point = Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(0.25 m));
+        assert_eq!(plane.signed_distance(point), qty!(-0.75 m));
+        assert_eq!(
+            plane.closest_point(point),
+            Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(1 m))
+        );
+
+        let post = LineSegment::new(
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(1 m), qty!(0 m), qty!(2 m)),
+        );
+        assert_eq!(
+            plane.intersect_segment(&post),
+            Some(Vector3::new(qty!(0.5 m), qty!(0 m), qty!(1 m)))
+        );
+        let short = LineSegment::new(post.start, post.midpoint() * num!(0.5));
+        assert_eq!(plane.intersect_segment(&short), None);
+
+        let ray = Ray::new(point, Vector3::new(num!(0), num!(3), num!(4)));
+        assert!(plane
+            .intersect_ray(&ray)
+            .unwrap()
+            .approx_eq(&qty!(0.9375 m)));
+        assert_eq!(
+            plane.intersect_ray(&Ray::new(point, -Number::ONE * Z_AXIS)),
+            None
+        );
+
+        let tilted = Plane::from_points(
+            Vector3::new(qty!(1 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(0 m), qty!(1 m), qty!(0 m)),
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(1 m)),
+        )
+        .unwrap();
+        assert!(tilted.approx_eq(&Plane::new(
+            Vector3::new(num!(1), num!(1), num!(1)),
+            qty!(1 m) / num!(3).sqrt()
+        )));
+    }
+}
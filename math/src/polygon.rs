@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{Abs, ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{Area, Length};
+
+use crate::Vector2;
+
+/// The way a polygon's points wind around its inside.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Orientation {
+    Anticlockwise,
+    Clockwise,
+}
+
+/// A closed polygon through a list of points, e.g. the outline of a sheet part. The last
+/// point joins back to the first.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Polygon<N> {
+    pub points: Vec<Vector2<N>>,
+}
+
+impl<N> Polygon<N> {
+    pub fn new(points: Vec<Vector2<N>>) -> Self {
+        Self { points }
+    }
+
+    /// Returns the polygon with its points in the opposite order.
+    pub fn reversed(mut self) -> Self {
+        self.points.reverse();
+        self
+    }
+}
+
+impl<N: Copy> Polygon<N> {
+    /// Each edge from a point to the next, ending with the edge back to the first.
+    pub fn edges(&self) -> impl Iterator<Item = (Vector2<N>, Vector2<N>)> + '_ {
+        let next = self.points.iter().cycle().skip(1);
+        self.points.iter().copied().zip(next.copied())
+    }
+}
+
+impl Polygon<Length> {
+    /// The area inside the polygon, positive if it winds anticlockwise.
+    pub fn signed_area(&self) -> Area {
+        let twice = self
+            .edges()
+            .fold(Area::zero(), |sum, (a, b)| sum + a.cross(&b));
+        twice * Number::HALF
+    }
+
+    pub fn area(&self) -> Area {
+        self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> Length {
+        self.edges()
+            .fold(Length::zero(), |sum, (a, b)| sum + (b - a).length())
+    }
+
+    /// The orientation of the polygon, anticlockwise if it has no area.
+    pub fn orientation(&self) -> Orientation {
+        if self.signed_area() < Area::zero() {
+            Orientation::Clockwise
+        } else {
+            Orientation::Anticlockwise
+        }
+    }
+
+    /// Returns the polygon wound in the given orientation, reversing it if it isn't.
+    pub fn with_orientation(self, orientation: Orientation) -> Self {
+        if self.orientation() == orientation {
+            self
+        } else {
+            self.reversed()
+        }
+    }
+
+    /// Whether the point is inside the polygon or on its edges.
+    pub fn contains(&self, point: Vector2<Length>) -> bool {
+        if self.on_edge(point) {
+            return true;
+        }
+        // Count the edges crossed going from the point towards +x.
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = a.x + (b.x - a.x) * ((point.y - a.y) / (b.y - a.y));
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Whether the point is on one of the edges of the polygon.
+    pub fn on_edge(&self, point: Vector2<Length>) -> bool {
+        self.edges().any(|(a, b)| on_segment(point, a, b))
+    }
+
+    /// Moves every edge outwards by `distance`, or inwards for a negative distance, e.g. to
+    /// allow for the kerf of a cut or the radius of a tool. Corners stay sharp unless they
+    /// would reach out more than twice the distance, when they are cut off.
+    ///
+    /// Offsetting inwards by more than half the width of a feature makes the polygon cross
+    /// itself there.
+    pub fn offset(&self, distance: Length) -> Self {
+        let count = self.points.len();
+        if count < 3 || distance == Length::zero() {
+            return self.clone();
+        }
+        // The outward normals are to the right of anticlockwise edges.
+        let orientation = self.orientation();
+        let outward = match orientation {
+            Orientation::Anticlockwise => -Number::ONE,
+            Orientation::Clockwise => Number::ONE,
+        };
+        let normal = |a: Vector2<Length>, b: Vector2<Length>| {
+            (b - a).map(|v| v.canonical()).normalize().perp() * outward
+        };
+        let mut points = Vec::with_capacity(count);
+        for i in 0..count {
+            let previous = self.points[(i + count - 1) % count];
+            let point = self.points[i];
+            let next = self.points[(i + 1) % count];
+            let (n1, n2) = (normal(previous, point), normal(point, next));
+            let denominator = Number::ONE + n1.dot(&n2);
+            // A corner pointing the way the edges move reaches out further than they do.
+            let turn = (point - previous).cross(&(next - point));
+            let convex = match orientation {
+                Orientation::Anticlockwise => turn > Area::zero(),
+                Orientation::Clockwise => turn < Area::zero(),
+            };
+            let reaches_out = convex == (distance > Length::zero());
+            if reaches_out && denominator < Number::HALF {
+                points.push(point + n1.map(|n| distance * n));
+                points.push(point + n2.map(|n| distance * n));
+            } else {
+                points.push(point + (n1 + n2).map(|n| distance * n / denominator));
+            }
+        }
+        Self::new(points)
+    }
+
+    /// Splits the polygon into triangles by clipping off its ears. The polygon must not
+    /// cross itself.
+    pub fn triangulate(&self) -> Triangulation<Length> {
+        let mut indices: Vec<usize> = (0..self.points.len()).collect();
+        if self.orientation() == Orientation::Clockwise {
+            indices.reverse();
+        }
+        Triangulation {
+            points: self.points.clone(),
+            triangles: ear_clip(&self.points, indices),
+        }
+    }
+}
+
+/// Whether `point` is on the segment from `a` to `b`.
+pub(crate) fn on_segment(point: Vector2<Length>, a: Vector2<Length>, b: Vector2<Length>) -> bool {
+    let in_range = |v: Length, a: Length, b: Length| a.min(b) <= v && v <= a.max(b);
+    (b - a).cross(&(point - a)) == Area::zero()
+        && in_range(point.x, a.x, b.x)
+        && in_range(point.y, a.y, b.y)
+}
+
+/// Clips ears off the anticlockwise polygon of `points` through `indices`.
+pub(crate) fn ear_clip(points: &[Vector2<Length>], mut indices: Vec<usize>) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::with_capacity(indices.len().saturating_sub(2));
+    let turn =
+        |a: usize, b: usize, c: usize| (points[b] - points[a]).cross(&(points[c] - points[b]));
+    while indices.len() >= 3 {
+        let count = indices.len();
+        let corner = |i: usize| {
+            (
+                indices[(i + count - 1) % count],
+                indices[i],
+                indices[(i + 1) % count],
+            )
+        };
+        let is_ear = |i: usize| {
+            let (a, b, c) = corner(i);
+            if turn(a, b, c) <= Area::zero() {
+                return false;
+            }
+            let corners = [points[a], points[b], points[c]];
+            indices.iter().all(|&other| {
+                let point = points[other];
+                corners.contains(&point) || !in_triangle(point, corners)
+            })
+        };
+        // Collinear corners add no area, so drop them first.
+        if let Some(i) = (0..count).find(|&i| {
+            let (a, b, c) = corner(i);
+            turn(a, b, c) == Area::zero()
+        }) {
+            indices.remove(i);
+            continue;
+        }
+        // A polygon which crosses itself can run out of ears: then clip the sharpest
+        // corner to carry on.
+        let i = (0..count).find(|&i| is_ear(i)).unwrap_or_else(|| {
+            (0..count)
+                .max_by_key(|&i| {
+                    let (a, b, c) = corner(i);
+                    turn(a, b, c)
+                })
+                .unwrap()
+        });
+        let (a, b, c) = corner(i);
+        triangles.push([a, b, c]);
+        indices.remove(i);
+    }
+    triangles
+}
+
+/// Whether `point` is inside the anticlockwise triangle or on its edges.
+fn in_triangle(point: Vector2<Length>, [a, b, c]: [Vector2<Length>; 3]) -> bool {
+    let zero = Area::zero();
+    (b - a).cross(&(point - a)) >= zero
+        && (c - b).cross(&(point - b)) >= zero
+        && (a - c).cross(&(point - c)) >= zero
+}
+
+impl<N: ApproxEq> ApproxEq for Polygon<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.points.len() == rhs.points.len()
+            && self
+                .points
+                .iter()
+                .zip(&rhs.points)
+                .all(|(a, b)| a.approx_eq(b))
+    }
+}
+
+impl<N: Validate> Validate for Polygon<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.points.iter().try_for_each(Validate::validate)
+    }
+}
+
+/// A flat mesh of triangles, each anticlockwise through three of the points.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Triangulation<N> {
+    pub points: Vec<Vector2<N>>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Triangulation<Length> {
+    pub fn area(&self) -> Area {
+        self.triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let [a, b, c] = [a, b, c].map(|i| self.points[i]);
+                (b - a).cross(&(c - a)) * Number::HALF
+            })
+            .fold(Area::zero(), |sum, area| sum + area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+    use crate::Polyline;
+
+    macro_rules! polygon {
+        ($(($x:literal, $y:literal)),* $(,)?) => {
+            Polygon::new(vec![$(Vector2::new(qty!($x m), qty!($y m))),*])
+        };
+    }
+
+    #[test]
+    fn measure() {
+        let l_shape = polygon![(0, 0), (2, 0), (2, 1), (1, 1), (1, 3), (0, 3)];
+        assert_eq!(l_shape.signed_area(), qty!(4 m2));
+        assert_eq!(l_shape.clone().reversed().signed_area(), qty!(-4 m2));
+        assert_eq!(l_shape.perimeter(), qty!(10 m));
+        let open = Polyline::new(l_shape.points.clone());
+        assert_eq!(open.length(), qty!(7 m));
+        assert_eq!(open.close(), l_shape);
+        assert_eq!(l_shape.orientation(), Orientation::Anticlockwise);
+        assert_eq!(
+            l_shape
+                .clone()
+                .reversed()
+                .with_orientation(Orientation::Anticlockwise),
+            l_shape
+        );
+
+        assert!(l_shape.contains(Vector2::new(qty!(0.5 m), qty!(2 m))));
+        assert!(l_shape.contains(Vector2::new(qty!(1 m), qty!(2 m))));
+        assert!(!l_shape.contains(Vector2::new(qty!(1.5 m), qty!(2 m))));
+        assert!(!l_shape.contains(Vector2::new(qty!(-1 m), qty!(1 m))));
+    }
+
+    #[test]
+    fn offset() {
+        let square = polygon![(0, 0), (2, 0), (2, 2), (0, 2)];
+        assert_eq!(
+            square.offset(qty!(0.5 m)),
+            polygon![(-0.5, -0.5), (2.5, -0.5), (2.5, 2.5), (-0.5, 2.5)]
+        );
+        assert_eq!(
+            square.clone().reversed().offset(qty!(-0.5 m)),
+            polygon![(0.5, 1.5), (1.5, 1.5), (1.5, 0.5), (0.5, 0.5)]
+        );
+        let l_shape = polygon![(0, 0), (2, 0), (2, 1), (1, 1), (1, 3), (0, 3)];
+        assert_eq!(l_shape.offset(qty!(0.25 m)).area(), qty!(6.75 m2));
+
+        // A sharp spike is cut off rather than reaching far out.
+        let spike = polygon![(0, 0), (4, 0), (0, 1)];
+        let offset = spike.offset(qty!(0.1 m));
+        assert_eq!(offset.points.len(), 4);
+        assert!(offset.points.iter().all(|point| point.x < qty!(4.3 m)));
+    }
+
+    #[test]
+    fn triangulate() {
+        let l_shape = polygon![(0, 0), (2, 0), (2, 1), (1, 1), (1, 3), (0, 3)].reversed();
+        let triangulation = l_shape.triangulate();
+        assert_eq!(triangulation.triangles.len(), 4);
+        assert_eq!(triangulation.area(), qty!(4 m2));
+
+        // Collinear points add no triangles.
+        let strip = polygon![(0, 0), (1, 0), (2, 0), (2, 1), (0, 1)];
+        assert_eq!(strip.triangulate().triangles.len(), 2);
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, Validate, Zero},
+    NumberError,
+};
+use villagekit_unit::Length;
+
+use crate::{Polygon, Vector2};
+
+/// An open path of straight lines through a list of points, e.g. a cut line or a
+/// flattened curve.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Polyline<N> {
+    pub points: Vec<Vector2<N>>,
+}
+
+impl<N> Polyline<N> {
+    pub fn new(points: Vec<Vector2<N>>) -> Self {
+        Self { points }
+    }
+
+    /// Joins the last point back to the first.
+    pub fn close(self) -> Polygon<N> {
+        Polygon::new(self.points)
+    }
+}
+
+impl<N: Copy> Polyline<N> {
+    /// Each line from a point to the next.
+    pub fn segments(&self) -> impl Iterator<Item = (Vector2<N>, Vector2<N>)> + '_ {
+        self.points.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+}
+
+impl Polyline<Length> {
+    pub fn length(&self) -> Length {
+        self.segments()
+            .fold(Length::zero(), |sum, (a, b)| sum + (b - a).length())
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Polyline<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.points.len() == rhs.points.len()
+            && self
+                .points
+                .iter()
+                .zip(&rhs.points)
+                .all(|(a, b)| a.approx_eq(b))
+    }
+}
+
+impl<N: Validate> Validate for Polyline<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.points.iter().try_for_each(Validate::validate)
+    }
+}
@@ -2,12 +2,40 @@ use serde::{Deserialize, Serialize};
 use std::ops::Mul;
 use villagekit_number::{
     num,
-    traits::{ApproxEq, Sqrt, Trig},
-    Number,
+    traits::{Abs, ApproxEq, Sqrt, Trig, TrigInv, Validate},
+    Number, NumberError,
 };
 use villagekit_unit::Angle;
 
-use crate::vector3::Vector3;
+use crate::{vector3::Vector3, X_AXIS, Y_AXIS, Z_AXIS};
+
+/// The order of the axes of Euler angles, each rotation about the axes as already turned
+/// by the ones before, e.g. `ZYX` for yaw, then pitch, then roll.
+///
+/// This is the same rotation as turning about the fixed axes in the reverse order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EulerOrder {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl EulerOrder {
+    /// The indices of the axes, and whether they are in cyclic (right-handed) order.
+    fn axes(self) -> ([usize; 3], bool) {
+        match self {
+            EulerOrder::XYZ => ([0, 1, 2], true),
+            EulerOrder::XZY => ([0, 2, 1], false),
+            EulerOrder::YXZ => ([1, 0, 2], false),
+            EulerOrder::YZX => ([1, 2, 0], true),
+            EulerOrder::ZXY => ([2, 0, 1], true),
+            EulerOrder::ZYX => ([2, 1, 0], false),
+        }
+    }
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Quaternion {
@@ -18,6 +46,13 @@ pub struct Quaternion {
 }
 
 impl Quaternion {
+    pub const IDENTITY: Self = Self {
+        x: num!(0),
+        y: num!(0),
+        z: num!(0),
+        w: num!(1),
+    };
+
     pub fn new(x: Number, y: Number, z: Number, w: Number) -> Self {
         Self { x, y, z, w }
     }
@@ -33,6 +68,157 @@ impl Quaternion {
         }
     }
 
+    /// Creates the rotation which takes the x, y and z axes to the given orthonormal axes.
+    pub fn from_axes(
+        x_axis: Vector3<Number>,
+        y_axis: Vector3<Number>,
+        z_axis: Vector3<Number>,
+    ) -> Self {
+        // from https://www.euclideanspace.com/maths/geometry/rotations/conversions/matrixToQuaternion/index.htm
+        let (m00, m01, m02) = (x_axis.x, y_axis.x, z_axis.x);
+        let (m10, m11, m12) = (x_axis.y, y_axis.y, z_axis.y);
+        let (m20, m21, m22) = (x_axis.z, y_axis.z, z_axis.z);
+        let trace = m00 + m11 + m22;
+        let quarter = Number::QUARTER;
+        let rotation = if trace > Number::ZERO {
+            let s = (trace + Number::ONE).sqrt() * Number::TWO;
+            Self::new(
+                (m21 - m12) / s,
+                (m02 - m20) / s,
+                (m10 - m01) / s,
+                quarter * s,
+            )
+        } else if m00 > m11 && m00 > m22 {
+            let s = (Number::ONE + m00 - m11 - m22).sqrt() * Number::TWO;
+            Self::new(
+                quarter * s,
+                (m01 + m10) / s,
+                (m02 + m20) / s,
+                (m21 - m12) / s,
+            )
+        } else if m11 > m22 {
+            let s = (Number::ONE + m11 - m00 - m22).sqrt() * Number::TWO;
+            Self::new(
+                (m01 + m10) / s,
+                quarter * s,
+                (m12 + m21) / s,
+                (m02 - m20) / s,
+            )
+        } else {
+            let s = (Number::ONE + m22 - m00 - m11).sqrt() * Number::TWO;
+            Self::new(
+                (m02 + m20) / s,
+                (m12 + m21) / s,
+                quarter * s,
+                (m10 - m01) / s,
+            )
+        };
+        rotation.normalize()
+    }
+
+    /// Creates the shortest rotation which turns the direction `from` to the direction `to`.
+    pub fn from_rotation_arc(from: Vector3<Number>, to: Vector3<Number>) -> Self {
+        let (from, to) = (from.normalize(), to.normalize());
+        let dot = from.dot(&to);
+        if dot.approx_eq(&-Number::ONE) {
+            // Opposite directions: any axis square to them will do.
+            let axis = from.cross(&X_AXIS);
+            let axis = if axis.length().approx_eq(&Number::ZERO) {
+                from.cross(&Y_AXIS)
+            } else {
+                axis
+            };
+            let axis = axis.normalize();
+            return Self::new(axis.x, axis.y, axis.z, Number::ZERO);
+        }
+        let axis = from.cross(&to);
+        Self::new(axis.x, axis.y, axis.z, Number::ONE + dot).normalize()
+    }
+
+    /// Creates the rotation which turns the y axis to face `direction`, with the z axis as
+    /// close to `up` as it can be: forward and up in a z-up model.
+    pub fn look_at(direction: Vector3<Number>, up: Vector3<Number>) -> Self {
+        let forward = direction.normalize();
+        let right = forward.cross(&up);
+        if right.length().approx_eq(&Number::ZERO) {
+            return Self::from_rotation_arc(Y_AXIS, forward);
+        }
+        let right = right.normalize();
+        Self::from_axes(right, forward, right.cross(&forward))
+    }
+
+    /// Creates the rotation by the angles `a`, then `b`, then `c` about the axes of `order`.
+    pub fn from_euler(order: EulerOrder, a: Angle, b: Angle, c: Angle) -> Self {
+        let ([i, j, k], _) = order.axes();
+        let axes = [X_AXIS, Y_AXIS, Z_AXIS];
+        Self::from_axis_angle(axes[i], a)
+            * Self::from_axis_angle(axes[j], b)
+            * Self::from_axis_angle(axes[k], c)
+    }
+
+    /// Returns the Euler angles of the rotation about the axes of `order`, such that
+    /// `from_euler` gives the rotation back.
+    ///
+    /// The middle angle is between -90° and 90°. Where it is at either end, the first and
+    /// last axes line up and the whole turn about them is given as the first angle.
+    pub fn to_euler(self, order: EulerOrder) -> (Angle, Angle, Angle) {
+        // from https://www.geometrictools.com/Documentation/EulerAngles.pdf
+        let ([i, j, k], cyclic) = order.axes();
+        let sign = if cyclic { Number::ONE } else { -Number::ONE };
+        let m = self.to_matrix();
+        let sin_b = (sign * m[i][k]).clamp(-Number::ONE, Number::ONE);
+        let (a, b, c) = if sin_b.abs().approx_eq(&Number::ONE) {
+            // `asin` is steepest at ±1, so snap to the lock rather than amplify rounding errors.
+            let b = if sin_b < Number::ZERO {
+                -Number::FRAC_PI_2
+            } else {
+                Number::FRAC_PI_2
+            };
+            ((sign * m[k][j]).atan2(m[j][j]), b, Number::ZERO)
+        } else {
+            (
+                (-sign * m[j][k]).atan2(m[k][k]),
+                sin_b.asin(),
+                (-sign * m[i][j]).atan2(m[i][i]),
+            )
+        };
+        (
+            Angle::from_canonical(a),
+            Angle::from_canonical(b),
+            Angle::from_canonical(c),
+        )
+    }
+
+    /// Returns the axis and angle of the rotation, with the angle between 0° and 180°.
+    ///
+    /// The axis of the identity rotation is the x axis.
+    pub fn to_axis_angle(self) -> (Vector3<Number>, Angle) {
+        let q = self.normalize();
+        let q = if q.w < Number::ZERO {
+            q.multiply_scalar(-Number::ONE)
+        } else {
+            q
+        };
+        let axis = Vector3::new(q.x, q.y, q.z);
+        let sin_half_angle = axis.length();
+        let angle = Angle::from_canonical(Number::TWO * sin_half_angle.atan2(q.w));
+        if sin_half_angle == Number::ZERO {
+            (X_AXIS, angle)
+        } else {
+            (axis / sin_half_angle, angle)
+        }
+    }
+
+    /// The angle of the rotation, between 0° and 180°.
+    pub fn angle(self) -> Angle {
+        self.to_axis_angle().1
+    }
+
+    /// The angle of the smallest rotation which turns `self` into `other`.
+    pub fn angle_between(self, other: Quaternion) -> Angle {
+        (self.inverse() * other).angle()
+    }
+
     pub fn multiply(self, other: Quaternion) -> Self {
         // from https://www.euclideanspace.com/maths/algebra/realNormedAlgebra/quaternions/code/index.htm
         let (q1, q2) = (self, other);
@@ -47,11 +233,32 @@ impl Quaternion {
         other.multiply(self)
     }
 
-    pub fn multipy_scalar(self, n: Number) -> Self {
+    pub fn multiply_scalar(self, n: Number) -> Self {
         let Self { x, y, z, w } = self;
         Quaternion::new(x * n, y * n, z * n, w * n)
     }
 
+    pub fn dot(self, other: Quaternion) -> Number {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Returns the quaternion with its vector part negated: the inverse of a unit
+    /// quaternion.
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Returns the rotation back by the same angle.
+    pub fn inverse(self) -> Self {
+        let length_squared = self.dot(self);
+        if length_squared == Number::ZERO {
+            Self::default()
+        } else {
+            self.conjugate()
+                .multiply_scalar(Number::ONE / length_squared)
+        }
+    }
+
     pub fn length(self) -> Number {
         let Self { x, y, z, w } = self;
         (x * x + y * y + z * z + w * w).sqrt()
@@ -70,16 +277,66 @@ impl Quaternion {
             }
         }
     }
+
+    /// Interpolates between the rotations along the shortest path, with a uniform speed,
+    /// from `self` at `t = 0` to `other` at `t = 1`.
+    pub fn slerp(self, other: Quaternion, t: Number) -> Self {
+        // from https://www.euclideanspace.com/maths/algebra/realNormedAlgebra/quaternions/slerp/index.htm
+        let (a, b) = (self.normalize(), other.normalize());
+        let dot = a.dot(b);
+        let (b, dot) = if dot < Number::ZERO {
+            (b.multiply_scalar(-Number::ONE), -dot)
+        } else {
+            (b, dot)
+        };
+        if dot.approx_eq(&Number::ONE) {
+            return a.nlerp(b, t);
+        }
+        let theta = dot.min(Number::ONE).acos();
+        let sin_theta = theta.sin();
+        let wa = ((Number::ONE - t) * theta).sin() / sin_theta;
+        let wb = (t * theta).sin() / sin_theta;
+        Self::new(
+            a.x * wa + b.x * wb,
+            a.y * wa + b.y * wb,
+            a.z * wa + b.z * wb,
+            a.w * wa + b.w * wb,
+        )
+        .normalize()
+    }
+
+    /// Interpolates between the rotations along the shortest path, like `slerp`, but
+    /// faster and with a speed which is not uniform.
+    pub fn nlerp(self, other: Quaternion, t: Number) -> Self {
+        let other = if self.dot(other) < Number::ZERO {
+            other.multiply_scalar(-Number::ONE)
+        } else {
+            other
+        };
+        let s = Number::ONE - t;
+        Self::new(
+            self.x * s + other.x * t,
+            self.y * s + other.y * t,
+            self.z * s + other.z * t,
+            self.w * s + other.w * t,
+        )
+        .normalize()
+    }
+
+    /// The rotation matrix, by rows.
+    fn to_matrix(self) -> [[Number; 3]; 3] {
+        let [x_axis, y_axis, z_axis] = [X_AXIS, Y_AXIS, Z_AXIS].map(|axis| self * axis);
+        [
+            [x_axis.x, y_axis.x, z_axis.x],
+            [x_axis.y, y_axis.y, z_axis.y],
+            [x_axis.z, y_axis.z, z_axis.z],
+        ]
+    }
 }
 
 impl Default for Quaternion {
     fn default() -> Self {
-        Self {
-            x: num!(0),
-            y: num!(0),
-            z: num!(0),
-            w: num!(1),
-        }
+        Self::IDENTITY
     }
 }
 
@@ -100,9 +357,144 @@ impl ApproxEq for Quaternion {
     }
 }
 
+impl Validate for Quaternion {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.x.validate()?;
+        self.y.validate()?;
+        self.z.validate()?;
+        self.w.validate()
+    }
+}
+
 impl From<Quaternion> for glam::Quat {
     fn from(value: Quaternion) -> Self {
         let Quaternion { x, y, z, w } = value;
         glam::Quat::from_xyzw(x.into(), y.into(), z.into(), w.into())
     }
 }
+
+impl TryFrom<glam::Quat> for Quaternion {
+    type Error = NumberError;
+
+    fn try_from(value: glam::Quat) -> Result<Self, Self::Error> {
+        Ok(Quaternion::new(
+            value.x.try_into()?,
+            value.y.try_into()?,
+            value.z.try_into()?,
+            value.w.try_into()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::num;
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    const ORDERS: [EulerOrder; 6] = [
+        EulerOrder::XYZ,
+        EulerOrder::XZY,
+        EulerOrder::YXZ,
+        EulerOrder::YZX,
+        EulerOrder::ZXY,
+        EulerOrder::ZYX,
+    ];
+
+    #[test]
+    fn inverse_and_axis_angle() {
+        let axis = Vector3::new(num!(1), num!(2), num!(2));
+        let q = Quaternion::from_axis_angle(axis, qty!(120 deg));
+        assert!((q * q.inverse()).approx_eq(&Quaternion::IDENTITY));
+        assert!(q.conjugate().approx_eq(&q.inverse()));
+        assert!(q
+            .multiply_scalar(num!(2))
+            .inverse()
+            .approx_eq(&q.inverse().multiply_scalar(num!(0.5))));
+
+        let (rotated_axis, angle) = q.to_axis_angle();
+        assert!(rotated_axis.approx_eq(&(axis / num!(3))));
+        assert!(angle.approx_eq(&qty!(120 deg)));
+        // The long way round is the short way about the opposite axis.
+        let (rotated_axis, angle) =
+            Quaternion::from_axis_angle(axis, qty!(300 deg)).to_axis_angle();
+        assert!(rotated_axis.approx_eq(&(axis / num!(-3))));
+        assert!(angle.approx_eq(&qty!(60 deg)));
+        assert_eq!(Quaternion::IDENTITY.to_axis_angle(), (X_AXIS, qty!(0 deg)));
+    }
+
+    #[test]
+    fn euler_round_trip() {
+        let (a, b, c) = (qty!(30 deg), qty!(-50 deg), qty!(140 deg));
+        for order in ORDERS {
+            let q = Quaternion::from_euler(order, a, b, c);
+            let (a2, b2, c2) = q.to_euler(order);
+            assert!(a2.approx_eq(&a), "{order:?}");
+            assert!(b2.approx_eq(&b), "{order:?}");
+            assert!(c2.approx_eq(&c), "{order:?}");
+        }
+        // Yaw a quarter turn, then pitch the nose up by a quarter turn.
+        let q = Quaternion::from_euler(EulerOrder::ZYX, qty!(90 deg), qty!(-90 deg), qty!(0 deg));
+        assert!((q * X_AXIS).approx_eq(&Z_AXIS));
+        // Gimbal lock keeps the rotation, if not the angles.
+        for order in ORDERS {
+            let q = Quaternion::from_euler(order, a, qty!(90 deg), c);
+            let (a2, b2, c2) = q.to_euler(order);
+            assert!(b2.approx_eq(&qty!(90 deg)), "{order:?}");
+            assert!(Quaternion::from_euler(order, a2, b2, c2)
+                .angle_between(q)
+                .approx_eq(&qty!(0 deg)));
+        }
+    }
+
+    #[test]
+    fn rotation_arc_and_look_at() {
+        let from = Vector3::new(num!(1), num!(1), num!(0));
+        let to = Vector3::new(num!(0), num!(0), num!(-3));
+        let q = Quaternion::from_rotation_arc(from, to);
+        assert!((q * from.normalize()).approx_eq(&to.normalize()));
+        assert!(q.angle().approx_eq(&qty!(90 deg)));
+        let q = Quaternion::from_rotation_arc(X_AXIS, -Number::ONE * X_AXIS);
+        assert!((q * X_AXIS).approx_eq(&(-Number::ONE * X_AXIS)));
+        assert!(Quaternion::from_rotation_arc(Z_AXIS, Z_AXIS).approx_eq(&Quaternion::IDENTITY));
+
+        let q = Quaternion::look_at(Vector3::new(num!(-1), num!(0), num!(1)), Z_AXIS);
+        let forward = Vector3::new(num!(-1), num!(0), num!(1)).normalize();
+        assert!((q * Y_AXIS).approx_eq(&forward));
+        assert!((q * X_AXIS).approx_eq(&Y_AXIS));
+        assert!((q * Z_AXIS).dot(&Z_AXIS) > num!(0));
+        assert!((Quaternion::look_at(Z_AXIS, Z_AXIS) * Y_AXIS).approx_eq(&Z_AXIS));
+    }
+
+    #[test]
+    fn interpolate() {
+        let a = Quaternion::from_axis_angle(Z_AXIS, qty!(10 deg));
+        let b = Quaternion::from_axis_angle(Z_AXIS, qty!(130 deg));
+        assert!(a.slerp(b, num!(0)).approx_eq(&a));
+        assert!(a.slerp(b, num!(1)).approx_eq(&b));
+        assert!(a
+            .slerp(b, num!(0.25))
+            .approx_eq(&Quaternion::from_axis_angle(Z_AXIS, qty!(40 deg))));
+        assert!(a
+            .nlerp(b, num!(0.5))
+            .approx_eq(&Quaternion::from_axis_angle(Z_AXIS, qty!(70 deg))));
+        // Along the shorter way, even from the other sign of the same rotation.
+        let c = Quaternion::from_axis_angle(Z_AXIS, qty!(-100 deg)).multiply_scalar(-Number::ONE);
+        assert!(a
+            .slerp(c, num!(0.5))
+            .angle_between(Quaternion::from_axis_angle(Z_AXIS, qty!(-45 deg)))
+            .approx_eq(&qty!(0 deg)));
+        assert!(a.angle_between(b).approx_eq(&qty!(120 deg)));
+        assert!(b.angle_between(a).approx_eq(&qty!(120 deg)));
+    }
+
+    #[test]
+    fn glam_round_trip() {
+        let q = Quaternion::from_axis_angle(Vector3::new(num!(3), num!(0), num!(4)), qty!(72 deg));
+        let glam = glam::Quat::from(q);
+        let back = Quaternion::try_from(glam).unwrap();
+        assert!(back.angle_between(q).canonical() < num!(0.0001));
+        assert!(Quaternion::try_from(glam::Quat::from_xyzw(f32::NAN, 0.0, 0.0, 1.0)).is_err());
+    }
+}
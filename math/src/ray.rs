@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, Sqrt, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{Area, Length, Span};
+
+use crate::{Aabb, Sphere, Triangle, Vector3};
+
+/// A half-line from an origin in a direction, e.g. from the camera through the cursor to
+/// pick what it points at.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Ray<N> {
+    pub origin: Vector3<N>,
+    /// The unit vector along the ray, so distances along it are in `N`.
+    pub direction: Vector3<Number>,
+}
+
+impl Ray<Length> {
+    pub fn new(origin: Vector3<Length>, direction: Vector3<Number>) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// The point a distance along the ray.
+    pub fn at(&self, distance: Length) -> Vector3<Length> {
+        self.origin + self.direction.map(|d| distance * d)
+    }
+
+    pub fn closest_point(&self, point: Vector3<Length>) -> Vector3<Length> {
+        let distance = (point - self.origin).dot(&self.direction);
+        self.at(distance.max(Length::zero()))
+    }
+
+    pub fn distance_to_point(&self, point: Vector3<Length>) -> Length {
+        (point - self.closest_point(point)).length()
+    }
+
+    /// Returns the span of distances along the ray inside the box, or `None` if it misses.
+    /// The span starts at zero if the ray starts inside.
+    pub fn intersect_aabb(&self, aabb: &Aabb<Length>) -> Option<Span<Length>> {
+        // The slab method: clip the ray between each pair of opposite faces in turn.
+        let mut inside = Span::new(Length::zero(), Length::from_canonical(Number::MAX));
+        let axes = [
+            (self.origin.x, self.direction.x, aabb.min.x, aabb.max.x),
+            (self.origin.y, self.direction.y, aabb.min.y, aabb.max.y),
+            (self.origin.z, self.direction.z, aabb.min.z, aabb.max.z),
+        ];
+        for (origin, direction, min, max) in axes {
+            if direction == Number::ZERO {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+            let slab = Span::new((min - origin) / direction, (max - origin) / direction);
+            inside = inside.intersection(&slab.ascending())?;
+        }
+        Some(inside)
+    }
+
+    /// Returns the distance along the ray to where it hits the triangle, from either side,
+    /// or `None` if it misses.
+    pub fn intersect_triangle(&self, triangle: &Triangle<Length>) -> Option<Length> {
+        // from Möller and Trumbore, Fast, Minimum Storage Ray/Triangle Intersection
+        let edge1 = triangle.b - triangle.a;
+        let edge2 = triangle.c - triangle.a;
+        let p = self.direction.cross(&edge2);
+        let determinant = edge1.dot(&p);
+        if determinant.approx_eq(&Area::zero()) {
+            return None;
+        }
+        let s = self.origin - triangle.a;
+        let u = s.dot(&p) / determinant;
+        if u < Number::ZERO || u > Number::ONE {
+            return None;
+        }
+        let q = s.cross(&edge1);
+        let v = self.direction.dot(&q) / determinant;
+        if v < Number::ZERO || u + v > Number::ONE {
+            return None;
+        }
+        let distance = edge2.dot(&q) / determinant;
+        (distance >= Length::zero()).then_some(distance)
+    }
+
+    /// Returns the distance along the ray to where it enters the sphere, zero if it starts
+    /// inside, or `None` if it misses.
+    pub fn intersect_sphere(&self, sphere: &Sphere<Length>) -> Option<Length> {
+        // from Ericson, Real-Time Collision Detection, 5.3.2
+        let m = self.origin - sphere.center;
+        let b = m.dot(&self.direction);
+        let c = m.dot(&m) - sphere.radius * sphere.radius;
+        if c > Area::zero() && b > Length::zero() {
+            return None;
+        }
+        let discriminant = b * b - c;
+        if discriminant < Area::zero() {
+            return None;
+        }
+        Some((-b - discriminant.sqrt()).max(Length::zero()))
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Ray<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.origin.approx_eq(&rhs.origin) && self.direction.approx_eq(&rhs.direction)
+    }
+}
+
+impl<N: Validate> Validate for Ray<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.origin.validate()?;
+        self.direction.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::num;
+    use villagekit_unit::qty;
+
+    use super::*;
+    use crate::X_AXIS;
+
+    #[test]
+    fn pick_box() {
+        let beam = Aabb::new(
+            Vector3::new(qty!(1 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(3 m), qty!(0.5 m), qty!(0.5 m)),
+        );
+        let ray = Ray::new(Vector3::new(qty!(0 m), qty!(0.25 m), qty!(0.25 m)), X_AXIS);
+        assert_eq!(
+            ray.intersect_aabb(&beam),
+            Some(Span::new(qty!(1 m), qty!(3 m)))
+        );
+        assert_eq!(ray.at(qty!(2 m)), beam.center());
+        let inside = Ray::new(beam.center(), Vector3::new(num!(0), num!(-1), num!(0)));
+        assert_eq!(
+            inside.intersect_aabb(&beam),
+            Some(Span::new(qty!(0 m), qty!(0.25 m)))
+        );
+        let away = Ray::new(ray.origin, -Number::ONE * X_AXIS);
+        assert_eq!(away.intersect_aabb(&beam), None);
+        let above = Ray::new(Vector3::new(qty!(0 m), qty!(1 m), qty!(0.25 m)), X_AXIS);
+        assert_eq!(above.intersect_aabb(&beam), None);
+        let edge = Vector3::new(qty!(3 m), qty!(0.5 m), qty!(0.25 m));
+        assert_eq!(above.distance_to_point(edge), qty!(0.5 m));
+        assert_eq!(away.closest_point(beam.max), away.origin);
+    }
+
+    #[test]
+    fn pick_triangle_and_sphere() {
+        let triangle = Triangle::new(
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(1 m)),
+            Vector3::new(qty!(2 m), qty!(0 m), qty!(1 m)),
+            Vector3::new(qty!(0 m), qty!(2 m), qty!(1 m)),
+        );
+        let down = Vector3::new(num!(0), num!(0), num!(-1));
+        let ray = Ray::new(Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(3 m)), down);
+        assert_eq!(ray.intersect_triangle(&triangle), Some(qty!(2 m)));
+        let miss = Ray::new(Vector3::new(qty!(1.5 m), qty!(1.5 m), qty!(3 m)), down);
+        assert_eq!(miss.intersect_triangle(&triangle), None);
+        let along = Ray::new(Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(1 m)), X_AXIS);
+        assert_eq!(along.intersect_triangle(&triangle), None);
+
+        let ball = Sphere::new(
+            Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(0 m)),
+            qty!(0.5 m),
+        );
+        assert_eq!(ray.intersect_sphere(&ball), Some(qty!(2.5 m)));
+        assert_eq!(miss.intersect_sphere(&ball), None);
+        let from_inside = Ray::new(ball.center, X_AXIS);
+        assert_eq!(from_inside.intersect_sphere(&ball), Some(qty!(0 m)));
+    }
+}
@@ -0,0 +1,634 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, TrigInv, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{Area, Length};
+
+use crate::{
+    polygon::{ear_clip, on_segment},
+    Orientation, Polygon, Triangulation, Vector2,
+};
+
+/// An area bounded by an outer polygon, with polygon holes cut out of it, e.g. a sheet part
+/// with openings.
+///
+/// The outer polygon winds anticlockwise and the holes clockwise, so the inside is always
+/// to the left of an edge.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(
+    from = "Fields<N>",
+    bound(deserialize = "N: Deserialize<'de>, Region<N>: From<Fields<N>>")
+)]
+pub struct Region<N> {
+    outer: Polygon<N>,
+    holes: Vec<Polygon<N>>,
+}
+
+/// The fields of a [`Region`] as deserialized, before their orientation is fixed.
+#[derive(Deserialize)]
+struct Fields<N> {
+    outer: Polygon<N>,
+    holes: Vec<Polygon<N>>,
+}
+
+impl From<Fields<Length>> for Region<Length> {
+    fn from(fields: Fields<Length>) -> Self {
+        Self::new(fields.outer, fields.holes)
+    }
+}
+
+impl<N> Region<N> {
+    pub fn outer(&self) -> &Polygon<N> {
+        &self.outer
+    }
+
+    pub fn holes(&self) -> &[Polygon<N>] {
+        &self.holes
+    }
+}
+
+/// A hole which [`Region::triangulate`] can't join to the outer polygon, as it overlaps the
+/// outer polygon or another hole.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnbridgedHole {
+    /// The index of the hole in [`Region::holes`].
+    pub index: usize,
+}
+
+impl fmt::Display for UnbridgedHole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hole {} overlaps the outline or another hole",
+            self.index
+        )
+    }
+}
+
+impl std::error::Error for UnbridgedHole {}
+
+impl Region<Length> {
+    /// Creates the region, fixing the orientation of the outer polygon and holes.
+    pub fn new(outer: Polygon<Length>, holes: Vec<Polygon<Length>>) -> Self {
+        Self {
+            outer: outer.with_orientation(Orientation::Anticlockwise),
+            holes: holes
+                .into_iter()
+                .map(|hole| hole.with_orientation(Orientation::Clockwise))
+                .collect(),
+        }
+    }
+
+    /// The outer polygon and then the holes.
+    pub fn polygons(&self) -> impl Iterator<Item = &Polygon<Length>> {
+        std::iter::once(&self.outer).chain(&self.holes)
+    }
+
+    pub fn area(&self) -> Area {
+        self.polygons()
+            .fold(Area::zero(), |sum, polygon| sum + polygon.signed_area())
+    }
+
+    /// The length around the outer polygon and every hole.
+    pub fn perimeter(&self) -> Length {
+        self.polygons()
+            .fold(Length::zero(), |sum, polygon| sum + polygon.perimeter())
+    }
+
+    /// Whether the point is inside the region or on its edges.
+    pub fn contains(&self, point: Vector2<Length>) -> bool {
+        self.outer.contains(point)
+            && self
+                .holes
+                .iter()
+                .all(|hole| !hole.contains(point) || hole.on_edge(point))
+    }
+
+    /// Moves every edge outwards by `distance`, or inwards for a negative distance, so the
+    /// holes shrink as the outer polygon grows. See [`Polygon::offset`].
+    pub fn offset(&self, distance: Length) -> Self {
+        Self {
+            outer: self.outer.offset(distance),
+            holes: self
+                .holes
+                .iter()
+                .map(|hole| hole.offset(-distance))
+                .collect(),
+        }
+    }
+
+    /// Splits the region into triangles, by bridging each hole to the outer polygon and
+    /// clipping ears off the result, or returns the first hole which can't be bridged.
+    pub fn triangulate(&self) -> Result<Triangulation<Length>, UnbridgedHole> {
+        let points: Vec<_> = self
+            .polygons()
+            .flat_map(|polygon| polygon.points.iter().copied())
+            .collect();
+        let mut indices: Vec<usize> = (0..self.outer.points.len()).collect();
+
+        let mut holes = Vec::with_capacity(self.holes.len());
+        let mut start = self.outer.points.len();
+        for (index, hole) in self.holes.iter().enumerate() {
+            holes.push((
+                index,
+                (start..start + hole.points.len()).collect::<Vec<_>>(),
+            ));
+            start += hole.points.len();
+        }
+        // Bridge the rightmost hole first, so a bridge never crosses a hole still to come.
+        holes.sort_by_key(|(_, hole)| hole.iter().map(|&i| points[i].x).max());
+        while let Some((index, hole)) = holes.pop() {
+            indices =
+                bridge_hole(&points, &indices, &hole, &holes).ok_or(UnbridgedHole { index })?;
+        }
+
+        Ok(Triangulation {
+            triangles: ear_clip(&points, indices),
+            points,
+        })
+    }
+
+    /// The area covered by either region.
+    ///
+    /// Like [`Region::intersection`] and [`Region::difference`], this returns each separate
+    /// piece as its own region, with any holes left inside it.
+    pub fn union(&self, other: &Self) -> Vec<Self> {
+        combine(self, other, Operation::Union)
+    }
+
+    /// The area covered by both regions.
+    pub fn intersection(&self, other: &Self) -> Vec<Self> {
+        combine(self, other, Operation::Intersection)
+    }
+
+    /// The area covered by this region but not the other.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        combine(self, other, Operation::Difference)
+    }
+}
+
+impl From<Polygon<Length>> for Region<Length> {
+    fn from(outer: Polygon<Length>) -> Self {
+        Self::new(outer, Vec::new())
+    }
+}
+
+/// Splices the hole into the anticlockwise polygon through `indices`, along a bridge from
+/// its rightmost point to the closest point of the polygon it can see.
+fn bridge_hole(
+    points: &[Vector2<Length>],
+    indices: &[usize],
+    hole: &[usize],
+    other_holes: &[(usize, Vec<usize>)],
+) -> Option<Vec<usize>> {
+    let (start, &from) = hole.iter().enumerate().max_by_key(|&(_, &i)| points[i].x)?;
+    let point = points[from];
+
+    let ring_edges = |ring: &[usize]| {
+        (0..ring.len())
+            .map(|i| (points[ring[i]], points[ring[(i + 1) % ring.len()]]))
+            .collect::<Vec<_>>()
+    };
+    let mut edges = ring_edges(indices);
+    edges.extend(ring_edges(hole));
+    for (_, other) in other_holes {
+        edges.extend(ring_edges(other));
+    }
+
+    let count = indices.len();
+    let mut candidates: Vec<usize> = (0..count).collect();
+    candidates.sort_by_key(|&i| {
+        let offset = points[indices[i]] - point;
+        offset.dot(&offset)
+    });
+    let position = candidates.into_iter().find(|&i| {
+        let to = points[indices[i]];
+        let previous = points[indices[(i + count - 1) % count]];
+        let next = points[indices[(i + 1) % count]];
+        in_wedge(point - to, previous - to, next - to)
+            && edges.iter().all(|&(a, b)| !crosses(point, to, a, b))
+            && points
+                .iter()
+                .all(|&other| other == point || other == to || !on_segment(other, point, to))
+    })?;
+
+    let mut bridged = Vec::with_capacity(count + hole.len() + 2);
+    bridged.extend_from_slice(&indices[..=position]);
+    bridged.extend((0..=hole.len()).map(|i| hole[(start + i) % hole.len()]));
+    bridged.extend_from_slice(&indices[position..]);
+    Some(bridged)
+}
+
+/// Whether `direction` points inside the corner of an anticlockwise polygon, between the
+/// offsets to the `previous` and `next` points.
+fn in_wedge(direction: Vector2<Length>, previous: Vector2<Length>, next: Vector2<Length>) -> bool {
+    let zero = Area::zero();
+    let after_next = next.cross(&direction) > zero;
+    let before_previous = direction.cross(&previous) > zero;
+    if next.cross(&previous) > zero {
+        after_next && before_previous
+    } else {
+        after_next || before_previous
+    }
+}
+
+/// Whether the segments cross at a point inside both of them.
+fn crosses(a: Vector2<Length>, b: Vector2<Length>, c: Vector2<Length>, d: Vector2<Length>) -> bool {
+    let zero = Area::zero();
+    let side = |p: Vector2<Length>, q: Vector2<Length>, r: Vector2<Length>| (q - p).cross(&(r - p));
+    let (abc, abd) = (side(a, b, c), side(a, b, d));
+    let (cda, cdb) = (side(c, d, a), side(c, d, b));
+    ((abc > zero && abd < zero) || (abc < zero && abd > zero))
+        && ((cda > zero && cdb < zero) || (cda < zero && cdb > zero))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// Where an edge of one region is relative to the other region.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Side {
+    Inside,
+    Outside,
+    /// On an edge of the other region going the same way.
+    Same,
+    /// On an edge of the other region going the opposite way.
+    Opposite,
+}
+
+type Edge = (Vector2<Length>, Vector2<Length>);
+
+/// Combines the regions by keeping the edges of each on the right side of the other and
+/// linking them into rings, which wind anticlockwise around the new regions and clockwise
+/// around their holes.
+///
+/// A clockwise ring outside every anticlockwise one should only come from rounding, as the
+/// edges kept always enclose what they cut out, so such a hole is dropped like an open chain
+/// in [`link_rings`].
+fn combine(a: &Region<Length>, b: &Region<Length>, operation: Operation) -> Vec<Region<Length>> {
+    let (a_edges, b_edges) = split_edges(a, b);
+    let a_set: BTreeSet<Edge> = a_edges.iter().copied().collect();
+    let b_set: BTreeSet<Edge> = b_edges.iter().copied().collect();
+    let side = |(start, end): Edge, other: &Region<Length>, other_set: &BTreeSet<Edge>| {
+        if other_set.contains(&(start, end)) {
+            Side::Same
+        } else if other_set.contains(&(end, start)) {
+            Side::Opposite
+        } else if other.contains((start + end) * Number::HALF) {
+            Side::Inside
+        } else {
+            Side::Outside
+        }
+    };
+
+    // Shared edges are kept from `a` only, so they aren't doubled up.
+    let mut edges = Vec::new();
+    for &edge in &a_edges {
+        let keep = match (operation, side(edge, b, &b_set)) {
+            (Operation::Union, side) => side == Side::Outside || side == Side::Same,
+            (Operation::Intersection, side) => side == Side::Inside || side == Side::Same,
+            (Operation::Difference, side) => side == Side::Outside || side == Side::Opposite,
+        };
+        if keep {
+            edges.push(edge);
+        }
+    }
+    for &(start, end) in &b_edges {
+        match (operation, side((start, end), a, &a_set)) {
+            (Operation::Union, Side::Outside) | (Operation::Intersection, Side::Inside) => {
+                edges.push((start, end))
+            }
+            (Operation::Difference, Side::Inside) => edges.push((end, start)),
+            _ => {}
+        }
+    }
+
+    let mut outers = Vec::new();
+    let mut holes = Vec::new();
+    for ring in link_rings(edges) {
+        let polygon = Polygon::new(ring);
+        let area = polygon.signed_area();
+        if area > Area::zero() {
+            outers.push(Region {
+                outer: polygon,
+                holes: Vec::new(),
+            });
+        } else if area < Area::zero() {
+            holes.push(polygon);
+        }
+    }
+    outers.sort_by_key(|region| region.outer.area());
+    for hole in holes {
+        // The smallest outer polygon around the hole is the one it is cut out of.
+        if let Some(region) = outers.iter_mut().find(|region| {
+            hole.points
+                .iter()
+                .all(|&point| region.outer.contains(point))
+        }) {
+            region.holes.push(hole);
+        }
+    }
+    outers
+}
+
+/// The edges of each region, split wherever they meet an edge of the other.
+fn split_edges(a: &Region<Length>, b: &Region<Length>) -> (Vec<Edge>, Vec<Edge>) {
+    let edges = |region: &Region<Length>| {
+        region
+            .polygons()
+            .flat_map(|polygon| polygon.edges())
+            .filter(|(start, end)| start != end)
+            .collect::<Vec<_>>()
+    };
+    let (a_edges, b_edges) = (edges(a), edges(b));
+    let mut a_splits = vec![Vec::new(); a_edges.len()];
+    let mut b_splits = vec![Vec::new(); b_edges.len()];
+
+    for (i, &(p, p_end)) in a_edges.iter().enumerate() {
+        for (j, &(q, q_end)) in b_edges.iter().enumerate() {
+            let (r, s) = (p_end - p, q_end - q);
+            let denominator = r.cross(&s);
+            if denominator == Area::zero() {
+                // Parallel edges only meet if they overlap along a line.
+                if (q - p).cross(&r) == Area::zero() {
+                    a_splits[i].extend([q, q_end].into_iter().filter(|&x| on_segment(x, p, p_end)));
+                    b_splits[j].extend([p, p_end].into_iter().filter(|&x| on_segment(x, q, q_end)));
+                }
+                continue;
+            }
+            let t = (q - p).cross(&s) / denominator;
+            let u = (q - p).cross(&r) / denominator;
+            let within = |v: Number| {
+                (v >= Number::ZERO || v.approx_eq(&Number::ZERO))
+                    && (v <= Number::ONE || v.approx_eq(&Number::ONE))
+            };
+            if !within(t) || !within(u) {
+                continue;
+            }
+            // Snap to an existing point where there is one, so both edges split at exactly
+            // the same point.
+            let point = if t.approx_eq(&Number::ZERO) {
+                p
+            } else if t.approx_eq(&Number::ONE) {
+                p_end
+            } else if u.approx_eq(&Number::ZERO) {
+                q
+            } else if u.approx_eq(&Number::ONE) {
+                q_end
+            } else {
+                p + r * t
+            };
+            a_splits[i].push(point);
+            b_splits[j].push(point);
+        }
+    }
+
+    let split = |edges: Vec<Edge>, splits: Vec<Vec<Vector2<Length>>>| {
+        let mut split = Vec::with_capacity(edges.len());
+        for ((start, end), mut points) in edges.into_iter().zip(splits) {
+            points.push(start);
+            points.push(end);
+            points.sort_by_key(|&point| (point - start).dot(&(end - start)));
+            points.dedup();
+            split.extend(points.windows(2).map(|pair| (pair[0], pair[1])));
+        }
+        split
+    };
+    (split(a_edges, a_splits), split(b_edges, b_splits))
+}
+
+/// Joins edges end to start into closed rings, taking the sharpest turn to the left where
+/// more than one edge goes on from a point, so rings which touch stay apart.
+fn link_rings(edges: Vec<Edge>) -> Vec<Vec<Vector2<Length>>> {
+    let mut from: BTreeMap<Vector2<Length>, Vec<Vector2<Length>>> = BTreeMap::new();
+    for (start, end) in edges {
+        from.entry(start).or_default().push(end);
+    }
+
+    let mut rings = Vec::new();
+    while let Some((&first, _)) = from.iter().find(|(_, ends)| !ends.is_empty()) {
+        let mut ring = vec![first];
+        let mut previous = first;
+        let mut current = from.get_mut(&first).unwrap().pop().unwrap();
+        while current != first {
+            let Some(ends) = from.get_mut(&current).filter(|ends| !ends.is_empty()) else {
+                // An open chain, which can only come from rounding: drop it.
+                ring.clear();
+                break;
+            };
+            let incoming = current - previous;
+            let turn = |end: &Vector2<Length>| {
+                let outgoing = *end - current;
+                let angle = incoming
+                    .cross(&outgoing)
+                    .canonical()
+                    .atan2(incoming.dot(&outgoing).canonical());
+                // Doubling straight back is the last choice.
+                if incoming.cross(&outgoing) == Area::zero() && angle > Number::ZERO {
+                    -angle
+                } else {
+                    angle
+                }
+            };
+            let (index, _) = ends
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, end)| turn(end))
+                .unwrap();
+            ring.push(current);
+            previous = current;
+            current = ends.swap_remove(index);
+        }
+        if ring.len() >= 3 {
+            rings.push(simplify(ring));
+        }
+    }
+    rings
+}
+
+/// Drops points in the middle of straight runs.
+fn simplify(mut ring: Vec<Vector2<Length>>) -> Vec<Vector2<Length>> {
+    let mut i = 0;
+    while i < ring.len() && ring.len() > 3 {
+        let count = ring.len();
+        let previous = ring[(i + count - 1) % count];
+        let next = ring[(i + 1) % count];
+        let (incoming, outgoing) = (ring[i] - previous, next - ring[i]);
+        if incoming.cross(&outgoing) == Area::zero() && incoming.dot(&outgoing) > Area::zero() {
+            ring.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    ring
+}
+
+impl<N: ApproxEq> ApproxEq for Region<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.outer.approx_eq(&rhs.outer)
+            && self.holes.len() == rhs.holes.len()
+            && self
+                .holes
+                .iter()
+                .zip(&rhs.holes)
+                .all(|(a, b)| a.approx_eq(b))
+    }
+}
+
+impl<N: Validate> Validate for Region<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.outer.validate()?;
+        self.holes.iter().try_for_each(Validate::validate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    macro_rules! square {
+        ($x:literal, $y:literal, $size:literal) => {{
+            let (x, y, size) = (qty!($x m), qty!($y m), qty!($size m));
+            Region::from(Polygon::new(vec![
+                Vector2::new(x, y),
+                Vector2::new(x + size, y),
+                Vector2::new(x + size, y + size),
+                Vector2::new(x, y + size),
+            ]))
+        }};
+    }
+
+    fn total_area(regions: &[Region<Length>]) -> Area {
+        regions
+            .iter()
+            .fold(Area::zero(), |sum, region| sum + region.area())
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let a = square!(0, 0, 2);
+        let b = square!(1, 1, 2);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].outer.points.len(), 8);
+        assert_eq!(total_area(&union), qty!(7 m2));
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.len(), 1);
+        assert_eq!(intersection[0].outer.points.len(), 4);
+        assert_eq!(intersection[0].area(), qty!(1 m2));
+        assert_eq!(total_area(&a.difference(&b)), qty!(3 m2));
+        assert_eq!(total_area(&b.difference(&a)), qty!(3 m2));
+
+        let apart = square!(5, 5, 1);
+        assert_eq!(a.union(&apart).len(), 2);
+        assert!(a.intersection(&apart).is_empty());
+        assert_eq!(a.difference(&apart), vec![a.clone()]);
+    }
+
+    #[test]
+    fn shared_edges() {
+        let a = square!(0, 0, 1);
+        assert_eq!(total_area(&a.union(&a)), qty!(1 m2));
+        assert_eq!(total_area(&a.intersection(&a)), qty!(1 m2));
+        assert!(a.difference(&a).is_empty());
+
+        let beside = square!(1, 0, 1);
+        let merged = a.union(&beside);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].outer.points.len(), 4);
+        assert_eq!(merged[0].area(), qty!(2 m2));
+        assert!(a.intersection(&beside).is_empty());
+
+        // Squares touching at a corner stay apart.
+        assert_eq!(a.union(&square!(1, 1, 1)).len(), 2);
+    }
+
+    #[test]
+    fn holes() {
+        let sheet = square!(0, 0, 4);
+        let opening = square!(1, 1, 1);
+        let cut = sheet.difference(&opening);
+        assert_eq!(cut.len(), 1);
+        assert_eq!(cut[0].holes.len(), 1);
+        assert_eq!(cut[0].area(), qty!(15 m2));
+        assert_eq!(cut[0].perimeter(), qty!(20 m));
+        assert!(!cut[0].contains(Vector2::new(qty!(1.5 m), qty!(1.5 m))));
+        assert!(cut[0].contains(Vector2::new(qty!(1 m), qty!(1.5 m))));
+        assert!(cut[0].contains(Vector2::new(qty!(3 m), qty!(3 m))));
+
+        // Filling the hole again leaves the sheet whole.
+        let filled = cut[0].union(&opening);
+        assert_eq!(filled.len(), 1);
+        assert!(filled[0].holes.is_empty());
+        assert_eq!(filled[0].area(), qty!(16 m2));
+
+        // A patch over the hole and the edge of the sheet.
+        let patch = square!(0.5, -0.5, 2);
+        assert_eq!(total_area(&cut[0].intersection(&patch)), qty!(2.5 m2));
+
+        let grown = cut[0].offset(qty!(0.25 m));
+        assert_eq!(
+            grown.area(),
+            qty!(4.5 m) * qty!(4.5 m) - qty!(0.5 m) * qty!(0.5 m)
+        );
+    }
+
+    #[test]
+    fn triangulate() {
+        let sheet = square!(0, 0, 4).difference(&square!(1, 1, 1));
+        let triangulation = sheet[0].triangulate().unwrap();
+        assert_eq!(triangulation.area(), qty!(15 m2));
+        assert!(triangulation.triangles.iter().all(|&[a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|i| triangulation.points[i]);
+            (b - a).cross(&(c - a)) > Area::zero()
+        }));
+
+        let two_holes = square!(0, 0, 4)
+            .difference(&square!(0.5, 0.5, 1))
+            .remove(0)
+            .difference(&square!(2, 2, 1))
+            .remove(0);
+        assert_eq!(two_holes.holes.len(), 2);
+        assert_eq!(two_holes.triangulate().unwrap().area(), qty!(14 m2));
+
+        // A hole sticking out past the edge has no way in to the outline.
+        let overlapping = Region::new(
+            square!(0, 0, 4).outer,
+            vec![square!(1, 1, 1).outer, square!(3, 1, 2).outer],
+        );
+        assert_eq!(overlapping.triangulate(), Err(UnbridgedHole { index: 1 }));
+    }
+
+    #[test]
+    fn deserialize_orients() {
+        let cut = square!(0, 0, 4).difference(&square!(1, 1, 1)).remove(0);
+        let json = serde_json::to_string(&cut).unwrap();
+        assert_eq!(serde_json::from_str::<Region<Length>>(&json).unwrap(), cut);
+
+        // Polygons wound the wrong way round are turned back, so the area stays positive.
+        let backwards = Region {
+            outer: Polygon::new(cut.outer.points.iter().rev().copied().collect()),
+            holes: vec![Polygon::new(
+                cut.holes[0].points.iter().rev().copied().collect(),
+            )],
+        };
+        assert_eq!(backwards.area(), qty!(-15 m2));
+        let json = serde_json::to_string(&backwards).unwrap();
+        let region = serde_json::from_str::<Region<Length>>(&json).unwrap();
+        assert_eq!(region.area(), qty!(15 m2));
+        assert_eq!(region.outer.orientation(), Orientation::Anticlockwise);
+        assert_eq!(region.holes[0].orientation(), Orientation::Clockwise);
+    }
+}
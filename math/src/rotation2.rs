@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Sub};
+use villagekit_number::{
+    num,
+    traits::{ApproxEq, Sqrt, Trig, TrigInv, Validate},
+    Number, NumberError,
+};
+use villagekit_unit::Angle;
+
+use crate::Vector2;
+
+/// A rotation in 2D, anticlockwise for positive angles, stored as the cosine and sine of
+/// its angle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Rotation2 {
+    pub cos: Number,
+    pub sin: Number,
+}
+
+impl Rotation2 {
+    pub const IDENTITY: Self = Self {
+        cos: num!(1),
+        sin: num!(0),
+    };
+
+    pub fn from_angle(angle: Angle) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { cos, sin }
+    }
+
+    /// The angle of the rotation, between -180° and 180°.
+    pub fn angle(self) -> Angle {
+        Angle::from_canonical(self.sin.atan2(self.cos))
+    }
+
+    /// Returns the rotation by both rotations, `self` then `other`.
+    pub fn multiply(self, other: Rotation2) -> Self {
+        Self {
+            cos: self.cos * other.cos - self.sin * other.sin,
+            sin: self.sin * other.cos + self.cos * other.sin,
+        }
+    }
+
+    /// Returns the rotation back by the same angle.
+    pub fn inverse(self) -> Self {
+        Self {
+            cos: self.cos,
+            sin: -self.sin,
+        }
+    }
+
+    pub fn normalize(self) -> Self {
+        let length = (self.cos * self.cos + self.sin * self.sin).sqrt();
+        if length == Number::ZERO {
+            Self::default()
+        } else {
+            Self {
+                cos: self.cos / length,
+                sin: self.sin / length,
+            }
+        }
+    }
+
+    pub fn rotate<N>(self, v: Vector2<N>) -> Vector2<N>
+    where
+        N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Number, Output = N>,
+    {
+        Vector2::new(
+            v.x * self.cos - v.y * self.sin,
+            v.x * self.sin + v.y * self.cos,
+        )
+    }
+}
+
+impl Default for Rotation2 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Rotation2 {
+    type Output = Rotation2;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.multiply(rhs)
+    }
+}
+
+impl<N> Mul<Vector2<N>> for Rotation2
+where
+    N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Number, Output = N>,
+{
+    type Output = Vector2<N>;
+
+    fn mul(self, rhs: Vector2<N>) -> Self::Output {
+        self.rotate(rhs)
+    }
+}
+
+impl ApproxEq for Rotation2 {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.cos.approx_eq(&rhs.cos) && self.sin.approx_eq(&rhs.sin)
+    }
+}
+
+impl Validate for Rotation2 {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.cos.validate()?;
+        self.sin.validate()
+    }
+}
+
+impl From<Rotation2> for glam::Mat2 {
+    fn from(value: Rotation2) -> Self {
+        let (cos, sin): (f32, f32) = (value.cos.into(), value.sin.into());
+        glam::Mat2::from_cols_array(&[cos, sin, -sin, cos])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    #[test]
+    fn compose_and_invert() {
+        let quarter = Rotation2::from_angle(qty!(90 deg));
+        let third = Rotation2::from_angle(qty!(120 deg));
+        assert!((quarter * third).angle().approx_eq(&qty!(-150 deg)));
+        assert!((third * third.inverse()).approx_eq(&Rotation2::IDENTITY));
+        assert!((quarter * quarter).approx_eq(&Rotation2::from_angle(qty!(180 deg))));
+    }
+}
@@ -0,0 +1,133 @@
+use std::ops::{Add, Sub};
+use villagekit_number::traits::Abs;
+use villagekit_unit::Span;
+
+use crate::Vector3;
+
+/// A box of three spans, one along each axis, e.g. the space a beam takes up in a grid.
+pub type SpanBox<N> = Vector3<Span<N>>;
+
+impl<N> Vector3<Span<N>> {
+    /// Creates the box spanning from one corner to the opposite corner.
+    pub fn between(start: Vector3<N>, end: Vector3<N>) -> Self {
+        Vector3::new(
+            Span::new(start.x, end.x),
+            Span::new(start.y, end.y),
+            Span::new(start.z, end.z),
+        )
+    }
+}
+
+impl<N: Copy> Vector3<Span<N>> {
+    /// The corner at the start of each span.
+    pub fn start(&self) -> Vector3<N> {
+        Vector3::new(self.x.start, self.y.start, self.z.start)
+    }
+
+    /// The corner at the end of each span.
+    pub fn end(&self) -> Vector3<N> {
+        Vector3::new(self.x.end, self.y.end, self.z.end)
+    }
+}
+
+impl<N> Vector3<Span<N>>
+where
+    N: Copy + PartialOrd,
+{
+    /// The corner with the lowest value along each axis.
+    pub fn min(&self) -> Vector3<N> {
+        Vector3::new(self.x.min(), self.y.min(), self.z.min())
+    }
+
+    /// The corner with the highest value along each axis.
+    pub fn max(&self) -> Vector3<N> {
+        Vector3::new(self.x.max(), self.y.max(), self.z.max())
+    }
+
+    pub fn contains(&self, point: Vector3<N>) -> bool {
+        self.x.contains(point.x) && self.y.contains(point.y) && self.z.contains(point.z)
+    }
+
+    /// Returns the box where both boxes overlap, or `None` if they don't.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Vector3::new(
+            self.x.intersection(&other.x)?,
+            self.y.intersection(&other.y)?,
+            self.z.intersection(&other.z)?,
+        ))
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        Vector3::new(
+            self.x.union(&other.x),
+            self.y.union(&other.y),
+            self.z.union(&other.z),
+        )
+    }
+}
+
+impl<N> Vector3<Span<N>>
+where
+    N: Copy + Sub<Output = N> + Abs<Output = N>,
+{
+    /// The absolute length of the box along each axis.
+    pub fn size(&self) -> Vector3<N> {
+        Vector3::new(
+            self.x.abs_length(),
+            self.y.abs_length(),
+            self.z.abs_length(),
+        )
+    }
+}
+
+/// Moves the box by an offset.
+impl<N> Add<Vector3<N>> for Vector3<Span<N>>
+where
+    N: Copy + Add<Output = N>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Vector3<N>) -> Self::Output {
+        Vector3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    #[test]
+    fn box_of_spans() {
+        let beam = SpanBox::between(
+            Vector3::new(qty!(0 m), qty!(40 mm), qty!(40 mm)),
+            Vector3::new(qty!(2 m), qty!(0 m), qty!(80 mm)),
+        );
+        assert_eq!(
+            beam.size(),
+            Vector3::new(qty!(2 m), qty!(40 mm), qty!(40 mm))
+        );
+        assert_eq!(beam.min(), Vector3::new(qty!(0 m), qty!(0 m), qty!(40 mm)));
+        assert!(beam.contains(Vector3::new(qty!(1 m), qty!(20 mm), qty!(60 mm))));
+
+        let post = SpanBox::between(
+            Vector3::new(qty!(1 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(1040 mm), qty!(40 mm), qty!(1 m)),
+        );
+        assert_eq!(
+            beam.intersection(&post),
+            Some(SpanBox::between(
+                Vector3::new(qty!(1 m), qty!(0 m), qty!(40 mm)),
+                Vector3::new(qty!(1040 mm), qty!(40 mm), qty!(80 mm)),
+            ))
+        );
+        let moved = post + Vector3::new(qty!(0 m), qty!(1 m), qty!(0 m));
+        assert_eq!(beam.intersection(&moved), None);
+        assert_eq!(
+            beam.union(&post).max(),
+            Vector3::new(qty!(2 m), qty!(40 mm), qty!(1 m))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    traits::{ApproxEq, Validate, Zero},
+    NumberError,
+};
+use villagekit_unit::Length;
+
+use crate::{Aabb, Vector3};
+
+/// A ball around a center, e.g. the clearance around a fixing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Sphere<N> {
+    pub center: Vector3<N>,
+    pub radius: N,
+}
+
+impl<N> Sphere<N> {
+    pub const fn new(center: Vector3<N>, radius: N) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl Sphere<Length> {
+    /// Whether the point is inside the sphere or on its surface.
+    pub fn contains(&self, point: Vector3<Length>) -> bool {
+        let offset = point - self.center;
+        offset.dot(&offset) <= self.radius * self.radius
+    }
+
+    /// The distance from the surface of the sphere to the point, negative inside it.
+    pub fn signed_distance(&self, point: Vector3<Length>) -> Length {
+        (point - self.center).length() - self.radius
+    }
+
+    /// The point on the surface of the sphere closest to `point`, or the center if that is
+    /// where `point` is.
+    pub fn closest_point(&self, point: Vector3<Length>) -> Vector3<Length> {
+        let offset = point - self.center;
+        let distance = offset.length();
+        if distance == Length::zero() {
+            return self.center;
+        }
+        self.center + offset * (self.radius / distance)
+    }
+
+    /// Whether the spheres overlap or touch.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let offset = other.center - self.center;
+        let reach = self.radius + other.radius;
+        offset.dot(&offset) <= reach * reach
+    }
+
+    pub fn bounds(&self) -> Aabb<Length> {
+        let radius = Vector3::new(self.radius, self.radius, self.radius);
+        Aabb::new(self.center - radius, self.center + radius)
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Sphere<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.center.approx_eq(&rhs.center) && self.radius.approx_eq(&rhs.radius)
+    }
+}
+
+impl<N: Validate> Validate for Sphere<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.center.validate()?;
+        self.radius.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    #[test]
+    fn clearance() {
+        let sphere = Sphere::new(Vector3::new(qty!(1 m), qty!(1 m), qty!(0 m)), qty!(0.5 m));
+        let point = Vector3::new(qty!(1 m), qty!(3 m), qty!(0 m));
+        assert!(!sphere.contains(point));
+        assert!(sphere.contains(Vector3::new(qty!(1.5 m), qty!(1 m), qty!(0 m))));
+        assert_eq!(sphere.signed_distance(point), qty!(1.5 m));
+        assert_eq!(sphere.signed_distance(sphere.center), qty!(-0.5 m));
+        assert_eq!(
+            sphere.closest_point(point),
+            Vector3::new(qty!(1 m), qty!(1.5 m), qty!(0 m))
+        );
+        assert!(sphere.intersects(&Sphere::new(point, qty!(1.5 m))));
+        assert!(!sphere.intersects(&Sphere::new(point, qty!(1 m))));
+        assert_eq!(
+            sphere.bounds(),
+            Aabb::new(
+                Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(-0.5 m)),
+                Vector3::new(qty!(1.5 m), qty!(1.5 m), qty!(0.5 m)),
+            )
+        );
+    }
+}
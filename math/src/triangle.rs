@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use villagekit_number::{
+    num,
+    traits::{ApproxEq, Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{Area, Length};
+
+use crate::{LineSegment, Plane, Vector3};
+
+/// A flat triangle between three points, e.g. a face of a mesh.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Triangle<N> {
+    pub a: Vector3<N>,
+    pub b: Vector3<N>,
+    pub c: Vector3<N>,
+}
+
+impl<N> Triangle<N> {
+    pub const fn new(a: Vector3<N>, b: Vector3<N>, c: Vector3<N>) -> Self {
+        Self { a, b, c }
+    }
+}
+
+impl Triangle<Length> {
+    /// The unit vector square to the triangle, on the side its points wind anticlockwise
+    /// around, or zero if they are in a line.
+    pub fn normal(&self) -> Vector3<Number> {
+        (self.b - self.a)
+            .cross(&(self.c - self.a))
+            .map(|area| area.canonical())
+            .normalize()
+    }
+
+    pub fn area(&self) -> Area {
+        (self.b - self.a).cross(&(self.c - self.a)).length() * Number::HALF
+    }
+
+    pub fn centroid(&self) -> Vector3<Length> {
+        (self.a + self.b + self.c) / num!(3)
+    }
+
+    pub fn edges(&self) -> [LineSegment<Length>; 3] {
+        [
+            LineSegment::new(self.a, self.b),
+            LineSegment::new(self.b, self.c),
+            LineSegment::new(self.c, self.a),
+        ]
+    }
+
+    /// The plane of the triangle, facing its normal, or `None` if its points are in a line.
+    pub fn plane(&self) -> Option<Plane<Length>> {
+        let normal = self.normal();
+        (normal != Vector3::default()).then(|| Plane::from_point_normal(self.a, normal))
+    }
+
+    pub fn closest_point(&self, point: Vector3<Length>) -> Vector3<Length> {
+        // from Ericson, Real-Time Collision Detection, 5.1.5
+        let Self { a, b, c } = *self;
+        let (ab, ac) = (b - a, c - a);
+        let zero = Area::zero();
+
+        let ap = point - a;
+        let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+        if d1 <= zero && d2 <= zero {
+            return a;
+        }
+        let bp = point - b;
+        let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+        if d3 >= zero && d4 <= d3 {
+            return b;
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= Zero::zero() && d1 >= zero && d3 <= zero {
+            return a + ab * (d1 / (d1 - d3));
+        }
+        let cp = point - c;
+        let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+        if d6 >= zero && d5 <= d6 {
+            return c;
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= Zero::zero() && d2 >= zero && d6 <= zero {
+            return a + ac * (d2 / (d2 - d6));
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= Zero::zero() && d4 - d3 >= zero && d5 - d6 >= zero {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+        // Inside the face.
+        let sum = va + vb + vc;
+        a + ab * (vb / sum) + ac * (vc / sum)
+    }
+
+    pub fn distance_to_point(&self, point: Vector3<Length>) -> Length {
+        (point - self.closest_point(point)).length()
+    }
+}
+
+impl<N: ApproxEq> ApproxEq for Triangle<N> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.a.approx_eq(&rhs.a) && self.b.approx_eq(&rhs.b) && self.c.approx_eq(&rhs.c)
+    }
+}
+
+impl<N: Validate> Validate for Triangle<N> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.a.validate()?;
+        self.b.validate()?;
+        self.c.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+    use crate::Z_AXIS;
+
+    #[test]
+    fn closest_point() {
+        let triangle = Triangle::new(
+            Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(4 m), qty!(0 m), qty!(0 m)),
+            Vector3::new(qty!(0 m), qty!(4 m), qty!(0 m)),
+        );
+        assert_eq!(triangle.normal(), Z_AXIS);
+        assert_eq!(triangle.area(), qty!(8 m2));
+        assert_eq!(triangle.plane(), Some(Plane::new(Z_AXIS, qty!(0 m))));
+
+        macro_rules! point {
+            ($x:literal, $y:literal, $z:literal) => {
+                Vector3::new(qty!($x m), qty!($y m), qty!($z m))
+            };
+        }
+        // Above the face, past a corner, and past each edge.
+        let cases = [
+            (point!(1, 1, 2), point!(1, 1, 0)),
+            (point!(-1, -2, 0), point!(0, 0, 0)),
+            (point!(5, -1, 1), point!(4, 0, 0)),
+            (point!(2, -3, 0), point!(2, 0, 0)),
+            (point!(3, 3, 0), point!(2, 2, 0)),
+            (point!(-1, 2, 0), point!(0, 2, 0)),
+        ];
+        for (given, expected) in cases {
+            assert_eq!(triangle.closest_point(given), expected);
+        }
+        assert_eq!(triangle.distance_to_point(point!(1, 1, -2)), qty!(2 m));
+        assert_eq!(triangle.centroid(), point!(4, 4, 0) / num!(3));
+
+        let line = Triangle::new(triangle.a, triangle.b, triangle.b * num!(2));
+        assert_eq!(line.plane(), None);
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+use villagekit_number::{
+    traits::{ApproxEq, One, Sqrt, Validate},
+    Number, NumberError,
+};
+use villagekit_unit::{
+    with_units::{SerdeUnits, WithUnits},
+    Angle,
+};
+
+use crate::{Rotation2, Vector3};
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct Vector2<N> {
+    pub x: N,
+    pub y: N,
+}
+
+impl<N> Vector2<N> {
+    pub const fn new(x: N, y: N) -> Self {
+        Self { x, y }
+    }
+
+    /// Extends the vector into 3D, e.g. a point on a sheet at a height.
+    pub fn extend(self, z: N) -> Vector3<N> {
+        Vector3::new(self.x, self.y, z)
+    }
+}
+
+impl<N: Display> Display for Vector2<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<N> Add for Vector2<N>
+where
+    N: Add<N, Output = N>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<N> Sub for Vector2<N>
+where
+    N: Sub<N, Output = N>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<N> Neg for Vector2<N>
+where
+    N: Neg<Output = N>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<N> Vector2<N>
+where
+    N: Mul<Number, Output = N>,
+{
+    pub fn multiply_scalar(self, n: Number) -> Self {
+        Self::new(self.x * n, self.y * n)
+    }
+}
+
+impl<N> Mul<Number> for Vector2<N>
+where
+    N: Mul<Number, Output = N>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Number) -> Self::Output {
+        self.multiply_scalar(rhs)
+    }
+}
+
+impl<N> Mul<Vector2<N>> for Number
+where
+    N: Mul<Number, Output = N>,
+{
+    type Output = Vector2<N>;
+
+    fn mul(self, rhs: Vector2<N>) -> Self::Output {
+        rhs.multiply_scalar(self)
+    }
+}
+
+impl<N> Vector2<N>
+where
+    N: Div<Number, Output = N>,
+{
+    pub fn divide_scalar(self, n: Number) -> Self {
+        Self::new(self.x / n, self.y / n)
+    }
+}
+
+impl<N> Div<Number> for Vector2<N>
+where
+    N: Div<Number, Output = N>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Number) -> Self::Output {
+        self.divide_scalar(rhs)
+    }
+}
+
+impl<N> Vector2<N>
+where
+    N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Number, Output = N>,
+{
+    pub fn rotate(self, angle: Angle) -> Self {
+        Rotation2::from_angle(angle) * self
+    }
+}
+
+impl<N> ApproxEq for Vector2<N>
+where
+    N: ApproxEq,
+{
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.x.approx_eq(&rhs.x) && self.y.approx_eq(&rhs.y)
+    }
+}
+
+impl<N> Validate for Vector2<N>
+where
+    N: Validate,
+{
+    fn validate(&self) -> Result<(), NumberError> {
+        self.x.validate()?;
+        self.y.validate()
+    }
+}
+
+impl<N> SerdeUnits for Vector2<N>
+where
+    N: SerdeUnits + Copy,
+{
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.map(WithUnits).serialize(serializer)
+    }
+
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vector2::<WithUnits<N>>::deserialize(deserializer)?.map(|WithUnits(value)| value))
+    }
+}
+
+impl<N> Vector2<N>
+where
+    N: Copy + Add<Output = N> + Mul,
+    <N as Mul>::Output: Add<Output = <N as Mul>::Output> + Sqrt<Output = N>,
+{
+    pub fn length(self) -> N {
+        let Self { x, y } = self;
+        (x * x + y * y).sqrt()
+    }
+}
+
+impl<N> Vector2<N>
+where
+    N: Copy,
+    // self.length()
+    N: Add<Output = N> + Mul,
+    <N as Mul>::Output: Add<Output = <N as Mul>::Output> + Sqrt<Output = N>,
+    // (...) / N::one()
+    N: One + Div<N, Output = Number>,
+    // (...) / length
+    N: Div<Number, Output = N>,
+{
+    pub fn normalize(self) -> Self {
+        let length = self.length() / N::one();
+        if length == Number::ZERO {
+            self
+        } else {
+            self / length
+        }
+    }
+}
+
+impl<A> Vector2<A> {
+    pub fn dot<B>(&self, other: &Vector2<B>) -> <A as Mul<B>>::Output
+    where
+        B: Copy,
+        A: Copy + Mul<B>,
+        <A as Mul<B>>::Output: Add<Output = <A as Mul<B>>::Output>,
+    {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<A> Vector2<A> {
+    /// The z component of the 3D cross product, i.e. the signed area of the parallelogram
+    /// of both vectors: positive if `other` is anticlockwise from `self`.
+    pub fn cross<B>(&self, other: &Vector2<B>) -> <A as Mul<B>>::Output
+    where
+        B: Copy,
+        A: Copy + Mul<B>,
+        <A as Mul<B>>::Output: Sub<Output = <A as Mul<B>>::Output>,
+    {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<N> Vector2<N>
+where
+    N: Neg<Output = N>,
+{
+    /// The vector rotated a quarter turn anticlockwise.
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl<A> Vector2<A> {
+    pub fn map<B>(self, mapper: impl Fn(A) -> B) -> Vector2<B> {
+        let Vector2 { x, y } = self;
+        Vector2::new(mapper(x), mapper(y))
+    }
+}
+
+impl<N> From<Vector2<N>> for glam::Vec2
+where
+    N: Into<f32>,
+{
+    fn from(value: Vector2<N>) -> Self {
+        let Vector2 { x, y } = value;
+        glam::Vec2::new(x.into(), y.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::num;
+    use villagekit_unit::{qty, Length};
+
+    use super::*;
+
+    #[test]
+    fn products_of_lengths() {
+        let a = Vector2::new(qty!(3 m), qty!(0 m));
+        let b = Vector2::new(qty!(1 m), qty!(2 m));
+        assert_eq!(a.dot(&b), qty!(3 m2));
+        assert_eq!(a.cross(&b), qty!(6 m2));
+        assert_eq!(b.cross(&a), qty!(-6 m2));
+        assert_eq!(b.perp(), Vector2::new(qty!(-2 m), qty!(1 m)));
+        assert_eq!(Vector2::new(qty!(3 m), qty!(4 m)).length(), qty!(5 m));
+        assert_eq!(
+            Vector2::new(qty!(3 m), qty!(4 m)).normalize(),
+            Vector2::new(qty!(0.6 m), qty!(0.8 m))
+        );
+    }
+
+    #[test]
+    fn rotate_by_angle() {
+        let v: Vector2<Length> = Vector2::new(qty!(2 m), qty!(0 m));
+        assert!(v
+            .rotate(qty!(90 deg))
+            .approx_eq(&Vector2::new(qty!(0 m), qty!(2 m))));
+        assert!(v
+            .rotate(qty!(-60 deg))
+            .approx_eq(&Vector2::new(qty!(1 m), qty!(-1 m) * num!(3).sqrt())));
+    }
+}
@@ -1,16 +1,19 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::Display,
     ops::{Add, Div, Mul, Sub},
 };
 use villagekit_number::{
-    traits::{ApproxEq, One, Sqrt},
-    Number,
+    traits::{ApproxEq, One, Sqrt, Validate},
+    Number, NumberError,
 };
+use villagekit_unit::with_units::{SerdeUnits, WithUnits};
 
 use crate::Quaternion;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 pub struct Vector3<N> {
     pub x: N,
     pub y: N,
@@ -168,6 +171,30 @@ where
     }
 }
 
+impl<N> Validate for Vector3<N>
+where
+    N: Validate,
+{
+    fn validate(&self) -> Result<(), NumberError> {
+        self.x.validate()?;
+        self.y.validate()?;
+        self.z.validate()
+    }
+}
+
+impl<N> SerdeUnits for Vector3<N>
+where
+    N: SerdeUnits + Copy,
+{
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.map(WithUnits).serialize(serializer)
+    }
+
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vector3::<WithUnits<N>>::deserialize(deserializer)?.map(|WithUnits(value)| value))
+    }
+}
+
 impl<N> Vector3<N>
 where
     N: Copy + Add<Output = N> + Mul,
@@ -266,4 +293,58 @@ mod tests {
         let cross = a.cross(&b);
         assert_eq!(cross, Vector3::new(-3, 6, -3));
     }
+
+    #[test]
+    fn toleranced_vector() {
+        use villagekit_unit::{qty, Interval, Length};
+
+        let exact = |value: Length| Interval::point(value);
+        let offset = Vector3::new(
+            Interval::plus_minus(qty!(3 m), qty!(1 mm)),
+            exact(qty!(4 m)),
+            exact(qty!(0 m)),
+        );
+        let moved = offset + Vector3::new(exact(qty!(1 m)), exact(qty!(0 m)), exact(qty!(0 m)));
+        assert!(moved
+            .x
+            .approx_eq(&Interval::new(qty!(3.999 m), qty!(4.001 m))));
+
+        let length = offset.length();
+        assert!(length.contains(qty!(5 m)));
+        assert!(length
+            .lower
+            .approx_eq(&(qty!(2.999 m) * qty!(2.999 m) + qty!(16 m2)).sqrt()));
+        assert!(length
+            .upper
+            .approx_eq(&(qty!(3.001 m) * qty!(3.001 m) + qty!(16 m2)).sqrt()));
+    }
+
+    #[test]
+    fn differentiated_vector() {
+        use villagekit_unit::{qty, Dual, Length};
+
+        // How the diagonal of a 3 m × 4 m frame changes per metre of width.
+        let constant = |value: Length| Dual::constant(value);
+        let diagonal = Vector3::new(
+            Dual::new(qty!(3 m), qty!(1 m)),
+            constant(qty!(4 m)),
+            constant(qty!(0 m)),
+        );
+        let length = diagonal.length();
+        assert!(length.approx_eq(&Dual::new(qty!(5 m), qty!(0.6 m))));
+    }
+
+    #[test]
+    fn serialize_with_units() {
+        use villagekit_unit::{qty, with_units, Length};
+
+        let position = Vector3::new(qty!(40 mm), qty!(1.2 m), qty!(0 m));
+        let json = with_units::serialize(&position, serde_json::value::Serializer).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "x": "40 mm", "y": "1200 mm", "z": "0 mm" })
+        );
+        let read: Vector3<Length> = with_units::deserialize(json).unwrap();
+        assert_eq!(read, position);
+    }
 }
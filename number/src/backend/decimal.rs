@@ -0,0 +1,472 @@
+use fastnum::decimal::{self, Context, RoundingMode};
+use serde::{
+    de::{self, Visitor},
+    Deserializer, Serialize, Serializer,
+};
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::{error::ParseErrorKind, traits::Rounding, NumberError, ParseError};
+
+#[cfg(feature = "d256")]
+pub type Inner = fastnum::D256;
+#[cfg(not(feature = "d256"))]
+pub type Inner = fastnum::D128;
+
+const CONTEXT: Context = Context::default();
+const UNTRAPPED: Context = CONTEXT.without_traps();
+
+pub const ZERO: Inner = Inner::ZERO;
+pub const ONE: Inner = Inner::ONE;
+pub const TWO: Inner = Inner::TWO;
+
+pub const PI: Inner = Inner::PI;
+pub const FRAC_1_PI: Inner = Inner::FRAC_1_PI;
+pub const FRAC_2_PI: Inner = Inner::FRAC_2_PI;
+pub const FRAC_PI_2: Inner = Inner::FRAC_PI_2;
+pub const FRAC_PI_3: Inner = Inner::FRAC_PI_3;
+pub const FRAC_PI_4: Inner = Inner::FRAC_PI_4;
+
+pub const E: Inner = Inner::E;
+
+pub const EPSILON: Inner = Inner::EPSILON;
+pub const MIN: Inner = Inner::MIN;
+pub const MAX: Inner = Inner::MAX;
+pub const MIN_POSITIVE: Inner = Inner::MIN_POSITIVE;
+
+pub const DECIMAL: bool = true;
+
+/// Decimal arithmetic is exact up to the precision of the type, but functions
+/// like `sin` may be off in the last couple of digits.
+pub const APPROX_EPSILON: Inner = EPSILON.mul(Inner::TEN.mul(Inner::TEN));
+
+pub fn parse(s: &str) -> Result<Inner, ParseError> {
+    Inner::from_str(s, CONTEXT).map_err(|error| {
+        ParseError::new(match error {
+            decimal::ParseError::Empty => ParseErrorKind::Empty,
+            decimal::ParseError::PosOverflow
+            | decimal::ParseError::NegOverflow
+            | decimal::ParseError::ExponentOverflow => ParseErrorKind::Overflow,
+            _ => ParseErrorKind::Invalid,
+        })
+    })
+}
+
+pub const fn parse_unchecked(s: &str) -> Inner {
+    Inner::parse_str(s, CONTEXT)
+}
+
+pub const fn add(lhs: Inner, rhs: Inner) -> Inner {
+    lhs.add(rhs)
+}
+
+pub const fn sub(lhs: Inner, rhs: Inner) -> Inner {
+    lhs.sub(rhs)
+}
+
+pub const fn mul(lhs: Inner, rhs: Inner) -> Inner {
+    lhs.mul(rhs)
+}
+
+pub const fn div(lhs: Inner, rhs: Inner) -> Inner {
+    lhs.div(rhs)
+}
+
+/// Clears the signals raised by earlier operations, so those of the next one can be read.
+fn without_signals(value: Inner) -> Inner {
+    if value.is_finite() {
+        let exponent = -(value.fractional_digits_count() as i32);
+        operand(Inner::from_parts(
+            value.digits(),
+            exponent,
+            value.sign(),
+            CONTEXT,
+        ))
+    } else {
+        operand(value)
+    }
+}
+
+/// Steps an inexact result one unit in its last place in the direction of `rounding`, so
+/// it bounds the exact result.
+fn directed(result: Inner, rounding: Rounding) -> Inner {
+    if !result.is_op_inexact() || !result.is_finite() {
+        return result;
+    }
+    let unit = Inner::quantum(-(result.fractional_digits_count() as i32), CONTEXT);
+    match rounding {
+        Rounding::Down => result.sub(unit),
+        Rounding::Up => result.add(unit),
+    }
+}
+
+pub fn add_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    directed(without_signals(lhs).add(without_signals(rhs)), rounding)
+}
+
+pub fn sub_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    directed(without_signals(lhs).sub(without_signals(rhs)), rounding)
+}
+
+pub fn mul_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    directed(without_signals(lhs).mul(without_signals(rhs)), rounding)
+}
+
+pub fn div_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    directed(without_signals(lhs).div(without_signals(rhs)), rounding)
+}
+
+pub fn sqrt_rounded(value: Inner, rounding: Rounding) -> Inner {
+    let root = without_signals(value).sqrt();
+    // fastnum signals every square root as inexact, so check the square instead.
+    let square = without_signals(root).mul(without_signals(root));
+    if !square.is_op_inexact() && cmp(square, value) == Ordering::Equal {
+        return root;
+    }
+    directed(root, rounding)
+}
+
+pub const fn is_zero(value: Inner) -> bool {
+    value.is_zero()
+}
+
+pub const fn check(value: Inner) -> Result<Inner, NumberError> {
+    // A division by zero also signals an invalid operation, so it's checked first.
+    if value.is_op_div_by_zero() {
+        Err(NumberError::DivideByZero)
+    } else if value.is_nan() || value.is_op_invalid() {
+        Err(NumberError::Domain)
+    } else if value.is_infinite() || value.is_op_overflow() {
+        Err(NumberError::Overflow)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Disables the panics on invalid operations, so the result can be checked instead.
+pub const fn untrapped(value: Inner) -> Inner {
+    value.with_ctx(UNTRAPPED)
+}
+
+/// Restores the default context after [`untrapped`].
+pub const fn trapped(value: Inner) -> Inner {
+    value.with_ctx(CONTEXT)
+}
+
+thread_local! {
+    static TRAPS: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Sets whether invalid operations panic on this thread, returning the previous setting.
+pub fn set_traps(enabled: bool) -> bool {
+    TRAPS.replace(enabled)
+}
+
+/// Gives an operand the context of the current thread, see [`set_traps`].
+pub fn operand(value: Inner) -> Inner {
+    if TRAPS.get() {
+        trapped(value)
+    } else {
+        untrapped(value)
+    }
+}
+
+pub const fn normalize(value: Inner) -> Inner {
+    if value.is_zero() {
+        ZERO
+    } else {
+        value.reduce()
+    }
+}
+
+/// Compares by the sign of the difference, as fastnum can misorder values of different
+/// scales when one carries more digits than the precision.
+pub fn cmp(lhs: Inner, rhs: Inner) -> Ordering {
+    let difference = untrapped(lhs).sub(untrapped(rhs));
+    if difference.is_nan() || difference.is_infinite() {
+        lhs.cmp(&rhs)
+    } else if difference.is_zero() {
+        Ordering::Equal
+    } else if difference.is_sign_negative() {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+/// Hashes the reduced value only, as fastnum's own `Hash` also covers the status flags and
+/// context that each value carries.
+pub fn hash<H: Hasher>(value: Inner, state: &mut H) {
+    let value = value.reduce();
+    value.is_nan().hash(state);
+    value.is_infinite().hash(state);
+    value.is_sign_negative().hash(state);
+    value.digits().hash(state);
+    value.fractional_digits_count().hash(state);
+}
+
+pub const fn round(value: Inner) -> Inner {
+    value.round(0)
+}
+
+/// Rounds to an integer in the given direction, then restores the default rounding mode.
+const fn round_towards(value: Inner, mode: RoundingMode) -> Inner {
+    value
+        .with_rounding_mode(mode)
+        .round(0)
+        .with_rounding_mode(CONTEXT.rounding_mode())
+}
+
+pub const fn floor(value: Inner) -> Inner {
+    round_towards(value, RoundingMode::Floor)
+}
+
+pub const fn ceil(value: Inner) -> Inner {
+    round_towards(value, RoundingMode::Ceiling)
+}
+
+pub const fn trunc(value: Inner) -> Inner {
+    round_towards(value, RoundingMode::Down)
+}
+
+pub const fn rem(lhs: Inner, rhs: Inner) -> Inner {
+    lhs.rem(rhs)
+}
+
+/// Whole powers exactly, and the rest as `exp(rhs * ln(lhs))`.
+pub fn powf(lhs: Inner, rhs: Inner) -> Inner {
+    if cmp(rhs, round(rhs)) == Ordering::Equal || !lhs.is_sign_positive() || is_zero(lhs) {
+        lhs.pow(rhs)
+    } else {
+        rhs.mul(ln(lhs)).exp()
+    }
+}
+
+pub fn from_integer(value: i128) -> Inner {
+    Inner::from(value)
+}
+
+/// By way of the decimal string, as fastnum converts values with more digits than the
+/// precision wrongly.
+pub fn to_f32(value: Inner) -> f32 {
+    value.to_string().parse().unwrap_or(f32::NAN)
+}
+
+// fastnum's transcendental functions go wrong away from a small range around zero, and its
+// `cbrt` does not end for some values, so the ones villagekit uses are computed here from
+// the exact operations instead.
+
+const THREE: Inner = ONE.add(TWO);
+const FOUR: Inner = TWO.mul(TWO);
+
+/// The most terms a series may take, in case the terms never stop changing the sum.
+const MAX_TERMS: u32 = 200;
+
+/// Sums the series whose first term is `first`, taking each next term from the last and
+/// its index, until the terms no longer change the sum.
+fn series(first: Inner, mut next: impl FnMut(Inner, Inner) -> Inner) -> Inner {
+    let (mut sum, mut term, mut index) = (first, first, ONE);
+    for _ in 0..MAX_TERMS {
+        term = next(term, index);
+        let total = sum.add(term);
+        if cmp(total, sum) == Ordering::Equal {
+            break;
+        }
+        sum = total;
+        index = index.add(ONE);
+    }
+    sum
+}
+
+/// The sine and cosine, by their series within an eighth of a turn of a quarter turn.
+pub fn sin_cos(value: Inner) -> (Inner, Inner) {
+    if !value.is_finite() {
+        return (value.sin(), value.cos());
+    }
+    let quarters = value.div(FRAC_PI_2).round(0);
+    let x = value.sub(quarters.mul(FRAC_PI_2));
+    let square = x.mul(x);
+    let sin = series(x, |term, n| {
+        term.neg()
+            .mul(square)
+            .div(TWO.mul(n).mul(TWO.mul(n).add(ONE)))
+    });
+    let cos = series(ONE, |term, n| {
+        term.neg()
+            .mul(square)
+            .div(TWO.mul(n).sub(ONE).mul(TWO.mul(n)))
+    });
+    let quadrant = quarters.sub(floor(quarters.div(FOUR)).mul(FOUR));
+    if is_zero(quadrant) {
+        (sin, cos)
+    } else if cmp(quadrant, ONE) == Ordering::Equal {
+        (cos, sin.neg())
+    } else if cmp(quadrant, TWO) == Ordering::Equal {
+        (sin.neg(), cos.neg())
+    } else {
+        (cos.neg(), sin)
+    }
+}
+
+pub fn sin(value: Inner) -> Inner {
+    sin_cos(value).0
+}
+
+pub fn cos(value: Inner) -> Inner {
+    sin_cos(value).1
+}
+
+pub fn tan(value: Inner) -> Inner {
+    let (sin, cos) = sin_cos(value);
+    sin.div(cos)
+}
+
+/// The arctangent, by its series after halving the angle until it is small.
+pub fn atan(value: Inner) -> Inner {
+    if !value.is_finite() {
+        return value.atan();
+    }
+    if value.is_sign_negative() {
+        return atan(value.neg()).neg();
+    }
+    if cmp(value, ONE) == Ordering::Greater {
+        return FRAC_PI_2.sub(atan(ONE.div(value)));
+    }
+    // tan(θ/2) = tan θ / (1 + sqrt(1 + tan² θ)), twice, leaves at most tan(π/16).
+    let mut x = value;
+    for _ in 0..2 {
+        x = x.div(ONE.add(ONE.add(x.mul(x)).sqrt()));
+    }
+    let square = x.mul(x);
+    let mut power = x;
+    series(x, |_, n| {
+        power = power.neg().mul(square);
+        power.div(TWO.mul(n).add(ONE))
+    })
+    .mul(FOUR)
+}
+
+pub fn atan2(y: Inner, x: Inner) -> Inner {
+    if is_zero(x) {
+        return if is_zero(y) {
+            ZERO
+        } else if y.is_sign_negative() {
+            FRAC_PI_2.neg()
+        } else {
+            FRAC_PI_2
+        };
+    }
+    let angle = atan(y.div(x));
+    if x.is_sign_positive() {
+        angle
+    } else if y.is_sign_negative() {
+        angle.sub(PI)
+    } else {
+        angle.add(PI)
+    }
+}
+
+pub fn asinh(value: Inner) -> Inner {
+    if value.is_sign_negative() {
+        value.neg().asinh().neg()
+    } else {
+        value.asinh()
+    }
+}
+
+/// The cube root, by Newton's method from the `f64` estimate.
+pub fn cbrt(value: Inner) -> Inner {
+    if is_zero(value) || !value.is_finite() {
+        return value;
+    }
+    if value.is_sign_negative() {
+        return cbrt(value.neg()).neg();
+    }
+    let estimate: f64 = value.to_string().parse().unwrap_or(f64::NAN);
+    let mut root = parse(&estimate.cbrt().to_string())
+        .ok()
+        .filter(|root| root.is_finite() && !root.is_zero())
+        .unwrap_or(value);
+    for _ in 0..MAX_TERMS {
+        let next = root.sub(
+            root.mul(root)
+                .mul(root)
+                .sub(value)
+                .div(THREE.mul(root).mul(root)),
+        );
+        if cmp(next, root) == Ordering::Equal {
+            break;
+        }
+        root = next;
+    }
+    root
+}
+
+pub fn recip(value: Inner) -> Inner {
+    ONE.div(value)
+}
+
+/// Takes logarithms of numbers below one from their reciprocal, where fastnum's are right.
+fn log_with(value: Inner, log: impl Fn(Inner) -> Inner) -> Inner {
+    if value.is_sign_positive() && !is_zero(value) && cmp(value, ONE) == Ordering::Less {
+        log(recip(value)).neg()
+    } else {
+        log(value)
+    }
+}
+
+pub fn ln(value: Inner) -> Inner {
+    log_with(value, Inner::ln)
+}
+
+pub fn log(value: Inner, base: Inner) -> Inner {
+    log_with(value, |value| value.log(base))
+}
+
+pub fn log2(value: Inner) -> Inner {
+    log_with(value, Inner::log2)
+}
+
+pub fn log10(value: Inner) -> Inner {
+    log_with(value, Inner::log10)
+}
+
+pub fn serialize<S: Serializer>(value: Inner, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Inner, D::Error> {
+    deserializer.deserialize_any(DecimalVisitor)
+}
+
+/// Accepts numbers as well as strings, reading floating point numbers by their shortest
+/// decimal form, e.g. `1.5` rather than the nearest binary fraction.
+struct DecimalVisitor;
+
+impl Visitor<'_> for DecimalVisitor {
+    type Value = Inner;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or a string containing a number")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Inner, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Inner, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Inner, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Inner, E> {
+        parse(value).map_err(E::custom)
+    }
+}
@@ -0,0 +1,368 @@
+use serde::{
+    de::{self, Visitor},
+    Deserializer, Serializer,
+};
+use std::{
+    cmp::Ordering,
+    f64::consts,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::{error::ParseErrorKind, traits::Rounding, NumberError, ParseError};
+
+pub type Inner = f64;
+
+pub const ZERO: Inner = 0.0;
+pub const ONE: Inner = 1.0;
+pub const TWO: Inner = 2.0;
+
+pub const PI: Inner = consts::PI;
+pub const FRAC_1_PI: Inner = consts::FRAC_1_PI;
+pub const FRAC_2_PI: Inner = consts::FRAC_2_PI;
+pub const FRAC_PI_2: Inner = consts::FRAC_PI_2;
+pub const FRAC_PI_3: Inner = consts::FRAC_PI_3;
+pub const FRAC_PI_4: Inner = consts::FRAC_PI_4;
+
+pub const E: Inner = consts::E;
+
+pub const EPSILON: Inner = f64::EPSILON;
+pub const MIN: Inner = f64::MIN;
+pub const MAX: Inner = f64::MAX;
+pub const MIN_POSITIVE: Inner = f64::MIN_POSITIVE;
+
+pub const DECIMAL: bool = false;
+
+/// Binary floating point rounds on most operations, so allow for a few
+/// accumulated rounding errors at the scale of a product.
+pub const APPROX_EPSILON: Inner = 1e-12;
+
+pub fn parse(s: &str) -> Result<Inner, ParseError> {
+    let s = s.replace('_', "");
+    let value: Inner = s.parse().map_err(|_| {
+        ParseError::new(if s.is_empty() {
+            ParseErrorKind::Empty
+        } else {
+            ParseErrorKind::Invalid
+        })
+    })?;
+    // Digits beyond the range parse as infinity, where the decimal backends refuse them.
+    if value.is_infinite() && !s.to_ascii_lowercase().contains("inf") {
+        return Err(ParseError::new(ParseErrorKind::Overflow));
+    }
+    Ok(value)
+}
+
+/// Parses a decimal literal, e.g. from [`num!`](crate::num), at compile time.
+///
+/// Keeps the first 19 significant digits, which is more than `f64` can hold.
+pub const fn parse_unchecked(s: &str) -> Inner {
+    let bytes = s.as_bytes();
+    let mut index = 0;
+
+    let mut negative = false;
+    if index < bytes.len() && (bytes[index] == b'-' || bytes[index] == b'+') {
+        negative = bytes[index] == b'-';
+        index += 1;
+    }
+
+    let mut mantissa: u64 = 0;
+    let mut digits = 0;
+    let mut exponent: i32 = 0;
+    let mut seen_point = false;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match byte {
+            b'0'..=b'9' => {
+                if digits < 19 {
+                    mantissa = mantissa * 10 + (byte - b'0') as u64;
+                    if mantissa > 0 {
+                        digits += 1;
+                    }
+                    if seen_point {
+                        exponent -= 1;
+                    }
+                } else if !seen_point {
+                    exponent += 1;
+                }
+            }
+            b'.' if !seen_point => seen_point = true,
+            b'_' => {}
+            b'e' | b'E' => break,
+            _ => panic!("invalid number literal"),
+        }
+        index += 1;
+    }
+
+    if index < bytes.len() {
+        // Skip the `e`.
+        index += 1;
+        let mut exponent_negative = false;
+        if index < bytes.len() && (bytes[index] == b'-' || bytes[index] == b'+') {
+            exponent_negative = bytes[index] == b'-';
+            index += 1;
+        }
+        let mut explicit: i32 = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                byte @ b'0'..=b'9' => explicit = explicit * 10 + (byte - b'0') as i32,
+                b'_' => {}
+                _ => panic!("invalid number literal"),
+            }
+            index += 1;
+        }
+        exponent += if exponent_negative {
+            -explicit
+        } else {
+            explicit
+        };
+    }
+
+    let mut value = mantissa as f64;
+    while exponent > 0 {
+        value *= 10.0;
+        exponent -= 1;
+    }
+    // Divide by the largest exactly representable power of ten at once, so
+    // short literals like `0.1` round correctly.
+    let mut scale = 1.0;
+    while exponent < 0 {
+        if scale >= 1e22 {
+            value /= scale;
+            scale = 1.0;
+        }
+        scale *= 10.0;
+        exponent += 1;
+    }
+    value /= scale;
+
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+pub const fn add(lhs: Inner, rhs: Inner) -> Inner {
+    lhs + rhs
+}
+
+pub const fn sub(lhs: Inner, rhs: Inner) -> Inner {
+    lhs - rhs
+}
+
+pub const fn mul(lhs: Inner, rhs: Inner) -> Inner {
+    lhs * rhs
+}
+
+pub const fn div(lhs: Inner, rhs: Inner) -> Inner {
+    lhs / rhs
+}
+
+/// Steps a result to the next value in the direction of `rounding` if its rounding error,
+/// the exact result minus the rounded one, lies that way.
+fn directed(result: Inner, error: Inner, rounding: Rounding) -> Inner {
+    match rounding {
+        Rounding::Down if error < 0.0 => result.next_down(),
+        Rounding::Up if error > 0.0 => result.next_up(),
+        _ => result,
+    }
+}
+
+/// Finds the rounding error exactly with Knuth's two-sum.
+pub fn add_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    let sum = lhs + rhs;
+    let rhs_part = sum - lhs;
+    let error = (lhs - (sum - rhs_part)) + (rhs - rhs_part);
+    directed(sum, error, rounding)
+}
+
+pub fn sub_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    add_rounded(lhs, -rhs, rounding)
+}
+
+/// Finds the rounding error exactly with a fused multiply-add, as for the other operations.
+pub fn mul_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    let product = lhs * rhs;
+    directed(product, lhs.mul_add(rhs, -product), rounding)
+}
+
+pub fn div_rounded(lhs: Inner, rhs: Inner, rounding: Rounding) -> Inner {
+    let quotient = lhs / rhs;
+    let remainder = -quotient.mul_add(rhs, -lhs);
+    let error = if rhs < 0.0 { -remainder } else { remainder };
+    directed(quotient, error, rounding)
+}
+
+pub fn sqrt_rounded(value: Inner, rounding: Rounding) -> Inner {
+    let root = value.sqrt();
+    directed(root, -root.mul_add(root, -value), rounding)
+}
+
+pub const fn is_zero(value: Inner) -> bool {
+    value == 0.0
+}
+
+pub const fn check(value: Inner) -> Result<Inner, NumberError> {
+    if value.is_nan() {
+        Err(NumberError::Domain)
+    } else if value.is_infinite() {
+        Err(NumberError::Overflow)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Floating point never panics on invalid operations.
+pub const fn untrapped(value: Inner) -> Inner {
+    value
+}
+
+pub const fn trapped(value: Inner) -> Inner {
+    value
+}
+
+pub fn set_traps(_enabled: bool) -> bool {
+    false
+}
+
+pub fn operand(value: Inner) -> Inner {
+    value
+}
+
+pub const fn normalize(value: Inner) -> Inner {
+    if value == 0.0 {
+        ZERO
+    } else if value.is_nan() {
+        f64::NAN
+    } else {
+        value
+    }
+}
+
+pub fn cmp(lhs: Inner, rhs: Inner) -> Ordering {
+    lhs.total_cmp(&rhs)
+}
+
+pub fn hash<H: Hasher>(value: Inner, state: &mut H) {
+    value.to_bits().hash(state)
+}
+
+pub fn round(value: Inner) -> Inner {
+    value.round()
+}
+
+pub fn floor(value: Inner) -> Inner {
+    value.floor()
+}
+
+pub fn ceil(value: Inner) -> Inner {
+    value.ceil()
+}
+
+pub fn trunc(value: Inner) -> Inner {
+    value.trunc()
+}
+
+pub const fn rem(lhs: Inner, rhs: Inner) -> Inner {
+    lhs % rhs
+}
+
+pub fn powf(lhs: Inner, rhs: Inner) -> Inner {
+    lhs.powf(rhs)
+}
+
+pub fn from_integer(value: i128) -> Inner {
+    value as f64
+}
+
+pub fn to_f32(value: Inner) -> f32 {
+    value as f32
+}
+
+pub fn sin_cos(value: Inner) -> (Inner, Inner) {
+    value.sin_cos()
+}
+
+pub fn sin(value: Inner) -> Inner {
+    value.sin()
+}
+
+pub fn cos(value: Inner) -> Inner {
+    value.cos()
+}
+
+pub fn tan(value: Inner) -> Inner {
+    value.tan()
+}
+
+pub fn atan(value: Inner) -> Inner {
+    value.atan()
+}
+
+pub fn atan2(y: Inner, x: Inner) -> Inner {
+    y.atan2(x)
+}
+
+pub fn asinh(value: Inner) -> Inner {
+    value.asinh()
+}
+
+pub fn cbrt(value: Inner) -> Inner {
+    value.cbrt()
+}
+
+pub fn recip(value: Inner) -> Inner {
+    value.recip()
+}
+
+pub fn ln(value: Inner) -> Inner {
+    value.ln()
+}
+
+pub fn log(value: Inner, base: Inner) -> Inner {
+    value.log(base)
+}
+
+pub fn log2(value: Inner) -> Inner {
+    value.log2()
+}
+
+pub fn log10(value: Inner) -> Inner {
+    value.log10()
+}
+
+/// Serialized as a string, the same as the decimal backends.
+pub fn serialize<S: Serializer>(value: Inner, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&value)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Inner, D::Error> {
+    deserializer.deserialize_any(FloatVisitor)
+}
+
+struct FloatVisitor;
+
+impl Visitor<'_> for FloatVisitor {
+    type Value = Inner;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or a string containing a number")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Inner, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Inner, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Inner, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Inner, E> {
+        parse(value).map_err(E::custom)
+    }
+}
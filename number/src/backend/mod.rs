@@ -0,0 +1,26 @@
+//! The representation behind [`Number`](crate::Number), chosen with cargo features:
+//!
+//! - `d128` (default): 128-bit decimal
+//! - `d256`: 256-bit decimal, for high-precision manufacturing outputs
+//! - `f64`: binary floating point, for fast interactive previews
+//!
+//! There is no 64-bit decimal backend, as fastnum has no 64-bit decimal type; `f64`
+//! covers the need for a smaller, faster number.
+//!
+//! Each backend provides the same set of constants and `const fn`s, so that
+//! `Number` can be written once. The `d128` default gives way to whichever other
+//! backend is enabled, so the test suite can be run against each of them with e.g.
+//! `cargo test --workspace --features villagekit-number/f64`.
+
+#[cfg(all(feature = "d256", feature = "f64"))]
+compile_error!("only one of the `d256` and `f64` number backends can be enabled");
+
+#[cfg(not(feature = "f64"))]
+mod decimal;
+#[cfg(not(feature = "f64"))]
+pub use decimal::*;
+
+#[cfg(feature = "f64")]
+mod float;
+#[cfg(feature = "f64")]
+pub use float::*;
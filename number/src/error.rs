@@ -0,0 +1,64 @@
+use std::fmt::{self, Display};
+
+/// An operation on numbers without a meaningful result, e.g. from a zero-width parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberError {
+    /// Division by zero.
+    DivideByZero,
+    /// An argument outside the domain of the operation, e.g. the square root of a negative number.
+    Domain,
+    /// A result too large to represent.
+    Overflow,
+    /// A string which is not a number.
+    Parse(ParseError),
+}
+
+impl Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberError::DivideByZero => write!(f, "division by zero"),
+            NumberError::Domain => write!(f, "argument outside the domain of the operation"),
+            NumberError::Overflow => write!(f, "result too large to represent"),
+            NumberError::Parse(error) => write!(f, "invalid number: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for NumberError {}
+
+/// Why a string is not a number, the same whichever backend parsed it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ParseErrorKind {
+    Empty,
+    Invalid,
+    Overflow,
+}
+
+impl ParseError {
+    pub(crate) const fn new(kind: ParseErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "empty string"),
+            ParseErrorKind::Invalid => write!(f, "invalid number literal"),
+            ParseErrorKind::Overflow => write!(f, "number too large to represent"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for NumberError {
+    fn from(value: ParseError) -> Self {
+        NumberError::Parse(value)
+    }
+}
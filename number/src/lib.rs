@@ -1,65 +1,208 @@
+mod backend;
+mod error;
 pub mod macros;
 pub mod traits;
 
-use fastnum::{
-    decimal::{Context, Decimal, ParseError},
-    D128,
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
 };
-use serde::{Deserialize, Serialize};
-use std::fmt::{Debug, Display};
 
+pub use crate::backend::Inner;
+pub use crate::error::{NumberError, ParseError};
 pub use crate::traits::*;
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub struct Number(pub D128);
+/// A real number, by default a 128-bit decimal. See the `backend` module for
+/// the other representations available as cargo features.
+///
+/// Numbers are compared, hashed and serialized by value, using their normalized
+/// form: `1.0`, `1.00` and `num!(0.5) * num!(2)` are all the same key.
+#[derive(Copy, Clone)]
+pub struct Number(pub Inner);
 
 impl Number {
-    const CONTEXT: Context = Context::default();
-
-    pub const ZERO: Number = Number(Decimal::ZERO);
-    pub const ONE: Number = Number(Decimal::ONE);
-    pub const TWO: Number = Number(Decimal::TWO);
+    pub const ZERO: Number = Number(backend::ZERO);
+    pub const ONE: Number = Number(backend::ONE);
+    pub const TWO: Number = Number(backend::TWO);
 
     pub const HALF: Number = num!(0.5);
     pub const QUARTER: Number = num!(0.25);
 
-    pub const PI: Number = Number(Decimal::PI);
-    pub const FRAC_1_PI: Number = Number(Decimal::FRAC_1_PI);
-    pub const FRAC_2_PI: Number = Number(Decimal::FRAC_2_PI);
-    pub const FRAC_PI_2: Number = Number(Decimal::FRAC_PI_2);
-    pub const FRAC_PI_3: Number = Number(Decimal::FRAC_PI_3);
-    pub const FRAC_PI_4: Number = Number(Decimal::FRAC_PI_4);
+    pub const PI: Number = Number(backend::PI);
+    pub const FRAC_1_PI: Number = Number(backend::FRAC_1_PI);
+    pub const FRAC_2_PI: Number = Number(backend::FRAC_2_PI);
+    pub const FRAC_PI_2: Number = Number(backend::FRAC_PI_2);
+    pub const FRAC_PI_3: Number = Number(backend::FRAC_PI_3);
+    pub const FRAC_PI_4: Number = Number(backend::FRAC_PI_4);
+
+    pub const E: Number = Number(backend::E);
 
-    pub const EPSILON: Number = Number(Decimal::EPSILON);
+    pub const EPSILON: Number = Number(backend::EPSILON);
+    pub const MIN: Number = Number(backend::MIN);
+    pub const MAX: Number = Number(backend::MAX);
+    pub const MIN_POSITIVE: Number = Number(backend::MIN_POSITIVE);
 
-    pub fn parse(s: &str) -> Result<Self, ParseError> {
-        Ok(Self(Decimal::from_str(s, Self::CONTEXT)?))
+    /// Whether the backend is decimal, so decimal literals and their sums are exact.
+    pub const DECIMAL: bool = backend::DECIMAL;
+
+    pub fn parse(s: &str) -> Result<Self, NumberError> {
+        Ok(Self(backend::parse(s)?))
     }
 
+    /// Parses a number at compile time, as used by [`num!`].
+    ///
+    /// Panics if the string is not a number, which in a constant is a compile error.
     pub const fn parse_unchecked(s: &str) -> Self {
-        Self(Decimal::parse_str(s, Self::CONTEXT))
+        Self(backend::parse_unchecked(s))
     }
 
     pub const fn add(self, rhs: Number) -> Self {
-        Self(self.0.add(rhs.0))
+        Self(backend::add(self.0, rhs.0))
     }
 
     pub const fn sub(self, rhs: Number) -> Self {
-        Self(self.0.sub(rhs.0))
+        Self(backend::sub(self.0, rhs.0))
     }
 
     pub const fn mul(self, rhs: Number) -> Self {
-        Self(self.0.mul(rhs.0))
+        Self(backend::mul(self.0, rhs.0))
     }
 
     pub const fn div(self, rhs: Number) -> Self {
-        Self(self.0.div(rhs.0))
+        Self(backend::div(self.0, rhs.0))
+    }
+
+    /// Returns the canonical form of the number: trailing zeros removed and zero unsigned.
+    pub const fn normalize(self) -> Self {
+        Self(backend::normalize(self.0))
+    }
+
+    pub const fn is_zero(self) -> bool {
+        backend::is_zero(self.0)
+    }
+
+    pub const fn is_finite(self) -> bool {
+        self.check().is_ok()
+    }
+
+    /// Returns an error if the number is NaN or infinite, or was the result of
+    /// an invalid operation.
+    pub const fn check(self) -> Result<Self, NumberError> {
+        match backend::check(self.0) {
+            Ok(value) => Ok(Self(value)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns the inner value in the context of the current thread, see [`without_traps`].
+    fn operand(self) -> Inner {
+        backend::operand(self.0)
+    }
+
+    /// Returns the inner value with the panics on invalid operations disabled,
+    /// to be passed to [`Number::from_untrapped`] after the operation.
+    fn untrapped(self) -> Inner {
+        backend::untrapped(self.0)
+    }
+
+    fn from_untrapped(value: Inner) -> Result<Self, NumberError> {
+        Ok(Self(backend::trapped(Self(value).check()?.0)))
+    }
+}
+
+/// Evaluates `f` with the panics on invalid operations disabled on this thread.
+///
+/// The decimal backends panic on e.g. a division by zero. Within `f` such an
+/// operation results in NaN or infinity instead, to be found with
+/// [`Validate`] afterwards. Floating point never panics, so this only calls `f`.
+pub fn without_traps<T>(f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            backend::set_traps(self.0);
+        }
+    }
+
+    let _restore = Restore(backend::set_traps(false));
+    f()
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Number {}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if backend::is_zero(self.0) && backend::is_zero(other.0) {
+            Ordering::Equal
+        } else {
+            backend::cmp(self.0, other.0)
+        }
+    }
+}
+
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        backend::hash(self.normalize().0, state)
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        backend::serialize(self.normalize().0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        backend::deserialize(deserializer).map(|value| Self(value).normalize())
     }
 }
 
 impl From<Number> for f32 {
     fn from(value: Number) -> Self {
-        value.0.into()
+        backend::to_f32(value.0)
+    }
+}
+
+macro_rules! from_integer {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for Number {
+            fn from(value: $ty) -> Self {
+                Self(backend::from_integer(value as i128))
+            }
+        }
+    )*};
+}
+
+from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Converts the shortest decimal which reads back as the same `f32`, e.g. `0.1` rather
+/// than `0.100000001490116`.
+impl TryFrom<f32> for Number {
+    type Error = NumberError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        if value.is_nan() {
+            Err(NumberError::Domain)
+        } else if value.is_infinite() {
+            Err(NumberError::Overflow)
+        } else {
+            Self::parse(&value.to_string())
+        }
     }
 }
 
@@ -73,7 +216,7 @@ impl Add for Number {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0.add(rhs.0))
+        Self(self.operand() + rhs.operand())
     }
 }
 
@@ -81,7 +224,7 @@ impl Sub for Number {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0.sub(rhs.0))
+        Self(self.operand() - rhs.operand())
     }
 }
 
@@ -89,7 +232,7 @@ impl Mul for Number {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        Self(self.0.mul(rhs.0))
+        Self(self.operand() * rhs.operand())
     }
 }
 
@@ -97,7 +240,7 @@ impl Div for Number {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
-        Self(self.0.div(rhs.0))
+        Self(self.operand() / rhs.operand())
     }
 }
 
@@ -105,7 +248,7 @@ impl Neg for Number {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        Self(self.0.neg())
+        Self(-self.0)
     }
 }
 
@@ -125,7 +268,7 @@ impl Sqrt for Number {
     type Output = Self;
 
     fn sqrt(self) -> Self::Output {
-        Self(self.0.sqrt())
+        Self(self.operand().sqrt())
     }
 }
 
@@ -141,23 +284,23 @@ impl Trig for Number {
     type Output = Self;
 
     fn hypot(&self, other: Self) -> Self::Output {
-        Self(self.0.hypot(other.0))
+        Self(self.operand().hypot(other.operand()))
     }
 
     fn sin(&self) -> Self::Output {
-        Self(self.0.sin())
+        Self(backend::sin(self.operand()))
     }
 
     fn cos(&self) -> Self::Output {
-        Self(self.0.cos())
+        Self(backend::cos(self.operand()))
     }
 
     fn tan(&self) -> Self::Output {
-        Self(self.0.tan())
+        Self(backend::tan(self.operand()))
     }
 
     fn sin_cos(&self) -> (Self::Output, Self::Output) {
-        let (s, c) = self.0.sin_cos();
+        let (s, c) = backend::sin_cos(self.operand());
         (Self(s), Self(c))
     }
 }
@@ -166,19 +309,293 @@ impl TrigInv for Number {
     type Output = Self;
 
     fn asin(&self) -> Self::Output {
-        Self(self.0.asin())
+        Self(self.operand().asin())
     }
 
     fn acos(&self) -> Self::Output {
-        Self(self.0.acos())
+        Self(self.operand().acos())
     }
 
     fn atan(&self) -> Self::Output {
-        Self(self.0.atan())
+        Self(backend::atan(self.operand()))
     }
 
     fn atan2(&self, other: Self) -> Self::Output {
-        Self(self.0.atan2(other.0))
+        Self(backend::atan2(self.operand(), other.operand()))
+    }
+}
+
+impl Hyp for Number {
+    type Output = Self;
+
+    fn sinh(&self) -> Self::Output {
+        Self(self.operand().sinh())
+    }
+
+    fn cosh(&self) -> Self::Output {
+        Self(self.operand().cosh())
+    }
+
+    fn tanh(&self) -> Self::Output {
+        Self(self.operand().tanh())
+    }
+
+    fn asinh(&self) -> Self::Output {
+        Self(backend::asinh(self.operand()))
+    }
+
+    fn acosh(&self) -> Self::Output {
+        Self(self.operand().acosh())
+    }
+
+    fn atanh(&self) -> Self::Output {
+        Self(self.operand().atanh())
+    }
+}
+
+impl Round for Number {
+    fn floor(self) -> Self {
+        Self(backend::floor(self.0))
+    }
+
+    fn ceil(self) -> Self {
+        Self(backend::ceil(self.0))
+    }
+
+    fn trunc(self) -> Self {
+        Self(backend::trunc(self.0))
+    }
+
+    fn round(self) -> Self {
+        Self(backend::round(self.0))
+    }
+
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+}
+
+impl RoundTo for Number {
+    fn floor_to(self, step: Self) -> Self {
+        (self / step).floor() * step
+    }
+
+    fn ceil_to(self, step: Self) -> Self {
+        (self / step).ceil() * step
+    }
+
+    fn round_to(self, step: Self) -> Self {
+        (self / step).round() * step
+    }
+
+    fn trunc_to(self, step: Self) -> Self {
+        (self / step).trunc() * step
+    }
+}
+
+impl Signum for Number {
+    fn signum(self) -> Number {
+        Self(self.0.signum())
+    }
+
+    fn is_sign_positive(&self) -> bool {
+        self.0.is_sign_positive()
+    }
+
+    fn is_sign_negative(&self) -> bool {
+        self.0.is_sign_negative()
+    }
+}
+
+impl Euclid for Number {
+    fn div_euclid(self, rhs: Self) -> Number {
+        let quotient = (self / rhs).trunc();
+        if Self(backend::rem(self.operand(), rhs.operand())) < Self::ZERO {
+            if rhs > Self::ZERO {
+                quotient - Self::ONE
+            } else {
+                quotient + Self::ONE
+            }
+        } else {
+            quotient
+        }
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        let remainder = Self(backend::rem(self.operand(), rhs.operand()));
+        if remainder < Self::ZERO {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+}
+
+impl Cbrt for Number {
+    type Output = Self;
+
+    forward_inner! {
+        backend::cbrt(self) -> Self;
+    }
+}
+
+impl Recip for Number {
+    type Output = Self;
+
+    forward_inner! {
+        backend::recip(self) -> Self;
+    }
+}
+
+impl MulAdd for Number {
+    type Output = Self;
+
+    forward_inner! {
+        Inner::mul_add(self, a: Self, b: Self) -> Self;
+    }
+}
+
+impl Pow for Number {
+    type Output = Self;
+
+    fn powi(self, n: i32) -> Self::Output {
+        Self(self.operand().powi(n))
+    }
+
+    fn powf(self, n: Self) -> Self::Output {
+        Self(backend::powf(self.operand(), n.operand()))
+    }
+}
+
+impl Exp for Number {
+    type Output = Self;
+
+    forward_inner! {
+        Inner::exp(self) -> Self;
+        Inner::exp2(self) -> Self;
+        Inner::exp_m1(self) -> Self;
+    }
+}
+
+impl Log for Number {
+    type Output = Self;
+
+    forward_inner! {
+        backend::ln(self) -> Self;
+        backend::log(self, base: Self) -> Self;
+        backend::log2(self) -> Self;
+        backend::log10(self) -> Self;
+        Inner::ln_1p(self) -> Self;
+    }
+}
+
+impl CheckedAdd for Number {
+    type Output = Self;
+
+    fn checked_add(self, rhs: Self) -> Result<Self::Output, NumberError> {
+        Self::from_untrapped(self.untrapped() + rhs.untrapped())
+    }
+}
+
+impl CheckedSub for Number {
+    type Output = Self;
+
+    fn checked_sub(self, rhs: Self) -> Result<Self::Output, NumberError> {
+        Self::from_untrapped(self.untrapped() - rhs.untrapped())
+    }
+}
+
+impl CheckedMul for Number {
+    type Output = Self;
+
+    fn checked_mul(self, rhs: Self) -> Result<Self::Output, NumberError> {
+        Self::from_untrapped(self.untrapped() * rhs.untrapped())
+    }
+}
+
+impl CheckedDiv for Number {
+    type Output = Self;
+
+    fn checked_div(self, rhs: Self) -> Result<Self::Output, NumberError> {
+        if rhs.is_zero() {
+            return Err(NumberError::DivideByZero);
+        }
+        Self::from_untrapped(self.untrapped() / rhs.untrapped())
+    }
+}
+
+impl CheckedSqrt for Number {
+    type Output = Self;
+
+    fn checked_sqrt(self) -> Result<Self::Output, NumberError> {
+        if self < Self::ZERO {
+            return Err(NumberError::Domain);
+        }
+        Self::from_untrapped(self.untrapped().sqrt())
+    }
+}
+
+impl CheckedTrigInv for Number {
+    type Output = Self;
+
+    fn checked_asin(&self) -> Result<Self::Output, NumberError> {
+        if self.abs() > Self::ONE {
+            return Err(NumberError::Domain);
+        }
+        Self::from_untrapped(self.untrapped().asin())
+    }
+
+    fn checked_acos(&self) -> Result<Self::Output, NumberError> {
+        if self.abs() > Self::ONE {
+            return Err(NumberError::Domain);
+        }
+        Self::from_untrapped(self.untrapped().acos())
+    }
+}
+
+impl RoundedAdd for Number {
+    type Output = Self;
+
+    fn add_rounded(self, rhs: Self, rounding: Rounding) -> Self::Output {
+        Self(backend::add_rounded(self.0, rhs.0, rounding))
+    }
+}
+
+impl RoundedSub for Number {
+    type Output = Self;
+
+    fn sub_rounded(self, rhs: Self, rounding: Rounding) -> Self::Output {
+        Self(backend::sub_rounded(self.0, rhs.0, rounding))
+    }
+}
+
+impl RoundedMul for Number {
+    type Output = Self;
+
+    fn mul_rounded(self, rhs: Self, rounding: Rounding) -> Self::Output {
+        Self(backend::mul_rounded(self.0, rhs.0, rounding))
+    }
+}
+
+impl RoundedDiv for Number {
+    type Output = Self;
+
+    fn div_rounded(self, rhs: Self, rounding: Rounding) -> Self::Output {
+        Self(backend::div_rounded(self.0, rhs.0, rounding))
+    }
+}
+
+impl RoundedSqrt for Number {
+    type Output = Self;
+
+    fn sqrt_rounded(self, rounding: Rounding) -> Self::Output {
+        Self(backend::sqrt_rounded(self.0, rounding))
+    }
+}
+
+impl Validate for Number {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.check().map(|_| ())
     }
 }
 
@@ -188,7 +605,7 @@ impl ApproxEq for Number {
             return true;
         }
 
-        (*self - *rhs).abs() <= Number::EPSILON
+        (*self - *rhs).abs() <= Number(backend::APPROX_EPSILON)
     }
 }
 
@@ -206,11 +623,19 @@ impl Display for Number {
 
 #[cfg(test)]
 mod tests {
+    use std::hash::DefaultHasher;
+
     use super::*;
 
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
     #[test]
     fn it_works() {
-        let expected: Result<Number, ParseError> = Ok(num!(0.2));
+        let expected: Result<Number, NumberError> = Ok(num!(0.2));
         let actual = Number::parse("0.2");
         assert_eq!(expected, actual);
 
@@ -218,4 +643,240 @@ mod tests {
         let actual = num!(1.30) * num!(1.20);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn checked_operations() {
+        assert_eq!(num!(1).checked_div(num!(4)), Ok(num!(0.25)));
+        assert_eq!(
+            num!(1).checked_div(num!(0.0)),
+            Err(NumberError::DivideByZero)
+        );
+        assert_eq!(num!(4).checked_sqrt(), Ok(num!(2)));
+        assert_eq!(num!(-4).checked_sqrt(), Err(NumberError::Domain));
+        assert_eq!(num!(1.5).checked_asin(), Err(NumberError::Domain));
+        assert_eq!(num!(-1.5).checked_acos(), Err(NumberError::Domain));
+        assert_eq!(num!(1).checked_acos(), Ok(num!(0)));
+        assert_eq!(
+            Number::MAX.checked_mul(num!(10)),
+            Err(NumberError::Overflow)
+        );
+        assert_eq!(
+            Number::MAX.checked_add(Number::MAX),
+            Err(NumberError::Overflow)
+        );
+        assert_eq!(num!(1).checked_sub(num!(3)), Ok(num!(-2)));
+    }
+
+    #[test]
+    fn without_traps() {
+        let quotient = super::without_traps(|| num!(1) / num!(0));
+        let root = super::without_traps(|| (num!(-4)).sqrt());
+        let sum = super::without_traps(|| num!(2) + num!(3));
+        if Number::DECIMAL {
+            assert_eq!(quotient.check(), Err(NumberError::DivideByZero));
+        } else {
+            assert!(quotient.check().is_err());
+        }
+        assert_eq!(root.check(), Err(NumberError::Domain));
+        assert_eq!(sum, num!(5));
+        // The traps are back once the closure returns.
+        if Number::DECIMAL {
+            assert!(std::panic::catch_unwind(|| num!(1) / num!(0)).is_err());
+        }
+    }
+
+    #[test]
+    fn directed_rounding() {
+        let third = (
+            num!(1).div_rounded(num!(3), Rounding::Down),
+            num!(1).div_rounded(num!(3), Rounding::Up),
+        );
+        let nearest = num!(1) / num!(3);
+        assert!(third.0 <= nearest && nearest <= third.1 && third.0 < third.1);
+        assert!(third.0.mul_rounded(num!(3), Rounding::Down) < num!(1));
+        assert!(third.1.mul_rounded(num!(3), Rounding::Up) > num!(1));
+
+        let root = (
+            num!(2).sqrt_rounded(Rounding::Down),
+            num!(2).sqrt_rounded(Rounding::Up),
+        );
+        assert!(root.0 < root.1);
+        assert!(root.0.mul_rounded(root.0, Rounding::Down) < num!(2));
+        assert!(root.1.mul_rounded(root.1, Rounding::Up) > num!(2));
+
+        let tiny = num!(1e-90);
+        assert!(num!(0.1).add_rounded(tiny, Rounding::Up) > num!(0.1));
+        assert!(num!(0.1).sub_rounded(tiny, Rounding::Down) < num!(0.1));
+
+        // Exact results are left as they are.
+        assert_eq!(num!(1.5).add_rounded(num!(2), Rounding::Down), num!(3.5));
+        assert_eq!(num!(1.5).mul_rounded(num!(2), Rounding::Up), num!(3));
+        assert_eq!(num!(9).sqrt_rounded(Rounding::Down), num!(3));
+        assert_eq!(num!(1).div_rounded(num!(4), Rounding::Up), num!(0.25));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(Number::parse("1.2.3"), Err(NumberError::Parse(_))));
+        assert!(matches!(Number::parse("m"), Err(NumberError::Parse(_))));
+        let Err(NumberError::Parse(empty)) = Number::parse("") else {
+            panic!("parsed an empty string");
+        };
+        assert_eq!(empty.to_string(), "empty string");
+        assert_ne!(Number::parse("1e99999999999"), Number::parse("x"));
+    }
+
+    #[test]
+    fn from_f32() {
+        assert_eq!(Number::try_from(0.1f32), Ok(num!(0.1)));
+        assert_eq!(Number::try_from(-2.5f32), Ok(num!(-2.5)));
+        assert_eq!(Number::try_from(f32::NAN), Err(NumberError::Domain));
+        assert_eq!(Number::try_from(f32::INFINITY), Err(NumberError::Overflow));
+    }
+
+    #[test]
+    fn normalized_equality() {
+        let scaled = [
+            num!(1),
+            num!(1.0),
+            num!(1.00),
+            num!(0.5) * num!(2),
+            num!(4) / num!(4),
+        ];
+        for value in scaled {
+            assert_eq!(value, Number::ONE);
+            assert_eq!(hash_of(&value), hash_of(&Number::ONE));
+            assert_eq!(value.cmp(&Number::ONE), Ordering::Equal);
+        }
+
+        assert_eq!(-Number::ZERO, Number::ZERO);
+        assert_eq!(hash_of(&-Number::ZERO), hash_of(&Number::ZERO));
+        assert_eq!(hash_of(&num!(0.000)), hash_of(&Number::ZERO));
+    }
+
+    #[test]
+    fn hash_ignores_status() {
+        let rounded = num!(2.7).floor();
+        let untrapped = super::without_traps(|| num!(2) + num!(3));
+        let inexact = num!(0.1) + num!(1e-90);
+        assert_eq!(rounded, num!(2));
+        assert_eq!(hash_of(&rounded), hash_of(&num!(2)));
+        assert_eq!(untrapped, num!(5));
+        assert_eq!(hash_of(&untrapped), hash_of(&num!(5)));
+        assert_eq!(inexact, num!(0.1));
+        assert_eq!(hash_of(&inexact), hash_of(&num!(0.1)));
+    }
+
+    #[test]
+    fn from_integers() {
+        assert_eq!(Number::from(12usize), num!(12));
+        assert_eq!(Number::from(-3i32), num!(-3));
+        assert_eq!(Number::from(0u8), Number::ZERO);
+        assert_eq!(Number::from(1_000_000_007i64), num!(1_000_000_007));
+    }
+
+    #[test]
+    fn ordering() {
+        // Results to full precision, at different scales.
+        let (small, large) = (num!(1).atan2(num!(4)), num!(1).atan2(num!(2)));
+        assert!(small < large);
+        assert!(large > small);
+        assert_eq!(large.cmp(&small), Ordering::Greater);
+        assert_eq!(Number::MIN.cmp(&Number::MAX), Ordering::Less);
+        assert_eq!(Number::MAX.cmp(&Number::MIN), Ordering::Greater);
+    }
+
+    #[test]
+    fn normalized_serde() {
+        let json = serde_json::to_string(&num!(1.500)).unwrap();
+        assert_eq!(json, serde_json::to_string(&num!(1.5)).unwrap());
+
+        let value: Number = serde_json::from_str(&json).unwrap();
+        assert_eq!(hash_of(&value), hash_of(&num!(1.5)));
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+
+        let value: Number = serde_json::from_str("\"2.50\"").unwrap();
+        assert_eq!(value, num!(2.5));
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"2.5\"");
+
+        let value: Number = serde_json::from_str("0.1").unwrap();
+        assert_eq!(value, num!(0.1));
+        let value: Number = serde_json::from_str("-40").unwrap();
+        assert_eq!(value, num!(-40));
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(num!(2.7).floor(), num!(2));
+        assert_eq!(num!(-2.7).floor(), num!(-3));
+        assert_eq!(num!(2.2).ceil(), num!(3));
+        assert_eq!(num!(-2.2).ceil(), num!(-2));
+        // The result rounds half up again afterwards, not towards the floor.
+        assert_eq!((num!(2.7).floor() + num!(0.5)).round(), num!(3));
+        assert_eq!(num!(2.5).round(), num!(3));
+        assert_eq!(num!(-2.7).trunc(), num!(-2));
+        assert_eq!(num!(-2.75).fract(), num!(-0.75));
+        assert_eq!(num!(7.3).round_to(num!(0.5)), num!(7.5));
+        assert_eq!(num!(7.3).floor_to(num!(2)), num!(6));
+    }
+
+    #[test]
+    fn sign_and_euclid() {
+        assert_eq!(num!(-4.2).signum(), num!(-1));
+        assert!(num!(4.2).is_sign_positive());
+        assert_eq!(num!(-7).rem_euclid(num!(4)), num!(1));
+        assert_eq!(num!(-7).div_euclid(num!(4)), num!(-2));
+        assert_eq!(num!(7).rem_euclid(num!(-4)), num!(3));
+        assert_eq!(num!(7).div_euclid(num!(-4)), num!(-1));
+        assert_eq!(num!(-1).clamp(num!(0), num!(1)), num!(0));
+    }
+
+    #[test]
+    fn powers_and_logs() {
+        assert_eq!(num!(1.5).powi(2), num!(2.25));
+        assert!(num!(2).powf(num!(0.5)).approx_eq(&num!(2).sqrt()));
+        assert!(num!(27).cbrt().approx_eq(&num!(3)));
+        assert_eq!(num!(4).recip(), num!(0.25));
+        assert_eq!(num!(2).mul_add(num!(3), num!(4)), num!(10));
+        assert!(num!(1).exp().approx_eq(&Number::E));
+        assert!(Number::E.ln().approx_eq(&num!(1)));
+        assert!(num!(1000).log10().approx_eq(&num!(3)));
+        assert!(num!(8).log2().approx_eq(&num!(3)));
+        assert!(num!(9).log(num!(3)).approx_eq(&num!(2)));
+        // Below one and below zero, where fastnum's own are wrong or do not end.
+        assert!(num!(0.001).log10().approx_eq(&num!(-3)));
+        assert!(num!(0.001).ln().approx_eq(&-num!(1000).ln()));
+        assert!(num!(-0.001).recip().approx_eq(&num!(-1000)));
+        assert!(num!(-8).cbrt().approx_eq(&num!(-2)));
+        assert!((num!(30).cbrt() - num!(3.107232505953859)).abs() < num!(1e-15));
+        assert!(num!(0.25).powf(num!(1.5)).approx_eq(&num!(0.125)));
+    }
+
+    #[test]
+    fn trig() {
+        // Every quadrant, and beyond a turn either way.
+        let turn = Number::PI * Number::TWO;
+        for quadrant in [num!(0.3), num!(2), num!(3.5), num!(5)] {
+            for angle in [quadrant, quadrant - turn, quadrant + turn * num!(3)] {
+                let (sin, cos) = angle.sin_cos();
+                assert!((sin * sin + cos * cos).approx_eq(&num!(1)));
+                let expected = if quadrant > Number::PI {
+                    quadrant - turn
+                } else {
+                    quadrant
+                };
+                assert!(sin.atan2(cos).approx_eq(&expected));
+                assert!(angle.tan().approx_eq(&(sin / cos)));
+            }
+        }
+        assert!((num!(2).sin() - num!(0.9092974268256817)).abs() < num!(1e-15));
+        assert!((num!(2).cos() - num!(-0.4161468365471424)).abs() < num!(1e-15));
+        assert!((num!(-5).atan() - num!(-1.373400766945016)).abs() < num!(1e-15));
+        assert!(num!(-1)
+            .atan2(num!(-1))
+            .approx_eq(&(-Number::PI * num!(0.75))));
+        assert_eq!(num!(0).atan2(num!(-1)), Number::PI);
+        assert_eq!(num!(-30).asinh(), -num!(30).asinh());
+        assert_eq!(f32::from(Number::PI), std::f32::consts::PI);
+    }
 }
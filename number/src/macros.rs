@@ -12,14 +12,14 @@ macro_rules! forward_inner {
         => {$(
             #[inline]
             fn $method(self $( , $arg : $ty )* ) -> $ret {
-                Self($base::$method(self.0 $( , $arg.0 )* ))
+                Self($base::$method(self.operand() $( , $arg.operand() )* ))
             }
         )*};
     ($( $base:ident :: $method:ident ( $( $arg:ident : $ty:ty ),* ) -> $ret:ty ; )*)
         => {$(
             #[inline]
             fn $method( $( $arg : $ty ),* ) -> $ret {
-                Self($base::$method( $( $arg.0 ),* ))
+                Self($base::$method( $( $arg.operand() ),* ))
             }
         )*};
 }
@@ -1,5 +1,10 @@
-// TODO split up and implement all traits in num-traits::real::Real
+// Split up from the traits in num-traits::real::Real
 //   https://docs.rs/num-traits/0.2.19/src/num_traits/real.rs.html
+//
+// `min`, `max` and `clamp` come from `Ord`, and `to_radians` / `to_degrees`
+// are covered by the `Angle` dimension in villagekit-unit.
+
+use crate::{Number, NumberError};
 
 pub use core::ops::{Add, Div, Mul, Neg, Sub};
 
@@ -48,6 +53,110 @@ pub trait ApproxEq {
     fn approx_eq(&self, rhs: &Self) -> bool;
 }
 
+pub trait CheckedAdd<Rhs = Self> {
+    type Output;
+
+    fn checked_add(self, rhs: Rhs) -> Result<Self::Output, NumberError>;
+}
+
+pub trait CheckedSub<Rhs = Self> {
+    type Output;
+
+    fn checked_sub(self, rhs: Rhs) -> Result<Self::Output, NumberError>;
+}
+
+pub trait CheckedMul<Rhs = Self> {
+    type Output;
+
+    fn checked_mul(self, rhs: Rhs) -> Result<Self::Output, NumberError>;
+}
+
+pub trait CheckedDiv<Rhs = Self> {
+    type Output;
+
+    fn checked_div(self, rhs: Rhs) -> Result<Self::Output, NumberError>;
+}
+
+pub trait CheckedSqrt {
+    type Output;
+
+    fn checked_sqrt(self) -> Result<Self::Output, NumberError>;
+}
+
+pub trait CheckedTrigInv {
+    type Output;
+
+    fn checked_asin(&self) -> Result<Self::Output, NumberError>;
+    fn checked_acos(&self) -> Result<Self::Output, NumberError>;
+}
+
+/// The direction to round the result of an operation in, instead of to the nearest value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Operations rounded in a given direction, so the result is a bound of the exact one,
+/// e.g. for the bounds of an interval. Exact results are left as they are.
+pub trait RoundedAdd<Rhs = Self> {
+    type Output;
+
+    fn add_rounded(self, rhs: Rhs, rounding: Rounding) -> Self::Output;
+}
+
+pub trait RoundedSub<Rhs = Self> {
+    type Output;
+
+    fn sub_rounded(self, rhs: Rhs, rounding: Rounding) -> Self::Output;
+}
+
+pub trait RoundedMul<Rhs = Self> {
+    type Output;
+
+    fn mul_rounded(self, rhs: Rhs, rounding: Rounding) -> Self::Output;
+}
+
+pub trait RoundedDiv<Rhs = Self> {
+    type Output;
+
+    fn div_rounded(self, rhs: Rhs, rounding: Rounding) -> Self::Output;
+}
+
+pub trait RoundedSqrt {
+    type Output;
+
+    fn sqrt_rounded(self, rounding: Rounding) -> Self::Output;
+}
+
+/// Check that every number within a value is valid, i.e. neither NaN nor infinite.
+///
+/// Used in strict mode to surface the results of unchecked operations.
+pub trait Validate {
+    fn validate(&self) -> Result<(), NumberError>;
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), NumberError> {
+        match self {
+            Some(value) => value.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Validate> Validate for [T] {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.iter().try_for_each(Validate::validate)
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.as_slice().validate()
+    }
+}
+
 pub trait Trig {
     type Output;
 
@@ -78,39 +187,72 @@ pub trait Hyp {
     fn atanh(&self) -> Self::Output;
 }
 
-// TODO min_value
-// TODO min_positive_value
-// TODO epsilon
-// TODO max_value
-//
-// TODO floor
-// TODO ceil
-// TODO round
-// TODO trunc
-// TODO fract
-//
-// TODO signum
-// TODO is_sign_positive
-// TODO is_sign_negative
-//
-// TODO mul_add
-// TODO recip
-// TODO powi
-// TODO powf
-//
-// TODO exp
-// TODO exp2
-//
-// TODO ln
-// TODO log
-// TODO log2
-// TODO log10
-//
-// TODO to_radians
-// TODO to_degrees
-//
-// TODO min
-// TODO max
-//
-// TODO cbrt
-// TODO exp_ml
+pub trait Round {
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn fract(self) -> Self;
+}
+
+/// Round to a multiple of a step, e.g. a length to the nearest grid unit.
+pub trait RoundTo {
+    fn floor_to(self, step: Self) -> Self;
+    fn ceil_to(self, step: Self) -> Self;
+    fn round_to(self, step: Self) -> Self;
+    fn trunc_to(self, step: Self) -> Self;
+}
+
+pub trait Signum {
+    fn signum(self) -> Number;
+    fn is_sign_positive(&self) -> bool;
+    fn is_sign_negative(&self) -> bool;
+}
+
+pub trait Euclid {
+    fn div_euclid(self, rhs: Self) -> Number;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+pub trait Cbrt {
+    type Output;
+
+    fn cbrt(self) -> Self::Output;
+}
+
+pub trait Recip {
+    type Output;
+
+    fn recip(self) -> Self::Output;
+}
+
+pub trait MulAdd {
+    type Output;
+
+    fn mul_add(self, a: Self, b: Self) -> Self::Output;
+}
+
+pub trait Pow {
+    type Output;
+
+    fn powi(self, n: i32) -> Self::Output;
+    fn powf(self, n: Self) -> Self::Output;
+}
+
+pub trait Exp {
+    type Output;
+
+    fn exp(self) -> Self::Output;
+    fn exp2(self) -> Self::Output;
+    fn exp_m1(self) -> Self::Output;
+}
+
+pub trait Log {
+    type Output;
+
+    fn ln(self) -> Self::Output;
+    fn log(self, base: Self) -> Self::Output;
+    fn log2(self) -> Self::Output;
+    fn log10(self) -> Self::Output;
+    fn ln_1p(self) -> Self::Output;
+}
@@ -1,46 +1,74 @@
 use dyn_clone::DynClone;
-use villagekit_math::Vector3;
-use villagekit_number::Number;
+use std::ops::{Add, Mul, Neg, Sub};
+use villagekit_math::{Aabb, Vector3};
+use villagekit_number::{traits::Validate, without_traps, Number, NumberError};
 use villagekit_render::{Renderable, Transform};
 use villagekit_unit::{Angle, Length};
 
-pub trait Stock: DynClone {
-    fn render(&self) -> Renderable;
-    fn place(self) -> Product
+pub trait Stock<L = Length>: DynClone {
+    fn render(&self) -> Renderable<L>;
+    /// Renders in strict mode, returning an error for any invalid number.
+    ///
+    /// Renders without traps, so an invalid operation is returned rather than panicking.
+    fn try_render(&self) -> Result<Renderable<L>, NumberError>
+    where
+        L: Validate,
+    {
+        let renderable = without_traps(|| self.render());
+        renderable.validate()?;
+        Ok(renderable)
+    }
+    fn place(self) -> Product<L>
     where
         Self: Sized + Send + Sync + 'static,
+        L: Default + 'static,
     {
         Product::new(ProductKind::Stock(Box::new(self)))
     }
 }
 
-pub trait Assembly: DynClone {
-    fn products(&self) -> Vec<Product>;
-    fn place(self) -> Product
+pub trait Assembly<L = Length>: DynClone {
+    fn products(&self) -> Vec<Product<L>>;
+    /// Evaluates in strict mode, returning an error for any invalid number
+    /// in the transforms of the products.
+    ///
+    /// Evaluates without traps, so an invalid operation is returned rather than panicking.
+    fn try_products(&self) -> Result<Vec<Product<L>>, NumberError>
+    where
+        L: Validate,
+    {
+        let products = without_traps(|| self.products());
+        for product in &products {
+            product.transform.validate()?;
+        }
+        Ok(products)
+    }
+    fn place(self) -> Product<L>
     where
         Self: Sized + Send + Sync + 'static,
+        L: Default + 'static,
     {
         Product::new(ProductKind::Assembly(Box::new(self)))
     }
 }
 
-dyn_clone::clone_trait_object!(Stock);
-dyn_clone::clone_trait_object!(Assembly);
+dyn_clone::clone_trait_object!(<L> Stock<L>);
+dyn_clone::clone_trait_object!(<L> Assembly<L>);
 
 #[derive(Default, Clone)]
-pub struct Group(pub Vec<Product>);
+pub struct Group<L = Length>(pub Vec<Product<L>>);
 
 #[derive(Default, Clone)]
-pub enum ProductKind {
-    Stock(Box<dyn Stock + Send + Sync>),
-    Assembly(Box<dyn Assembly + Send + Sync>),
-    Group(Group),
+pub enum ProductKind<L = Length> {
+    Stock(Box<dyn Stock<L> + Send + Sync>),
+    Assembly(Box<dyn Assembly<L> + Send + Sync>),
+    Group(Group<L>),
     #[default]
     None,
 }
 
-impl From<Option<ProductKind>> for ProductKind {
-    fn from(value: Option<ProductKind>) -> Self {
+impl<L> From<Option<ProductKind<L>>> for ProductKind<L> {
+    fn from(value: Option<ProductKind<L>>) -> Self {
         match value {
             Some(kind) => kind,
             None => ProductKind::None,
@@ -48,34 +76,93 @@ impl From<Option<ProductKind>> for ProductKind {
     }
 }
 
+/// A stock, an assembly or a group of products, placed by a transform.
+///
+/// Products are generic over their lengths, by default [`Length`]. Written against any
+/// length type, a design can also be built from [`Dual`](villagekit_unit::Dual) lengths,
+/// to find how its sizes and [`Product::bounds`] change with one of its parameters.
 #[derive(Default, Clone)]
-pub struct Product {
-    pub kind: ProductKind,
-    pub transform: Transform,
+pub struct Product<L = Length> {
+    pub kind: ProductKind<L>,
+    pub transform: Transform<L>,
 }
 
-impl Product {
-    pub fn new(kind: ProductKind) -> Self {
+impl<L: Default> Product<L> {
+    pub fn new(kind: ProductKind<L>) -> Self {
         Self {
             kind,
             transform: Transform::default(),
         }
     }
+}
 
-    pub fn translate(self, x: Length, y: Length, z: Length) -> Self {
+impl<L> Product<L>
+where
+    L: Copy + Add<Output = L>,
+{
+    pub fn translate(self, x: L, y: L, z: L) -> Self {
         self.update_transform(|t| t.translate(x, y, z))
     }
+}
 
-    pub fn rotate(
-        self,
-        axis: Vector3<Number>,
-        angle: Angle,
-        origin: Option<Vector3<Length>>,
-    ) -> Self {
+impl<L> Product<L>
+where
+    L: Copy
+        + Default
+        + Add<Output = L>
+        + Sub<Output = L>
+        + Neg<Output = L>
+        + Mul<Number, Output = L>,
+    Number: Mul<L, Output = L>,
+{
+    pub fn rotate(self, axis: Vector3<Number>, angle: Angle, origin: Option<Vector3<L>>) -> Self {
         self.update_transform(|t| t.rotate(axis, angle, origin))
     }
+}
+
+impl<L: Validate> Product<L> {
+    /// Evaluates the whole product tree in strict mode, returning the first
+    /// invalid number found.
+    pub fn validate(&self) -> Result<(), NumberError> {
+        self.transform.validate()?;
+        match &self.kind {
+            ProductKind::Stock(stock) => stock.try_render().map(|_| ()),
+            ProductKind::Assembly(assembly) => assembly
+                .try_products()?
+                .iter()
+                .try_for_each(Product::validate),
+            ProductKind::Group(group) => group.0.iter().try_for_each(Product::validate),
+            ProductKind::None => Ok(()),
+        }
+    }
+}
+
+impl<L> Product<L>
+where
+    L: Copy + Default + PartialOrd + Add<Output = L> + Sub<Output = L> + Mul<Number, Output = L>,
+{
+    /// The smallest axis-aligned box containing the whole product tree, or `None` if it
+    /// renders no meshes.
+    pub fn bounds(&self) -> Option<Aabb<L>> {
+        let bounds = match &self.kind {
+            ProductKind::Stock(stock) => stock.render().bounds(),
+            ProductKind::Assembly(assembly) => Self::union_bounds(&assembly.products()),
+            ProductKind::Group(group) => Self::union_bounds(&group.0),
+            ProductKind::None => None,
+        }?;
+        Some(self.transform.transform_aabb(bounds))
+    }
+
+    fn union_bounds(products: &[Product<L>]) -> Option<Aabb<L>> {
+        products
+            .iter()
+            .filter_map(Product::bounds)
+            .reduce(|a, b| a.union(&b))
+    }
+}
 
-    fn update_transform(self, updater: impl Fn(Transform) -> Transform) -> Self {
+impl<L> Product<L> {
+    fn update_transform(self, updater: impl Fn(Transform<L>) -> Transform<L>) -> Self {
         Self {
             transform: updater(self.transform),
             ..self
@@ -85,9 +172,240 @@ impl Product {
 
 #[cfg(test)]
 mod tests {
+    use villagekit_number::{num, traits::ApproxEq, Inner};
+    use villagekit_render::{RenderableInstance, RenderableMesh};
+    use villagekit_unit::{qty, Dual};
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Block {
+        length: Length,
+    }
+
+    impl Stock for Block {
+        fn render(&self) -> Renderable {
+            Renderable::default()
+                .insert_mesh(
+                    "block".into(),
+                    RenderableMesh::Cuboid {
+                        x_length: self.length,
+                        y_length: qty!(1 m),
+                        z_length: qty!(1 m),
+                    },
+                )
+                .insert_instance(RenderableInstance {
+                    mesh: Some("block".into()),
+                    material: None,
+                    transform: None,
+                    children: None,
+                })
+        }
+    }
+
+    #[derive(Clone)]
+    struct Pair {
+        length: Length,
+    }
+
+    impl Assembly for Pair {
+        fn products(&self) -> Vec<Product> {
+            vec![
+                Block { length: qty!(1 m) }.place(),
+                Block {
+                    length: self.length,
+                }
+                .place()
+                .translate(qty!(1 m), qty!(0 m), qty!(0 m)),
+            ]
+        }
+    }
+
+    #[derive(Clone)]
+    struct Shelf {
+        width: Length,
+        bays: Number,
+    }
+
+    impl Assembly for Shelf {
+        fn products(&self) -> Vec<Product> {
+            vec![Block {
+                length: self.width / self.bays,
+            }
+            .place()]
+        }
+    }
+
+    /// A post standing on the origin, generic over its lengths.
+    #[derive(Clone)]
+    struct Post<L> {
+        height: L,
+    }
+
+    impl<L> Stock<L> for Post<L>
+    where
+        L: Copy + Default + From<Length> + Add<Output = L> + Mul<Number, Output = L>,
+    {
+        fn render(&self) -> Renderable<L> {
+            Renderable::default()
+                .insert_mesh(
+                    "post".into(),
+                    RenderableMesh::Cuboid {
+                        x_length: qty!(50 mm).into(),
+                        y_length: qty!(50 mm).into(),
+                        z_length: self.height,
+                    },
+                )
+                .insert_instance(RenderableInstance {
+                    mesh: Some("post".into()),
+                    material: None,
+                    transform: Some(Transform::default().translate(
+                        qty!(0 m).into(),
+                        qty!(0 m).into(),
+                        self.height * Number::HALF,
+                    )),
+                    children: None,
+                })
+        }
+    }
+
+    /// Two posts a span apart along x.
+    #[derive(Clone)]
+    struct Fence<L> {
+        height: L,
+        span: L,
+    }
+
+    impl<L> Assembly<L> for Fence<L>
+    where
+        L: Copy + Default + From<Length> + Add<Output = L> + Mul<Number, Output = L>,
+        L: Send + Sync + 'static,
+    {
+        fn products(&self) -> Vec<Product<L>> {
+            let post = Post {
+                height: self.height,
+            };
+            vec![
+                post.clone().place(),
+                post.place()
+                    .translate(self.span, qty!(0 m).into(), qty!(0 m).into()),
+            ]
+        }
+    }
+
+    #[test]
+    fn differentiated_bounds() {
+        // How the bounds change per centimetre of height, then per centimetre of span.
+        let by_height = Fence {
+            height: Dual::new(qty!(1 m), qty!(1 cm)),
+            span: Dual::constant(qty!(2 m)),
+        }
+        .place();
+        let bounds = by_height.bounds().unwrap();
+        assert_eq!(bounds.max.z, Dual::new(qty!(1 m), qty!(1 cm)));
+        assert_eq!(bounds.min.z, Dual::constant(qty!(0 m)));
+        assert_eq!(bounds.max.x, Dual::constant(qty!(2025 mm)));
+
+        // The sizes a cut list reads off the stocks change too.
+        let ProductKind::Assembly(fence) = &by_height.kind else {
+            panic!("a fence is an assembly");
+        };
+        let ProductKind::Stock(post) = &fence.products()[0].kind else {
+            panic!("a post is a stock");
+        };
+        let RenderableMesh::Cuboid { z_length, .. } = post.render().meshes["post"] else {
+            panic!("a post is a cuboid");
+        };
+        assert_eq!(z_length.derivative, qty!(1 cm));
+
+        let by_span = Fence {
+            height: Dual::constant(qty!(1 m)),
+            span: Dual::new(qty!(2 m), qty!(1 cm)),
+        }
+        .place();
+        assert_eq!(by_span.bounds().unwrap().size().x.derivative, qty!(1 cm));
+
+        // Turned to run along y, the span now changes the depth.
+        let turned = Product::new(ProductKind::Group(Group(vec![by_span]))).rotate(
+            Vector3::new(num!(0), num!(0), num!(1)),
+            qty!(90 deg),
+            None,
+        );
+        let size = turned.bounds().unwrap().size();
+        assert!(size.y.derivative.approx_eq(&qty!(1 cm)));
+        assert!(size.x.derivative.approx_eq(&qty!(0 m)));
+
+        // The same design still renders plain lengths.
+        let plain = Fence {
+            height: qty!(1 m),
+            span: qty!(2 m),
+        }
+        .place();
+        assert_eq!(plain.bounds().unwrap().max.z, qty!(1 m));
+    }
 
     #[test]
     fn it_works() {
-        assert!(true);
+        let product = Pair { length: qty!(2 m) }.place();
+        assert_eq!(product.validate(), Ok(()));
+    }
+
+    #[test]
+    fn bounds() {
+        let product = Pair { length: qty!(2 m) }.place();
+        let expected = Aabb::new(
+            Vector3::new(qty!(-0.5 m), qty!(-0.5 m), qty!(-0.5 m)),
+            Vector3::new(qty!(2 m), qty!(0.5 m), qty!(0.5 m)),
+        );
+        assert_eq!(product.bounds(), Some(expected));
+
+        let upright = Product::new(ProductKind::Group(Group(vec![product])))
+            .rotate(Vector3::new(num!(0), num!(1), num!(0)), qty!(-90 deg), None)
+            .translate(qty!(0 m), qty!(0 m), qty!(0.5 m));
+        assert!(upright.bounds().unwrap().approx_eq(&Aabb::new(
+            Vector3::new(qty!(-0.5 m), qty!(-0.5 m), qty!(0 m)),
+            Vector3::new(qty!(0.5 m), qty!(0.5 m), qty!(2.5 m)),
+        )));
+        assert_eq!(Product::<Length>::default().bounds(), None);
+    }
+
+    #[test]
+    fn validate_surfaces_invalid_numbers() {
+        let invalid = Length::from_canonical(Number(Inner::NAN));
+        let product = Pair { length: invalid }.place();
+        assert_eq!(product.validate(), Err(NumberError::Domain));
+
+        let infinite = Length::from_canonical(Number(Inner::INFINITY));
+        let block = Block { length: infinite };
+        assert_eq!(block.try_render().err(), Some(NumberError::Overflow));
+        assert!(Block {
+            length: num!(3) * qty!(1 m)
+        }
+        .try_render()
+        .is_ok());
+    }
+
+    #[test]
+    fn validate_catches_division_by_zero() {
+        let product = Shelf {
+            width: qty!(2 m),
+            bays: num!(0),
+        }
+        .place();
+        let expected = if Number::DECIMAL {
+            NumberError::DivideByZero
+        } else {
+            NumberError::Overflow
+        };
+        assert_eq!(product.validate(), Err(expected));
+        assert_eq!(
+            Shelf {
+                width: qty!(2 m),
+                bays: num!(4),
+            }
+            .place()
+            .validate(),
+            Ok(())
+        );
     }
 }
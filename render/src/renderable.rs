@@ -1,23 +1,46 @@
 use bevy_color::Color;
 use bevy_math::prelude::Cuboid;
 use bevy_pbr::StandardMaterial;
-use bevy_render::mesh::Mesh;
+use bevy_render::{
+    mesh::{Indices, Mesh, PrimitiveTopology},
+    render_asset::RenderAssetUsages,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use villagekit_number::Number;
-use villagekit_unit::{Dimension, Length};
+use std::{
+    collections::BTreeMap,
+    ops::{Add, Mul, Sub},
+};
+use villagekit_math::{Aabb, Triangulation, Vector3};
+use villagekit_number::{
+    traits::{Validate, Zero},
+    Number, NumberError,
+};
+use villagekit_unit::{with_units::SerdeUnits, Length};
 
 use crate::Transform;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct Renderable {
-    pub meshes: BTreeMap<String, RenderableMesh>,
+/// Meshes, materials and instances of them, sized by lengths of type `L`: [`Length`] to
+/// render, or e.g. [`Dual<Length>`](villagekit_unit::Dual) to differentiate their bounds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "L: SerdeUnits + Copy")]
+pub struct Renderable<L = Length> {
+    pub meshes: BTreeMap<String, RenderableMesh<L>>,
     pub materials: BTreeMap<String, RenderableMaterial>,
-    pub instances: Vec<RenderableInstance>,
+    pub instances: Vec<RenderableInstance<L>>,
+}
+
+impl<L> Default for Renderable<L> {
+    fn default() -> Self {
+        Self {
+            meshes: BTreeMap::new(),
+            materials: BTreeMap::new(),
+            instances: Vec::new(),
+        }
+    }
 }
 
-impl Renderable {
-    pub fn insert_mesh(mut self, key: String, mesh: RenderableMesh) -> Self {
+impl<L> Renderable<L> {
+    pub fn insert_mesh(mut self, key: String, mesh: RenderableMesh<L>) -> Self {
         self.meshes.insert(key, mesh);
         self
     }
@@ -25,29 +48,131 @@ impl Renderable {
         self.materials.insert(key, material);
         self
     }
-    pub fn insert_instance(mut self, instance: RenderableInstance) -> Self {
+    pub fn insert_instance(mut self, instance: RenderableInstance<L>) -> Self {
         // TODO check that meshes and materials exist, for children too.
         self.instances.push(instance);
         self
     }
 }
 
+impl<L> Renderable<L>
+where
+    L: Copy + Default + PartialOrd + Add<Output = L> + Sub<Output = L> + Mul<Number, Output = L>,
+{
+    /// The smallest axis-aligned box containing every instance, or `None` if there are no
+    /// meshes to bound.
+    pub fn bounds(&self) -> Option<Aabb<L>> {
+        self.instances
+            .iter()
+            .filter_map(|instance| instance.bounds(&self.meshes))
+            .reduce(|a, b| a.union(&b))
+    }
+}
+
+impl<L: Validate> Validate for Renderable<L> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.meshes.values().try_for_each(Validate::validate)?;
+        self.materials.values().try_for_each(Validate::validate)?;
+        self.instances.validate()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
-#[serde(tag = "type")]
-pub enum RenderableMesh {
+#[serde(tag = "type", bound = "L: SerdeUnits + Copy")]
+pub enum RenderableMesh<L = Length> {
     Cuboid {
-        x_length: Length,
-        y_length: Length,
-        z_length: Length,
+        #[serde(with = "villagekit_unit::with_units")]
+        x_length: L,
+        #[serde(with = "villagekit_unit::with_units")]
+        y_length: L,
+        #[serde(with = "villagekit_unit::with_units")]
+        z_length: L,
+    },
+    /// Triangles through the positions, each anticlockwise seen from its front.
+    Triangles {
+        #[serde(with = "villagekit_unit::with_units")]
+        positions: Vec<Vector3<L>>,
+        indices: Vec<[u32; 3]>,
     },
 }
 
+impl<L: Validate> Validate for RenderableMesh<L> {
+    fn validate(&self) -> Result<(), NumberError> {
+        match self {
+            RenderableMesh::Cuboid {
+                x_length,
+                y_length,
+                z_length,
+            } => {
+                x_length.validate()?;
+                y_length.validate()?;
+                z_length.validate()
+            }
+            RenderableMesh::Triangles { positions, indices } => {
+                positions.iter().try_for_each(Validate::validate)?;
+                // An index past the positions has no point to draw.
+                let len = positions.len();
+                if indices
+                    .iter()
+                    .flatten()
+                    .all(|&index| (index as usize) < len)
+                {
+                    Ok(())
+                } else {
+                    Err(NumberError::Domain)
+                }
+            }
+        }
+    }
+}
+
+impl From<Triangulation<Length>> for RenderableMesh {
+    /// A flat mesh in the xy plane, facing +z.
+    fn from(value: Triangulation<Length>) -> Self {
+        RenderableMesh::Triangles {
+            positions: value
+                .points
+                .into_iter()
+                .map(|point| point.extend(Length::zero()))
+                .collect(),
+            indices: value
+                .triangles
+                .into_iter()
+                .map(|triangle| triangle.map(|i| i as u32))
+                .collect(),
+        }
+    }
+}
+
 impl From<RenderableMesh> for Mesh {
     fn from(value: RenderableMesh) -> Self {
         value.mesh()
     }
 }
 
+impl<L> RenderableMesh<L>
+where
+    L: Copy + Default + PartialOrd + Add<Output = L> + Sub<Output = L> + Mul<Number, Output = L>,
+{
+    /// The smallest axis-aligned box containing the mesh, before any transform, or an empty
+    /// box at the origin if it has no positions.
+    pub fn bounds(&self) -> Aabb<L> {
+        match self {
+            &RenderableMesh::Cuboid {
+                x_length,
+                y_length,
+                z_length,
+            } => Aabb::from_center_size(
+                Vector3::default(),
+                Vector3::new(x_length, y_length, z_length),
+            ),
+            RenderableMesh::Triangles { positions, .. } => {
+                Aabb::from_points(positions.iter().copied()).unwrap_or_default()
+            }
+        }
+    }
+}
+
 impl RenderableMesh {
     fn mesh(&self) -> Mesh {
         match self {
@@ -61,6 +186,22 @@ impl RenderableMesh {
                 z_length.canonical().into(),
             )
             .into(),
+            RenderableMesh::Triangles { positions, indices } => {
+                let positions: Vec<[f32; 3]> = positions
+                    .iter()
+                    .map(|position| {
+                        [position.x, position.y, position.z].map(|v| v.canonical().into())
+                    })
+                    .collect();
+                Mesh::new(
+                    PrimitiveTopology::TriangleList,
+                    RenderAssetUsages::default(),
+                )
+                .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+                .with_inserted_indices(Indices::U32(indices.iter().flatten().copied().collect()))
+                .with_duplicated_vertices()
+                .with_computed_flat_normals()
+            }
         }
     }
 }
@@ -76,6 +217,24 @@ pub enum RenderableColor {
     },
 }
 
+impl Validate for RenderableColor {
+    fn validate(&self) -> Result<(), NumberError> {
+        match self {
+            RenderableColor::Hsla {
+                hue,
+                saturation,
+                lightness,
+                alpha,
+            } => {
+                hue.validate()?;
+                saturation.validate()?;
+                lightness.validate()?;
+                alpha.validate()
+            }
+        }
+    }
+}
+
 impl From<RenderableColor> for Color {
     fn from(value: RenderableColor) -> Self {
         match value {
@@ -100,6 +259,14 @@ pub enum RenderableMaterial {
     Color { color: RenderableColor },
 }
 
+impl Validate for RenderableMaterial {
+    fn validate(&self) -> Result<(), NumberError> {
+        match self {
+            RenderableMaterial::Color { color } => color.validate(),
+        }
+    }
+}
+
 impl RenderableMaterial {
     fn material(&self) -> StandardMaterial {
         match self {
@@ -115,13 +282,81 @@ impl From<RenderableMaterial> for StandardMaterial {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RenderableInstance {
+#[serde(bound = "L: SerdeUnits + Copy")]
+pub struct RenderableInstance<L = Length> {
     #[serde(default)]
     pub mesh: Option<String>,
     #[serde(default)]
     pub material: Option<String>,
     #[serde(default)]
-    pub transform: Option<Transform>,
+    pub transform: Option<Transform<L>>,
     #[serde(default)]
-    pub children: Option<Vec<RenderableInstance>>,
+    pub children: Option<Vec<RenderableInstance<L>>>,
+}
+
+impl<L> RenderableInstance<L>
+where
+    L: Copy + Default + PartialOrd + Add<Output = L> + Sub<Output = L> + Mul<Number, Output = L>,
+{
+    /// The smallest axis-aligned box containing the instance's mesh and children, in the
+    /// space of its parent, or `None` if they have no meshes.
+    pub fn bounds(&self, meshes: &BTreeMap<String, RenderableMesh<L>>) -> Option<Aabb<L>> {
+        let mesh = self
+            .mesh
+            .as_ref()
+            .and_then(|key| meshes.get(key))
+            .map(RenderableMesh::bounds);
+        let children = self
+            .children
+            .iter()
+            .flatten()
+            .filter_map(|child| child.bounds(meshes));
+        let bounds = mesh
+            .into_iter()
+            .chain(children)
+            .reduce(|a, b| a.union(&b))?;
+        Some(match &self.transform {
+            Some(transform) => transform.transform_aabb(bounds),
+            None => bounds,
+        })
+    }
+}
+
+impl<L: Validate> Validate for RenderableInstance<L> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.transform.validate()?;
+        self.children.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_unit::qty;
+
+    use super::*;
+
+    fn triangle(indices: [u32; 3]) -> RenderableMesh {
+        RenderableMesh::Triangles {
+            positions: vec![
+                Vector3::new(qty!(0 m), qty!(0 m), qty!(0 m)),
+                Vector3::new(qty!(40 mm), qty!(0 m), qty!(0 m)),
+                Vector3::new(qty!(0 m), qty!(40 mm), qty!(0 m)),
+            ],
+            indices: vec![indices],
+        }
+    }
+
+    #[test]
+    fn triangles_validate_indices() {
+        assert_eq!(triangle([0, 1, 2]).validate(), Ok(()));
+        assert_eq!(triangle([0, 1, 3]).validate(), Err(NumberError::Domain));
+    }
+
+    #[test]
+    fn triangles_serialize_with_units() {
+        let mesh = triangle([0, 1, 2]);
+        let json = serde_json::to_string(&mesh).unwrap();
+        assert!(json.contains(r#""x":"40 mm""#), "{}", json);
+        assert_eq!(serde_json::from_str::<RenderableMesh>(&json).unwrap(), mesh);
+    }
 }
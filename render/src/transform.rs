@@ -1,42 +1,64 @@
 use bevy_transform::components::Transform as BevyTransform;
 use serde::{Deserialize, Serialize};
-use villagekit_math::{Quaternion, Vector3};
-use villagekit_number::Number;
-use villagekit_unit::{Angle, Dimension, Length};
+use std::ops::{Add, Mul, Neg, Sub};
+use villagekit_math::{Aabb, Affine3, AxisRotation, Quaternion, Vector3};
+use villagekit_number::{traits::Validate, Number, NumberError};
+use villagekit_unit::{with_units::SerdeUnits, Angle, Length};
 
+/// A rotation then a translation, by lengths of type `L`: [`Length`] to render, or e.g.
+/// [`Dual<Length>`](villagekit_unit::Dual) to differentiate the placement of a product.
 #[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
-pub struct Transform {
-    translation: Vector3<Length>,
+#[serde(bound = "L: SerdeUnits + Copy")]
+pub struct Transform<L = Length> {
+    #[serde(with = "villagekit_unit::with_units")]
+    translation: Vector3<L>,
     rotation: Quaternion,
 }
 
-impl Transform {
-    pub fn apply_translation(self, translation: Vector3<Length>) -> Self {
+impl<L> Transform<L> {
+    pub fn apply_rotation(self, rotation: Quaternion) -> Self {
         Self {
-            translation: self.translation + translation,
+            rotation: self.rotation * rotation,
             ..self
         }
     }
+}
 
-    pub fn translate(self, x: Length, y: Length, z: Length) -> Self {
-        self.apply_translation(Vector3::new(x, y, z))
-    }
-
-    pub fn apply_rotation(self, rotation: Quaternion) -> Self {
+impl<L> Transform<L>
+where
+    L: Copy + Add<Output = L>,
+{
+    pub fn apply_translation(self, translation: Vector3<L>) -> Self {
         Self {
-            rotation: self.rotation * rotation,
+            translation: self.translation + translation,
             ..self
         }
     }
 
+    pub fn translate(self, x: L, y: L, z: L) -> Self {
+        self.apply_translation(Vector3::new(x, y, z))
+    }
+}
+
+impl<L> Transform<L>
+where
+    L: Copy
+        + Default
+        + Add<Output = L>
+        + Sub<Output = L>
+        + Neg<Output = L>
+        + Mul<Number, Output = L>,
+    Number: Mul<L, Output = L>,
+{
     /// Rotate this transform around an arbitrary axis that passes through a given origin.
-    pub fn rotate(
-        self,
-        axis: Vector3<Number>,
-        angle: Angle,
-        origin: Option<Vector3<Length>>,
-    ) -> Self {
+    ///
+    /// Quarter turns about the x, y or z axis are exact: the translation has no rounding,
+    /// and a rotation of only quarter turns is always the same quaternion.
+    pub fn rotate(self, axis: Vector3<Number>, angle: Angle, origin: Option<Vector3<L>>) -> Self {
         let origin = origin.unwrap_or_default();
+        if let Some(rotation) = AxisRotation::from_axis_angle(axis, angle) {
+            return self.rotate_exact(rotation, origin);
+        }
         let rotation = Quaternion::from_axis_angle(axis, angle);
 
         Self {
@@ -48,6 +70,38 @@ impl Transform {
             rotation: self.rotation * rotation,
         }
     }
+
+    fn rotate_exact(self, rotation: AxisRotation, origin: Vector3<L>) -> Self {
+        let quarter_turns = AxisRotation::from_quaternion(self.rotation)
+            .and_then(|current| (current * rotation).to_quaternion());
+        Self {
+            translation: rotation * (self.translation - origin) + origin,
+            rotation: quarter_turns.unwrap_or_else(|| {
+                // Not a grid rotation to begin with, so there's nothing to keep exact.
+                self.rotation
+                    * rotation
+                        .to_quaternion()
+                        .expect("quarter turns don't mirror")
+            }),
+        }
+    }
+}
+
+impl<L> Transform<L>
+where
+    L: Copy + Add<Output = L> + Sub<Output = L> + Mul<Number, Output = L>,
+{
+    /// Returns the smallest axis-aligned box containing a box once transformed.
+    pub fn transform_aabb(&self, aabb: Aabb<L>) -> Aabb<L> {
+        aabb.transform(self.rotation, self.translation)
+    }
+}
+
+impl<L: Validate> Validate for Transform<L> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.translation.validate()?;
+        self.rotation.validate()
+    }
 }
 
 impl From<Transform> for BevyTransform {
@@ -59,10 +113,21 @@ impl From<Transform> for BevyTransform {
         }
     }
 }
+
+/// The transform as an affine map, to apply to points or compose with other transforms.
+impl From<Transform> for Affine3<Length> {
+    fn from(value: Transform) -> Self {
+        Affine3 {
+            translation: value.translation,
+            ..Affine3::from_rotation(value.rotation)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use villagekit_number::{num, traits::ApproxEq};
-    use villagekit_unit::{qty, Radians};
+    use villagekit_unit::{qty, Radians, Rotations};
 
     use super::*;
 
@@ -149,6 +214,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotate_quarter_turns() {
+        // A beam turned upright then end over end, as grid-beam products are placed.
+        let initial = Transform::default().translate(qty!(0.1 m), qty!(0.2 m), qty!(0.3 m));
+        let result = initial
+            .rotate(
+                Vector3::new(num!(0), num!(1), num!(0)),
+                -Rotations::QUARTER,
+                None,
+            )
+            .rotate(
+                Vector3::new(num!(1), num!(0), num!(0)),
+                Rotations::HALF,
+                None,
+            )
+            .rotate(
+                Vector3::new(num!(1), num!(0), num!(0)),
+                Rotations::HALF,
+                None,
+            );
+        assert_eq!(
+            result.translation,
+            Vector3::new(qty!(-0.3 m), qty!(0.2 m), qty!(0.1 m))
+        );
+        let upright = Transform::<Length>::default().rotate(
+            Vector3::new(num!(0), num!(1), num!(0)),
+            -Rotations::QUARTER,
+            None,
+        );
+        assert_eq!(result.rotation, upright.rotation);
+        assert_eq!(
+            AxisRotation::from_quaternion(result.rotation).unwrap()
+                * Vector3::new(num!(1), num!(0), num!(0)),
+            Vector3::new(num!(0), num!(0), num!(1))
+        );
+    }
+
+    #[test]
+    fn test_into_affine() {
+        let parent = Transform::default()
+            .rotate(
+                Vector3::new(num!(0), num!(0), num!(1)),
+                Radians::FRAC_PI_2,
+                None,
+            )
+            .translate(qty!(1 m), qty!(0 m), qty!(0 m));
+        let child = Transform::default().translate(qty!(2 m), qty!(0 m), qty!(0 m));
+        let world = Affine3::from(parent) * Affine3::from(child);
+        let corner = Vector3::new(qty!(0 m), qty!(1 m), qty!(0 m));
+        assert_approx_eq!(
+            &(world * corner),
+            &Vector3::new(qty!(0 m), qty!(2 m), qty!(0 m))
+        );
+    }
+
     #[test]
     fn test_rotate_with_origin() {
         // Rotate a point at (2,0,0) 90° about the z-axis with pivot (1,0,0).
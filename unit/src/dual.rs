@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+use villagekit_number::{
+    traits::{Abs, ApproxEq, One, Sqrt, Trig, TrigInv, Validate, Zero},
+    Number, NumberError,
+};
+
+use crate::Angle;
+
+/// A value paired with its derivative with respect to one input parameter, for
+/// forward-mode automatic differentiation.
+///
+/// The derivative is the (linearised) change in the value for one step of the
+/// parameter, e.g. seeding a chair width with `Dual::new(width, qty!(1 cm))`
+/// gives derivatives of every length computed from it per centimetre of width.
+///
+/// Duals go wherever the math is generic over the scalar, e.g. `Vector3<Dual<Length>>`
+/// or `Aabb<Dual<Length>>`. Products are generic over their lengths too, so a design
+/// built from `Dual<Length>` gives the sensitivities of its sizes and bounding box.
+///
+/// Duals are ordered by value, then by derivative.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct Dual<T> {
+    pub value: T,
+    pub derivative: T,
+}
+
+impl<T> Dual<T> {
+    pub const fn new(value: T, derivative: T) -> Self {
+        Self { value, derivative }
+    }
+}
+
+impl<T: One> Dual<T> {
+    /// Creates the input parameter being differentiated against, so derivatives are
+    /// per unit of it. Dimensioned parameters have no unit step, so are seeded with
+    /// [`Dual::new`] and a step of their own instead.
+    pub fn variable(value: T) -> Self {
+        Self::new(value, T::one())
+    }
+}
+
+impl<T: Zero> Dual<T> {
+    /// Creates a value which does not depend on the parameter.
+    pub fn constant(value: T) -> Self {
+        Self::new(value, T::zero())
+    }
+}
+
+/// A constant, so designs generic over `L: From<Length>` can use plain lengths.
+impl<T: Zero> From<T> for Dual<T> {
+    fn from(value: T) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<T: Display> Display for Dual<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {}ε", self.value, self.derivative)
+    }
+}
+
+impl<A, B, C> Add<Dual<B>> for Dual<A>
+where
+    A: Add<B, Output = C>,
+{
+    type Output = Dual<C>;
+
+    fn add(self, rhs: Dual<B>) -> Self::Output {
+        Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+
+impl<A, B, C> Sub<Dual<B>> for Dual<A>
+where
+    A: Sub<B, Output = C>,
+{
+    type Output = Dual<C>;
+
+    fn sub(self, rhs: Dual<B>) -> Self::Output {
+        Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+
+impl<T> Neg for Dual<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+impl<A, B, C> Mul<Dual<B>> for Dual<A>
+where
+    A: Copy + Mul<B, Output = C>,
+    B: Copy,
+    C: Add<Output = C>,
+{
+    type Output = Dual<C>;
+
+    fn mul(self, rhs: Dual<B>) -> Self::Output {
+        Dual::new(
+            self.value * rhs.value,
+            self.derivative * rhs.value + self.value * rhs.derivative,
+        )
+    }
+}
+
+impl<A, B, C> Div<Dual<B>> for Dual<A>
+where
+    A: Copy + Div<B, Output = C>,
+    B: Copy + Div<B, Output = Number>,
+    C: Copy + Sub<Output = C> + Mul<Number, Output = C>,
+{
+    type Output = Dual<C>;
+
+    fn div(self, rhs: Dual<B>) -> Self::Output {
+        let value = self.value / rhs.value;
+        Dual::new(
+            value,
+            self.derivative / rhs.value - value * (rhs.derivative / rhs.value),
+        )
+    }
+}
+
+impl<T> Mul<Number> for Dual<T>
+where
+    T: Mul<Number, Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Number) -> Self::Output {
+        Dual::new(self.value * rhs, self.derivative * rhs)
+    }
+}
+
+impl<T> Mul<Dual<T>> for Number
+where
+    T: Mul<Number, Output = T>,
+{
+    type Output = Dual<T>;
+
+    fn mul(self, rhs: Dual<T>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<T> Div<Number> for Dual<T>
+where
+    T: Div<Number, Output = T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Number) -> Self::Output {
+        Dual::new(self.value / rhs, self.derivative / rhs)
+    }
+}
+
+impl<T> Sum for Dual<T>
+where
+    T: Zero + Add<Output = T>,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, dual| sum + dual)
+    }
+}
+
+impl<T: Zero> Zero for Dual<T> {
+    fn zero() -> Self {
+        Dual::new(T::zero(), T::zero())
+    }
+}
+
+impl<T> Abs for Dual<T>
+where
+    T: PartialOrd + Zero + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn abs(self) -> Self::Output {
+        if self.value < T::zero() {
+            -self
+        } else {
+            self
+        }
+    }
+}
+
+impl<T, U> Sqrt for Dual<T>
+where
+    T: Sqrt<Output = U> + Div<U, Output = U>,
+    U: Copy + Mul<Number, Output = U>,
+{
+    type Output = Dual<U>;
+
+    fn sqrt(self) -> Self::Output {
+        let value = self.value.sqrt();
+        Dual::new(value, self.derivative / value * Number::HALF)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Dual<T> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.value.approx_eq(&rhs.value) && self.derivative.approx_eq(&rhs.derivative)
+    }
+}
+
+impl<T: Validate> Validate for Dual<T> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.value.validate()?;
+        self.derivative.validate()
+    }
+}
+
+impl Trig for Dual<Number> {
+    type Output = Dual<Number>;
+
+    fn hypot(&self, other: Self) -> Self::Output {
+        let value = self.value.hypot(other.value);
+        let derivative = (self.value * self.derivative + other.value * other.derivative) / value;
+        Dual::new(value, derivative)
+    }
+
+    fn sin(&self) -> Self::Output {
+        let (sin, cos) = self.value.sin_cos();
+        Dual::new(sin, cos * self.derivative)
+    }
+
+    fn cos(&self) -> Self::Output {
+        let (sin, cos) = self.value.sin_cos();
+        Dual::new(cos, -sin * self.derivative)
+    }
+
+    fn tan(&self) -> Self::Output {
+        let (sin, cos) = self.value.sin_cos();
+        Dual::new(sin / cos, self.derivative / (cos * cos))
+    }
+
+    fn sin_cos(&self) -> (Self::Output, Self::Output) {
+        let (sin, cos) = self.value.sin_cos();
+        (
+            Dual::new(sin, cos * self.derivative),
+            Dual::new(cos, -sin * self.derivative),
+        )
+    }
+}
+
+impl TrigInv for Dual<Number> {
+    type Output = Dual<Number>;
+
+    fn asin(&self) -> Self::Output {
+        let slope = (Number::ONE - self.value * self.value).sqrt();
+        Dual::new(self.value.asin(), self.derivative / slope)
+    }
+
+    fn acos(&self) -> Self::Output {
+        let slope = (Number::ONE - self.value * self.value).sqrt();
+        Dual::new(self.value.acos(), -self.derivative / slope)
+    }
+
+    fn atan(&self) -> Self::Output {
+        let slope = Number::ONE + self.value * self.value;
+        Dual::new(self.value.atan(), self.derivative / slope)
+    }
+
+    fn atan2(&self, other: Self) -> Self::Output {
+        let (y, x) = (self, other);
+        let derivative = (x.value * y.derivative - y.value * x.derivative)
+            / (x.value * x.value + y.value * y.value);
+        Dual::new(y.value.atan2(x.value), derivative)
+    }
+}
+
+impl Dual<Angle> {
+    fn radians(&self) -> Dual<Number> {
+        Dual::new(self.value.canonical(), self.derivative.canonical())
+    }
+}
+
+impl Trig for Dual<Angle> {
+    type Output = Dual<Number>;
+
+    fn hypot(&self, other: Self) -> Self::Output {
+        self.radians().hypot(other.radians())
+    }
+
+    fn sin(&self) -> Self::Output {
+        self.radians().sin()
+    }
+
+    fn cos(&self) -> Self::Output {
+        self.radians().cos()
+    }
+
+    fn tan(&self) -> Self::Output {
+        self.radians().tan()
+    }
+
+    fn sin_cos(&self) -> (Self::Output, Self::Output) {
+        self.radians().sin_cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::{num, Trig, TrigInv};
+
+    use crate::*;
+
+    #[test]
+    fn product_and_quotient_rules() {
+        // f(x) = x² / (x + 1) at x = 2: f = 4/3, f' = (x² + 2x) / (x + 1)² = 8/9
+        let x = Dual::variable(num!(2));
+        let f = x * x / (x + Dual::constant(num!(1)));
+        assert!(f.approx_eq(&Dual::new(num!(4) / num!(3), num!(8) / num!(9))));
+    }
+
+    #[test]
+    fn beam_length_per_cm_of_width() {
+        let width = Dual::new(qty!(500 mm), qty!(1 cm));
+        let depth = Dual::constant(qty!(400 mm));
+        let height = Dual::constant(qty!(450 mm));
+
+        // Four legs, plus a rail along each edge of the seat.
+        let total: Dual<Length> = [height, height, height, height, width, width, depth, depth]
+            .into_iter()
+            .sum();
+        assert!(total.approx_eq(&Dual::new(qty!(3.6 m), qty!(2 cm))));
+
+        let seat_area = width * depth;
+        assert!(seat_area.derivative.approx_eq(&(qty!(1 cm) * qty!(400 mm))));
+
+        // A diagonal brace across the seat: d/dw √(w² + d²) = w / √(w² + d²)
+        let brace = (width * width + depth * depth).sqrt();
+        let expected = qty!(1 cm) * (qty!(500 mm) / brace.value);
+        assert!(brace.derivative.approx_eq(&expected));
+    }
+
+    #[test]
+    fn trig_derivatives() {
+        let angle = Dual::new(qty!(30 deg), qty!(1 rad));
+        let (sin, cos) = angle.sin_cos();
+        assert!(sin.derivative.approx_eq(&qty!(30 deg).cos()));
+        assert!(cos.derivative.approx_eq(&-qty!(30 deg).sin()));
+
+        let x = Dual::variable(num!(0.5));
+        // d/dx atan(x) = 1 / (1 + x²)
+        assert!(x.atan().derivative.approx_eq(&(num!(1) / num!(1.25))));
+        // d/dx atan2(y, x) = -y / (x² + y²)
+        let y = Dual::constant(num!(0.25));
+        assert!(y.atan2(x).derivative.approx_eq(&num!(-0.8)));
+    }
+}
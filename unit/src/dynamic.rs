@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use villagekit_number::{traits::Validate, Number};
+
+use crate::{parse::UnitEntry, Dimension};
+
+/// A unit defined at runtime, e.g. the 40 mm or 1.5" grid unit of a grid beam system.
+///
+/// ```
+/// use villagekit_unit::*;
+///
+/// let grid = DynamicUnit::new("grid unit", "gu", qty!(40 mm)).unwrap();
+/// let length = Length::from_dynamic(num!(3), &grid);
+/// assert_eq!(length, qty!(120 mm));
+/// assert_eq!(length.to_dynamic(&grid), num!(3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Fields", bound = "")]
+pub struct DynamicUnit<D> {
+    pub name: String,
+    pub abbreviation: String,
+    coefficient: Number,
+    constant: Number,
+    #[serde(skip)]
+    dimension: PhantomData<fn() -> D>,
+}
+
+impl<D: Dimension> DynamicUnit<D> {
+    /// Creates a unit of the given size, or `None` unless the size is above zero, as nothing
+    /// could be measured in it.
+    pub fn new(name: impl Into<String>, abbreviation: impl Into<String>, size: D) -> Option<Self> {
+        Self::from_parts(
+            name.into(),
+            abbreviation.into(),
+            size.canonical(),
+            Number::ZERO,
+        )
+    }
+
+    /// Sets where the unit starts counting from, e.g. `qty!(0 degC)` for a temperature scale
+    /// which reads zero at freezing.
+    pub fn with_zero(mut self, zero: D) -> Self {
+        self.constant = zero.canonical() / self.coefficient;
+        self
+    }
+
+    /// Returns the size of one unit.
+    pub fn size(&self) -> D {
+        D::from_canonical(self.coefficient)
+    }
+}
+
+impl<D> DynamicUnit<D> {
+    /// The size of one unit in the canonical unit of the dimension.
+    pub fn coefficient(&self) -> Number {
+        self.coefficient
+    }
+
+    /// The canonical zero in this unit, negated, as with [`UnitOf::CONVERSION_CONSTANT`];
+    /// zero except for units of point dimensions such as temperature.
+    ///
+    /// [`UnitOf::CONVERSION_CONSTANT`]: crate::UnitOf::CONVERSION_CONSTANT
+    pub fn constant(&self) -> Number {
+        self.constant
+    }
+
+    fn from_parts(
+        name: String,
+        abbreviation: String,
+        coefficient: Number,
+        constant: Number,
+    ) -> Option<Self> {
+        let valid = coefficient > Number::ZERO
+            && coefficient.validate().is_ok()
+            && constant.validate().is_ok();
+        valid.then_some(Self {
+            name,
+            abbreviation,
+            coefficient,
+            constant,
+            dimension: PhantomData,
+        })
+    }
+
+    fn retype<E>(self) -> DynamicUnit<E> {
+        DynamicUnit {
+            name: self.name,
+            abbreviation: self.abbreviation,
+            coefficient: self.coefficient,
+            constant: self.constant,
+            dimension: PhantomData,
+        }
+    }
+}
+
+/// The fields of a [`DynamicUnit`] as deserialized, before its size is checked.
+#[derive(Deserialize)]
+struct Fields {
+    name: String,
+    abbreviation: String,
+    coefficient: Number,
+    #[serde(default)]
+    constant: Number,
+}
+
+impl<D> TryFrom<Fields> for DynamicUnit<D> {
+    type Error = &'static str;
+
+    fn try_from(fields: Fields) -> Result<Self, Self::Error> {
+        Self::from_parts(
+            fields.name,
+            fields.abbreviation,
+            fields.coefficient,
+            fields.constant,
+        )
+        .ok_or("the size of a unit must be above zero")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RegisteredUnit {
+    dimension: String,
+    #[serde(flatten)]
+    unit: DynamicUnit<()>,
+}
+
+/// A set of [`DynamicUnit`]s, for parsing and formatting quantities in them
+/// alongside the units of [`qty!`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitRegistry {
+    units: Vec<RegisteredUnit>,
+}
+
+impl UnitRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a unit, replacing any unit with the same abbreviation.
+    pub fn register<D: Dimension>(&mut self, unit: DynamicUnit<D>) -> &mut Self {
+        self.units
+            .retain(|registered| registered.unit.abbreviation != unit.abbreviation);
+        self.units.push(RegisteredUnit {
+            dimension: D::NAME.into(),
+            unit: unit.retype(),
+        });
+        self
+    }
+
+    pub fn with<D: Dimension>(mut self, unit: DynamicUnit<D>) -> Self {
+        self.register(unit);
+        self
+    }
+
+    /// Returns the unit of the given dimension with the given abbreviation or name.
+    pub fn get<D: Dimension>(&self, symbol: &str) -> Option<DynamicUnit<D>> {
+        self.find(symbol)
+            .filter(|registered| registered.dimension == D::NAME)
+            .map(|registered| registered.unit.clone().retype())
+    }
+
+    fn find(&self, symbol: &str) -> Option<&RegisteredUnit> {
+        self.units.iter().find(|registered| {
+            registered.unit.abbreviation == symbol || registered.unit.name == symbol
+        })
+    }
+
+    pub(crate) fn lookup(&self, symbol: &str) -> Option<UnitEntry<'_>> {
+        self.find(symbol).map(|registered| UnitEntry {
+            symbol: &registered.unit.abbreviation,
+            name: Some(&registered.unit.name),
+            dimension: &registered.dimension,
+            coefficient: registered.unit.coefficient,
+            constant: registered.unit.constant,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn registry() -> UnitRegistry {
+        UnitRegistry::new()
+            .with(DynamicUnit::new("grid unit", "gu", qty!(40 mm)).unwrap())
+            .with(DynamicUnit::new("imperial grid unit", "igu", qty!(1.5 in)).unwrap())
+    }
+
+    #[test]
+    fn convert() {
+        let grid = DynamicUnit::new("grid unit", "gu", qty!(1.5 in)).unwrap();
+        assert_eq!(Length::from_dynamic(num!(2), &grid), qty!(3 in));
+        assert_eq!(qty!(6 in).to_dynamic(&grid), num!(4));
+        assert_eq!(grid.size(), qty!(1.5 in));
+        assert_eq!(
+            registry().get::<Length>("gu").map(|unit| unit.size()),
+            Some(qty!(40 mm))
+        );
+        assert_eq!(registry().get::<Area>("gu"), None);
+        assert_eq!(DynamicUnit::new("nothing", "nil", qty!(0 mm)), None);
+        assert_eq!(DynamicUnit::new("backwards", "bk", qty!(-1 mm)), None);
+    }
+
+    #[test]
+    fn convert_points() {
+        let reaumur = DynamicUnit::new("degree Réaumur", "°Ré", qty!(1.25 K))
+            .unwrap()
+            .with_zero(qty!(0 degC));
+        assert!(Temperature::from_dynamic(num!(80), &reaumur).approx_eq(&qty!(100 degC)));
+        assert!(qty!(100 degC).to_dynamic(&reaumur).approx_eq(&num!(80)));
+        assert!(qty!(0 degC).to_dynamic(&reaumur).approx_eq(&num!(0)));
+        let warm = qty!(20 degC);
+        assert!(Temperature::from_dynamic(warm.to_dynamic(&reaumur), &reaumur).approx_eq(&warm));
+
+        let units = UnitRegistry::new().with(reaumur);
+        assert!(Temperature::parse_with("80 °Ré", &units)
+            .unwrap()
+            .approx_eq(&qty!(100 degC)));
+    }
+
+    #[test]
+    fn serde_checked() {
+        let grid = DynamicUnit::new("grid unit", "gu", qty!(40 mm)).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DynamicUnit<Length>>(&json).unwrap(),
+            grid
+        );
+        for size in [r#""0""#, r#""-0.04""#] {
+            let bad = json.replace(r#""0.04""#, size);
+            assert_ne!(bad, json);
+            assert!(serde_json::from_str::<DynamicUnit<Length>>(&bad).is_err());
+        }
+
+        let units = registry();
+        let json = serde_json::to_string(&units).unwrap();
+        assert_eq!(serde_json::from_str::<UnitRegistry>(&json).unwrap(), units);
+        let bad = json.replace(r#""0.04""#, r#""0""#);
+        assert_ne!(bad, json);
+        assert!(serde_json::from_str::<UnitRegistry>(&bad).is_err());
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let units = registry();
+        assert_eq!(Length::parse_with("3 gu", &units), Ok(qty!(120 mm)));
+        assert_eq!(Length::parse_with("1 gu 5 mm", &units), Ok(qty!(45 mm)));
+        assert_eq!(
+            Length::parse("3 gu"),
+            Err(ParseQuantityError::UnknownUnit("gu".into()))
+        );
+        assert_eq!(
+            Area::parse_with("3 gu", &units),
+            Err(ParseQuantityError::MismatchedUnit {
+                unit: "gu".into(),
+                expected: "Area",
+                found: "Length".into(),
+            })
+        );
+
+        let mut format = QuantityFormat::new(UnitSystem::Custom(CustomUnits {
+            length: "gu".into(),
+            area: "m2".into(),
+            volume: "l".into(),
+            angle: "deg".into(),
+        }));
+        format.units = units;
+        assert_eq!(format.format(qty!(120 mm)), "3 gu");
+        format.unit_style = UnitStyle::Name;
+        assert_eq!(format.format(qty!(40 mm)), "1 grid unit");
+        assert_eq!(format.format(qty!(120 mm)), "3 grid units");
+    }
+}
@@ -0,0 +1,669 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display},
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+    sync::Arc,
+};
+use villagekit_number::{
+    traits::{
+        CheckedAdd, CheckedDiv, CheckedMul, CheckedSqrt, CheckedSub, CheckedTrigInv, Sqrt, Trig,
+        TrigInv,
+    },
+    Number, NumberError,
+};
+
+use crate::{Dimension, UnitSymbol};
+
+/// A value which can be used in an [`Expr`], stored as its canonical number.
+pub trait ExprValue: Copy {
+    /// The symbol of the canonical unit, or empty for plain numbers.
+    const UNIT: &'static str;
+
+    fn to_canonical(self) -> Number;
+    fn from_canonical(value: Number) -> Self;
+}
+
+impl ExprValue for Number {
+    const UNIT: &'static str = "";
+
+    fn to_canonical(self) -> Number {
+        self
+    }
+
+    fn from_canonical(value: Number) -> Self {
+        value
+    }
+}
+
+impl<T> ExprValue for T
+where
+    T: Dimension + Copy,
+    T::CanonicalUnit: UnitSymbol,
+{
+    const UNIT: &'static str = <T::CanonicalUnit as UnitSymbol>::SYMBOL;
+
+    fn to_canonical(self) -> Number {
+        self.canonical()
+    }
+
+    fn from_canonical(value: Number) -> Self {
+        <T as Dimension>::from_canonical(value)
+    }
+}
+
+/// An evaluation of an [`Expr`] without a meaningful result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    /// A variable without a value in the [`Bindings`].
+    UnboundVariable(String),
+    /// A variable bound to a value of a different dimension.
+    UnitMismatch {
+        variable: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// An operation on the values without a meaningful result.
+    Number(NumberError),
+}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::UnboundVariable(name) => write!(f, "no value for variable `{}`", name),
+            ExprError::UnitMismatch {
+                variable,
+                expected,
+                found,
+            } => write!(
+                f,
+                "variable `{}` expects a value in `{}`, found `{}`",
+                variable, expected, found
+            ),
+            ExprError::Number(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+impl From<NumberError> for ExprError {
+    fn from(value: NumberError) -> Self {
+        ExprError::Number(value)
+    }
+}
+
+/// The values of the variables in an [`Expr`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bindings {
+    values: HashMap<String, (Number, &'static str)>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set<T: ExprValue>(&mut self, name: impl Into<String>, value: T) -> &mut Self {
+        self.values
+            .insert(name.into(), (value.to_canonical(), T::UNIT));
+        self
+    }
+
+    pub fn with<T: ExprValue>(mut self, name: impl Into<String>, value: T) -> Self {
+        self.set(name, value);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Sqrt,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Hypot,
+}
+
+impl Function {
+    fn name(&self) -> &'static str {
+        match self {
+            Function::Sqrt => "sqrt",
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Asin => "asin",
+            Function::Acos => "acos",
+            Function::Atan => "atan",
+            Function::Atan2 => "atan2",
+            Function::Hypot => "hypot",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Op {
+    Constant(Number),
+    Variable(String),
+    Neg(Arc<Node>),
+    Binary(BinaryOp, Arc<Node>, Arc<Node>),
+    Call(Function, Vec<Arc<Node>>),
+}
+
+/// A node of the computation graph, with the unit symbol of its result.
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    op: Op,
+    unit: &'static str,
+}
+
+impl Node {
+    fn new(op: Op, unit: &'static str) -> Arc<Self> {
+        Arc::new(Self { op, unit })
+    }
+
+    fn constant(&self) -> Option<Number> {
+        match self.op {
+            Op::Constant(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn is_constant(&self, value: Number) -> bool {
+        self.constant() == Some(value)
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Result<Number, ExprError> {
+        Ok(match &self.op {
+            Op::Constant(value) => *value,
+            Op::Variable(name) => {
+                let (value, unit) = bindings
+                    .values
+                    .get(name)
+                    .ok_or_else(|| ExprError::UnboundVariable(name.clone()))?;
+                if *unit != self.unit {
+                    return Err(ExprError::UnitMismatch {
+                        variable: name.clone(),
+                        expected: self.unit,
+                        found: unit,
+                    });
+                }
+                *value
+            }
+            Op::Neg(node) => -node.evaluate(bindings)?,
+            Op::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(bindings)?, rhs.evaluate(bindings)?);
+                match op {
+                    BinaryOp::Add => lhs.checked_add(rhs)?,
+                    BinaryOp::Sub => lhs.checked_sub(rhs)?,
+                    BinaryOp::Mul => lhs.checked_mul(rhs)?,
+                    BinaryOp::Div => lhs.checked_div(rhs)?,
+                }
+            }
+            Op::Call(function, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(bindings))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match function {
+                    Function::Sqrt => args[0].checked_sqrt()?,
+                    Function::Sin => args[0].sin(),
+                    Function::Cos => args[0].cos(),
+                    Function::Tan => args[0].tan(),
+                    Function::Asin => args[0].checked_asin()?,
+                    Function::Acos => args[0].checked_acos()?,
+                    Function::Atan => args[0].atan(),
+                    Function::Atan2 => args[0].atan2(args[1]),
+                    Function::Hypot => args[0].hypot(args[1]),
+                }
+            }
+        })
+    }
+
+    fn simplify(self: &Arc<Self>) -> Arc<Self> {
+        let node = match &self.op {
+            Op::Constant(_) | Op::Variable(_) => return self.clone(),
+            Op::Neg(node) => {
+                let node = node.simplify();
+                if let Op::Neg(inner) = &node.op {
+                    return inner.clone();
+                }
+                Node::new(Op::Neg(node), self.unit)
+            }
+            Op::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.simplify(), rhs.simplify());
+                if let Some(node) = self.simplify_binary(*op, &lhs, &rhs) {
+                    return node;
+                }
+                Node::new(Op::Binary(*op, lhs, rhs), self.unit)
+            }
+            Op::Call(function, args) => Node::new(
+                Op::Call(*function, args.iter().map(Node::simplify).collect()),
+                self.unit,
+            ),
+        };
+        // Fold constant subtrees, keeping the operation if it fails so the error surfaces on evaluation.
+        let is_constant = match &node.op {
+            Op::Neg(node) => node.constant().is_some(),
+            Op::Binary(_, lhs, rhs) => lhs.constant().is_some() && rhs.constant().is_some(),
+            Op::Call(_, args) => args.iter().all(|arg| arg.constant().is_some()),
+            _ => false,
+        };
+        if !is_constant {
+            return node;
+        }
+        match node.evaluate(&Bindings::new()) {
+            Ok(value) => Node::new(Op::Constant(value.normalize()), self.unit),
+            Err(_) => node,
+        }
+    }
+
+    /// Removes identities such as adding zero or multiplying by one.
+    fn simplify_binary(&self, op: BinaryOp, lhs: &Arc<Node>, rhs: &Arc<Node>) -> Option<Arc<Node>> {
+        let keep = |node: &Arc<Node>| (node.unit == self.unit).then(|| node.clone());
+        match op {
+            BinaryOp::Add if lhs.is_constant(Number::ZERO) => keep(rhs),
+            BinaryOp::Add | BinaryOp::Sub if rhs.is_constant(Number::ZERO) => keep(lhs),
+            BinaryOp::Sub if lhs == rhs => Some(Node::new(Op::Constant(Number::ZERO), self.unit)),
+            BinaryOp::Sub if lhs.is_constant(Number::ZERO) => {
+                Some(Node::new(Op::Neg(rhs.clone()), self.unit))
+            }
+            BinaryOp::Mul if lhs.is_constant(Number::ZERO) || rhs.is_constant(Number::ZERO) => {
+                Some(Node::new(Op::Constant(Number::ZERO), self.unit))
+            }
+            BinaryOp::Mul if lhs.is_constant(Number::ONE) => keep(rhs),
+            BinaryOp::Mul | BinaryOp::Div if rhs.is_constant(Number::ONE) => keep(lhs),
+            _ => None,
+        }
+    }
+
+    fn variables<'a>(&'a self, variables: &mut BTreeSet<&'a str>) {
+        match &self.op {
+            Op::Constant(_) => {}
+            Op::Variable(name) => {
+                variables.insert(name);
+            }
+            Op::Neg(node) => node.variables(variables),
+            Op::Binary(_, lhs, rhs) => {
+                lhs.variables(variables);
+                rhs.variables(variables);
+            }
+            Op::Call(_, args) => args.iter().for_each(|arg| arg.variables(variables)),
+        }
+    }
+
+    /// Binding strength when printed, higher binds tighter.
+    fn precedence(&self) -> u8 {
+        match &self.op {
+            Op::Constant(value) if *value < Number::ZERO => 3,
+            Op::Constant(_) | Op::Variable(_) | Op::Call(..) => 4,
+            Op::Neg(_) => 3,
+            Op::Binary(BinaryOp::Mul | BinaryOp::Div, ..) => 2,
+            Op::Binary(BinaryOp::Add | BinaryOp::Sub, ..) => 1,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, parens: bool) -> fmt::Result {
+        if parens {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.op {
+            Op::Constant(value) if self.unit.is_empty() => write!(f, "{}", value.normalize()),
+            Op::Constant(value) => write!(f, "{} {}", value.normalize(), self.unit),
+            Op::Variable(name) => write!(f, "{}", name),
+            Op::Neg(node) => {
+                write!(f, "-")?;
+                node.fmt_operand(f, node.precedence() < 4)
+            }
+            Op::Binary(op, lhs, rhs) => {
+                let (symbol, precedence) = match op {
+                    BinaryOp::Add => ("+", 1),
+                    BinaryOp::Sub => ("-", 1),
+                    BinaryOp::Mul => ("*", 2),
+                    BinaryOp::Div => ("/", 2),
+                };
+                // Subtraction and division are left-associative, so a right operand of the same precedence needs parentheses.
+                let right_associates = matches!(op, BinaryOp::Sub | BinaryOp::Div);
+                lhs.fmt_operand(f, lhs.precedence() < precedence)?;
+                write!(f, " {} ", symbol)?;
+                rhs.fmt_operand(
+                    f,
+                    rhs.precedence() < precedence
+                        || (right_associates && rhs.precedence() == precedence),
+                )
+            }
+            Op::Call(function, args) => {
+                write!(f, "{}(", function.name())?;
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// A symbolic expression which records how a quantity is computed from named
+/// parameters, to be evaluated later with [`Bindings`].
+///
+/// ```
+/// use villagekit_unit::*;
+///
+/// let width = Expr::<Length>::variable("width");
+/// let beam = width - qty!(1 m);
+/// assert_eq!(beam.to_string(), "width - 1 m");
+///
+/// let bindings = Bindings::new().with("width", qty!(3 m));
+/// assert_eq!(beam.evaluate(&bindings), Ok(qty!(2 m)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr<T> {
+    node: Arc<Node>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T: ExprValue> Expr<T> {
+    fn from_node(node: Arc<Node>) -> Self {
+        Self {
+            node,
+            value: PhantomData,
+        }
+    }
+
+    pub fn constant(value: T) -> Self {
+        Self::from_node(Node::new(Op::Constant(value.to_canonical()), T::UNIT))
+    }
+
+    pub fn variable(name: impl Into<String>) -> Self {
+        Self::from_node(Node::new(Op::Variable(name.into()), T::UNIT))
+    }
+
+    /// Evaluates the expression with the given values for its variables.
+    pub fn evaluate(&self, bindings: &Bindings) -> Result<T, ExprError> {
+        Ok(T::from_canonical(self.node.evaluate(bindings)?))
+    }
+
+    /// Returns an equivalent expression with constant subexpressions folded and
+    /// identities such as `x + 0` and `x * 1` removed.
+    ///
+    /// The identities hold whatever the variables are, so `x - x` becomes `0` and no
+    /// longer needs `x` to be bound.
+    pub fn simplify(&self) -> Self {
+        Self::from_node(self.node.simplify())
+    }
+
+    /// Returns the names of the variables used by the expression.
+    pub fn variables(&self) -> BTreeSet<&str> {
+        let mut variables = BTreeSet::new();
+        self.node.variables(&mut variables);
+        variables
+    }
+
+    fn call<U: ExprValue>(function: Function, args: Vec<Arc<Node>>) -> Expr<U> {
+        Expr::from_node(Node::new(Op::Call(function, args), U::UNIT))
+    }
+}
+
+impl<T: ExprValue> From<T> for Expr<T> {
+    fn from(value: T) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<T> Display for Expr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.node.fmt(f)
+    }
+}
+
+macro_rules! expr_binary_op {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl<A, B, C> $trait<Expr<B>> for Expr<A>
+        where
+            A: $trait<B, Output = C>,
+            C: ExprValue,
+        {
+            type Output = Expr<C>;
+
+            fn $method(self, rhs: Expr<B>) -> Self::Output {
+                Expr::from_node(Node::new(Op::Binary($op, self.node, rhs.node), C::UNIT))
+            }
+        }
+
+        impl<A, B, C> $trait<B> for Expr<A>
+        where
+            A: $trait<B, Output = C>,
+            B: ExprValue,
+            C: ExprValue,
+        {
+            type Output = Expr<C>;
+
+            fn $method(self, rhs: B) -> Self::Output {
+                Expr::from_node(Node::new(
+                    Op::Binary($op, self.node, Expr::constant(rhs).node),
+                    C::UNIT,
+                ))
+            }
+        }
+    };
+}
+
+expr_binary_op!(Add, add, BinaryOp::Add);
+expr_binary_op!(Sub, sub, BinaryOp::Sub);
+expr_binary_op!(Mul, mul, BinaryOp::Mul);
+expr_binary_op!(Div, div, BinaryOp::Div);
+
+impl<T> Mul<Expr<T>> for Number
+where
+    T: ExprValue + Mul<Number, Output = T>,
+{
+    type Output = Expr<T>;
+
+    fn mul(self, rhs: Expr<T>) -> Self::Output {
+        Expr::from_node(Node::new(
+            Op::Binary(BinaryOp::Mul, Expr::constant(self).node, rhs.node),
+            T::UNIT,
+        ))
+    }
+}
+
+impl<T> Neg for Expr<T>
+where
+    T: ExprValue + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Expr::from_node(Node::new(Op::Neg(self.node), T::UNIT))
+    }
+}
+
+impl<T, U> Sqrt for Expr<T>
+where
+    T: ExprValue + Sqrt<Output = U>,
+    U: ExprValue,
+{
+    type Output = Expr<U>;
+
+    fn sqrt(self) -> Self::Output {
+        Self::call(Function::Sqrt, vec![self.node])
+    }
+}
+
+impl<T> Trig for Expr<T>
+where
+    T: ExprValue + Trig<Output = Number>,
+{
+    type Output = Expr<Number>;
+
+    fn hypot(&self, other: Self) -> Self::Output {
+        Self::call(Function::Hypot, vec![self.node.clone(), other.node])
+    }
+
+    fn sin(&self) -> Self::Output {
+        Self::call(Function::Sin, vec![self.node.clone()])
+    }
+
+    fn cos(&self) -> Self::Output {
+        Self::call(Function::Cos, vec![self.node.clone()])
+    }
+
+    fn tan(&self) -> Self::Output {
+        Self::call(Function::Tan, vec![self.node.clone()])
+    }
+
+    fn sin_cos(&self) -> (Self::Output, Self::Output) {
+        (self.sin(), self.cos())
+    }
+}
+
+impl<T> TrigInv for Expr<T>
+where
+    T: ExprValue + TrigInv<Output = Number>,
+{
+    type Output = Expr<Number>;
+
+    fn asin(&self) -> Self::Output {
+        Self::call(Function::Asin, vec![self.node.clone()])
+    }
+
+    fn acos(&self) -> Self::Output {
+        Self::call(Function::Acos, vec![self.node.clone()])
+    }
+
+    fn atan(&self) -> Self::Output {
+        Self::call(Function::Atan, vec![self.node.clone()])
+    }
+
+    fn atan2(&self, other: Self) -> Self::Output {
+        Self::call(Function::Atan2, vec![self.node.clone(), other.node])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::{num, Trig};
+
+    use crate::*;
+
+    #[test]
+    fn evaluate_with_bindings() {
+        let width = Expr::<Length>::variable("width");
+        let depth = Expr::<Length>::variable("depth");
+        let area = width.clone() * depth.clone();
+        let bindings = Bindings::new()
+            .with("width", qty!(2 m))
+            .with("depth", qty!(3 m));
+        assert_eq!(area.evaluate(&bindings), Ok(qty!(6 m2)));
+        assert_eq!(
+            area.variables().into_iter().collect::<Vec<_>>(),
+            vec!["depth", "width"]
+        );
+
+        let diagonal = (width.clone() * width.clone() + depth.clone() * depth).sqrt();
+        let bindings = Bindings::new()
+            .with("width", qty!(3 m))
+            .with("depth", qty!(4 m));
+        assert_eq!(diagonal.evaluate(&bindings), Ok(qty!(5 m)));
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        let width = Expr::<Length>::variable("width");
+        assert_eq!(
+            width.evaluate(&Bindings::new()),
+            Err(ExprError::UnboundVariable("width".into()))
+        );
+        assert_eq!(
+            width.evaluate(&Bindings::new().with("width", qty!(90 deg))),
+            Err(ExprError::UnitMismatch {
+                variable: "width".into(),
+                expected: "m",
+                found: "rad",
+            })
+        );
+        let ratio = Expr::constant(qty!(1 m)) / width;
+        assert_eq!(
+            ratio.evaluate(&Bindings::new().with("width", qty!(0 m))),
+            Err(ExprError::Number(NumberError::DivideByZero))
+        );
+    }
+
+    #[test]
+    fn display() {
+        let width = Expr::<Length>::variable("width");
+        let leg = Expr::<Length>::variable("leg");
+        assert_eq!((width.clone() - qty!(1 m)).to_string(), "width - 1 m");
+        assert_eq!(
+            (width.clone() - (leg.clone() - qty!(1 m))).to_string(),
+            "width - (leg - 1 m)"
+        );
+        assert_eq!(
+            ((width.clone() + leg.clone()) * num!(2)).to_string(),
+            "(width + leg) * 2"
+        );
+        assert_eq!((-(width.clone() * leg)).to_string(), "-(width * leg)");
+
+        let angle = Expr::<Angle>::variable("angle");
+        assert_eq!((width * angle.sin()).to_string(), "width * sin(angle)");
+    }
+
+    #[test]
+    fn evaluate_on_another_thread() {
+        let beam = Expr::<Length>::variable("width") - qty!(1 m);
+        let bindings = Bindings::new().with("width", qty!(3 m));
+        let length = std::thread::spawn(move || beam.evaluate(&bindings))
+            .join()
+            .unwrap();
+        assert_eq!(length, Ok(qty!(2 m)));
+    }
+
+    #[test]
+    fn simplify() {
+        let width = Expr::<Length>::variable("width");
+        let expr = width.clone() * num!(1) + qty!(0 m);
+        assert_eq!(expr.simplify(), width);
+
+        let folded = width.clone() - (Expr::constant(qty!(1 m)) + qty!(500 mm));
+        assert_eq!(folded.simplify().to_string(), "width - 1.5 m");
+
+        let difference = width.clone() - width.clone();
+        assert_eq!(
+            difference.evaluate(&Bindings::new()),
+            Err(ExprError::UnboundVariable("width".into()))
+        );
+        let cancelled = difference.simplify();
+        assert_eq!(cancelled.to_string(), "0 m");
+        assert!(cancelled.variables().is_empty());
+        assert_eq!(cancelled.evaluate(&Bindings::new()), Ok(qty!(0 m)));
+
+        // Failing constant subexpressions are kept, so the error surfaces on evaluation.
+        let invalid = Expr::constant(qty!(1 m)) / Expr::constant(qty!(0 m));
+        assert_eq!(
+            invalid.simplify().evaluate(&Bindings::new()),
+            Err(ExprError::Number(NumberError::DivideByZero))
+        );
+    }
+}
@@ -0,0 +1,677 @@
+//! Limits of size for mating holes and shafts, from ISO 286 tolerance classes such as
+//! `H7/g6`, and from the ANSI B4.1 running and sliding fits `RC1` to `RC9`.
+//!
+//! A part with a hole for a bolt or dowel can size the hole from the fit it needs, rather
+//! than from a hand-picked allowance:
+//!
+//! ```
+//! use villagekit_unit::{fits::{Fit, IsoFit}, *};
+//!
+//! let fit: IsoFit = "H7/g6".parse().unwrap();
+//! let hole = fit.hole(qty!(10 mm)).unwrap();
+//! assert!(hole.approx_eq(&Interval::new(qty!(10 mm), qty!(10.015 mm))));
+//!
+//! // A dowel driven into a reamed hole never has any clearance.
+//! let press = IsoFit::MEDIUM_DRIVE.clearance(qty!(6 mm)).unwrap();
+//! assert!(press.upper < Length::zero());
+//! ```
+
+use std::{fmt, str::FromStr};
+use villagekit_number::{num, traits::Euclid, Number};
+
+use crate::{Inches, Interval, Length, Millimeters};
+
+/// A fit between a hole and a shaft of the same nominal size.
+pub trait Fit {
+    /// The limits of size of the hole.
+    fn hole(&self, nominal: Length) -> Result<Interval<Length>, FitError>;
+    /// The limits of size of the shaft.
+    fn shaft(&self, nominal: Length) -> Result<Interval<Length>, FitError>;
+
+    /// The clearance between the hole and the shaft, negative for an interference.
+    fn clearance(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        Ok(self.hole(nominal)? - self.shaft(nominal)?)
+    }
+}
+
+/// A fit which can't be computed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitError {
+    /// A nominal size outside the sizes covered by the fit's tables.
+    NominalSize { nominal: Length, max: Length },
+    /// A tolerance class which is not covered by the tables, e.g. `K9`.
+    UnsupportedClass(String),
+    /// A string which is not a fit, e.g. `"H7g6"`.
+    InvalidFit(String),
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::NominalSize { nominal, max } => write!(
+                f,
+                "nominal size {} mm is outside the tables, which cover up to {} mm",
+                nominal.to::<Millimeters>(),
+                max.to::<Millimeters>()
+            ),
+            FitError::UnsupportedClass(class) => {
+                write!(f, "tolerance class `{}` is not supported", class)
+            }
+            FitError::InvalidFit(fit) => write!(f, "invalid fit `{}`", fit),
+        }
+    }
+}
+
+impl std::error::Error for FitError {}
+
+/// An ISO 286 fundamental deviation, which places a tolerance zone relative to the nominal
+/// size: uppercase for holes and lowercase for shafts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Deviation {
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    Js,
+    K,
+    M,
+    N,
+    P,
+    R,
+    S,
+}
+
+impl Deviation {
+    const ALL: [Deviation; 13] = [
+        Deviation::C,
+        Deviation::D,
+        Deviation::E,
+        Deviation::F,
+        Deviation::G,
+        Deviation::H,
+        Deviation::Js,
+        Deviation::K,
+        Deviation::M,
+        Deviation::N,
+        Deviation::P,
+        Deviation::R,
+        Deviation::S,
+    ];
+
+    /// The letters of the deviation for a hole, e.g. `"JS"`.
+    pub const fn hole_letters(&self) -> &'static str {
+        match self {
+            Deviation::C => "C",
+            Deviation::D => "D",
+            Deviation::E => "E",
+            Deviation::F => "F",
+            Deviation::G => "G",
+            Deviation::H => "H",
+            Deviation::Js => "JS",
+            Deviation::K => "K",
+            Deviation::M => "M",
+            Deviation::N => "N",
+            Deviation::P => "P",
+            Deviation::R => "R",
+            Deviation::S => "S",
+        }
+    }
+
+    /// The letters of the deviation for a shaft, e.g. `"js"`.
+    pub const fn shaft_letters(&self) -> &'static str {
+        match self {
+            Deviation::C => "c",
+            Deviation::D => "d",
+            Deviation::E => "e",
+            Deviation::F => "f",
+            Deviation::G => "g",
+            Deviation::H => "h",
+            Deviation::Js => "js",
+            Deviation::K => "k",
+            Deviation::M => "m",
+            Deviation::N => "n",
+            Deviation::P => "p",
+            Deviation::R => "r",
+            Deviation::S => "s",
+        }
+    }
+}
+
+/// An ISO 286 tolerance class: a fundamental deviation and a standard tolerance grade,
+/// e.g. `H7` for a hole or `g6` for a shaft.
+///
+/// The tables cover nominal sizes up to 500 mm and grades IT5 to IT16.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ToleranceClass {
+    pub deviation: Deviation,
+    pub grade: u8,
+}
+
+impl ToleranceClass {
+    pub const fn new(deviation: Deviation, grade: u8) -> Self {
+        Self { deviation, grade }
+    }
+
+    /// The upper and lower deviations of a hole from its nominal size.
+    pub fn hole_deviations(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        let size = IsoSize::new(nominal)?;
+        let tolerance = self.tolerance(&size, self.hole_name())?;
+        let fundamental = |deviation: Deviation| SHAFT_DEVIATIONS[size.row][deviation as usize];
+        // The extra tolerance of a hole a grade wider than the shaft it is paired with.
+        let delta = if size.millimeters <= num!(3) {
+            Number::ZERO
+        } else {
+            tolerance - size.standard_tolerance(self.grade - 1)
+        };
+        let upper = match self.deviation {
+            Deviation::Js => return Ok(js(tolerance, self.grade)),
+            // The lower deviation mirrors the upper deviation of the shaft.
+            Deviation::C | Deviation::D | Deviation::E | Deviation::F | Deviation::G => {
+                return Ok(micrometers(-fundamental(self.deviation), tolerance))
+            }
+            Deviation::H => return Ok(micrometers(Number::ZERO, tolerance)),
+            Deviation::K if self.grade <= 8 => -fundamental(Deviation::K) + delta,
+            Deviation::K => return Err(FitError::UnsupportedClass(self.hole_name())),
+            Deviation::M | Deviation::N if self.grade <= 8 => -fundamental(self.deviation) + delta,
+            Deviation::N if size.millimeters > num!(3) => Number::ZERO,
+            Deviation::P | Deviation::R | Deviation::S if self.grade <= 7 => {
+                -fundamental(self.deviation) + delta
+            }
+            deviation => -fundamental(deviation),
+        };
+        Ok(micrometers(upper - tolerance, tolerance))
+    }
+
+    /// The upper and lower deviations of a shaft from its nominal size.
+    pub fn shaft_deviations(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        let size = IsoSize::new(nominal)?;
+        let tolerance = self.tolerance(&size, self.shaft_name())?;
+        let fundamental = SHAFT_DEVIATIONS[size.row][self.deviation as usize];
+        Ok(match self.deviation {
+            Deviation::Js => js(tolerance, self.grade),
+            Deviation::C | Deviation::D | Deviation::E | Deviation::F | Deviation::G => {
+                micrometers(fundamental - tolerance, tolerance)
+            }
+            Deviation::H => micrometers(-tolerance, tolerance),
+            // The lower deviation of `k` is only above zero for grades IT4 to IT7.
+            Deviation::K if self.grade > 7 => micrometers(Number::ZERO, tolerance),
+            _ => micrometers(fundamental, tolerance),
+        })
+    }
+
+    /// The limits of size of a hole, e.g. 10 mm `H7` is 10 mm to 10.015 mm.
+    pub fn hole_limits(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        Ok(self.hole_deviations(nominal)? + Interval::point(nominal))
+    }
+
+    /// The limits of size of a shaft, e.g. 10 mm `g6` is 9.986 mm to 9.995 mm.
+    pub fn shaft_limits(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        Ok(self.shaft_deviations(nominal)? + Interval::point(nominal))
+    }
+
+    fn tolerance(&self, size: &IsoSize, name: String) -> Result<Number, FitError> {
+        if !(5..=16).contains(&self.grade) {
+            return Err(FitError::UnsupportedClass(name));
+        }
+        Ok(size.standard_tolerance(self.grade))
+    }
+
+    fn hole_name(&self) -> String {
+        format!("{}{}", self.deviation.hole_letters(), self.grade)
+    }
+
+    fn shaft_name(&self) -> String {
+        format!("{}{}", self.deviation.shaft_letters(), self.grade)
+    }
+}
+
+/// An ISO 286 fit of a hole class with a shaft class, e.g. `H7/g6`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IsoFit {
+    pub hole: ToleranceClass,
+    pub shaft: ToleranceClass,
+}
+
+impl IsoFit {
+    /// `H11/c11`, for wide commercial tolerances or allowances on external members.
+    pub const LOOSE_RUNNING: Self = Self::hole_basis(Deviation::C, 11);
+    /// `H9/d9`, for large temperature variations, high running speeds or heavy journal
+    /// pressures, and for bolt clearance holes.
+    pub const FREE_RUNNING: Self = Self::hole_basis_with(9, Deviation::D, 9);
+    /// `H8/f7`, for running on accurate machines and accurate location at moderate speeds.
+    pub const CLOSE_RUNNING: Self = Self::hole_basis_with(8, Deviation::F, 7);
+    /// `H7/g6`, for parts which move and turn freely and locate accurately.
+    pub const SLIDING: Self = Self::hole_basis_with(7, Deviation::G, 6);
+    /// `H7/h6`, for stationary parts which can be freely assembled and disassembled.
+    pub const LOCATIONAL_CLEARANCE: Self = Self::hole_basis_with(7, Deviation::H, 6);
+    /// `H7/k6`, for accurate location, a compromise between clearance and interference.
+    pub const LOCATIONAL_TRANSITION: Self = Self::hole_basis_with(7, Deviation::K, 6);
+    /// `H7/p6`, for parts requiring rigidity and alignment, e.g. press-fit dowels.
+    pub const LOCATIONAL_INTERFERENCE: Self = Self::hole_basis_with(7, Deviation::P, 6);
+    /// `H7/s6`, for ordinary steel parts or shrink fits on light sections.
+    pub const MEDIUM_DRIVE: Self = Self::hole_basis_with(7, Deviation::S, 6);
+
+    pub const fn new(hole: ToleranceClass, shaft: ToleranceClass) -> Self {
+        Self { hole, shaft }
+    }
+
+    /// A hole basis fit of the same grade for the hole and shaft, e.g. `H11/c11`.
+    const fn hole_basis(shaft: Deviation, grade: u8) -> Self {
+        Self::hole_basis_with(grade, shaft, grade)
+    }
+
+    const fn hole_basis_with(hole_grade: u8, shaft: Deviation, shaft_grade: u8) -> Self {
+        Self::new(
+            ToleranceClass::new(Deviation::H, hole_grade),
+            ToleranceClass::new(shaft, shaft_grade),
+        )
+    }
+}
+
+impl Fit for IsoFit {
+    fn hole(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        self.hole.hole_limits(nominal)
+    }
+
+    fn shaft(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        self.shaft.shaft_limits(nominal)
+    }
+}
+
+impl fmt::Display for IsoFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.hole.hole_name(), self.shaft.shaft_name())
+    }
+}
+
+impl FromStr for IsoFit {
+    type Err = FitError;
+
+    /// Parses a fit such as `"H7/g6"`, with the hole class in uppercase and the shaft class
+    /// in lowercase.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || FitError::InvalidFit(s.to_string());
+        let (hole, shaft) = s.trim().split_once('/').ok_or_else(invalid)?;
+        let parse = |class: &str, letters: fn(&Deviation) -> &'static str| {
+            let class = class.trim();
+            let split = class
+                .find(|c: char| c.is_ascii_digit())
+                .ok_or_else(invalid)?;
+            let deviation = Deviation::ALL
+                .into_iter()
+                .find(|deviation| letters(deviation) == &class[..split])
+                .ok_or_else(invalid)?;
+            let grade = class[split..].parse().map_err(|_| invalid())?;
+            Ok(ToleranceClass::new(deviation, grade))
+        };
+        Ok(Self::new(
+            parse(hole, Deviation::hole_letters)?,
+            parse(shaft, Deviation::shaft_letters)?,
+        ))
+    }
+}
+
+/// An ANSI B4.1 running and sliding fit, from `RC1` for close sliding fits of accurate
+/// parts to `RC9` for loose running fits of commercial stock.
+///
+/// The tables cover nominal sizes up to 12.41 in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RunningFit {
+    RC1,
+    RC2,
+    RC3,
+    RC4,
+    RC5,
+    RC6,
+    RC7,
+    RC8,
+    RC9,
+}
+
+impl RunningFit {
+    /// The upper limit of the hole, and upper and lower limits of the shaft, in thousandths
+    /// of an inch.
+    fn limits(&self, nominal: Length) -> Result<[Number; 3], FitError> {
+        let size = nominal.to::<Inches>();
+        let row = RUNNING_FIT_SIZES
+            .iter()
+            .position(|upper| size <= *upper)
+            .filter(|_| size > Number::ZERO)
+            .ok_or_else(|| FitError::NominalSize {
+                nominal,
+                max: Length::from_scalar::<Inches>(num!(12.41)),
+            })?;
+        Ok(RUNNING_FITS[row][*self as usize])
+    }
+}
+
+impl Fit for RunningFit {
+    fn hole(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        let [upper, _, _] = self.limits(nominal)?;
+        Ok(Interval::new(nominal, nominal + thousandths(upper)))
+    }
+
+    fn shaft(&self, nominal: Length) -> Result<Interval<Length>, FitError> {
+        let [_, upper, lower] = self.limits(nominal)?;
+        Ok(Interval::new(
+            nominal + thousandths(lower),
+            nominal + thousandths(upper),
+        ))
+    }
+}
+
+impl fmt::Display for RunningFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RC{}", *self as usize + 1)
+    }
+}
+
+/// A nominal size within the ISO 286 tables.
+struct IsoSize {
+    millimeters: Number,
+    /// The row of the size in the tables of fundamental deviations.
+    row: usize,
+    /// The row of the size in the table of standard tolerances.
+    main_row: usize,
+}
+
+impl IsoSize {
+    fn new(nominal: Length) -> Result<Self, FitError> {
+        let millimeters = nominal.to::<Millimeters>();
+        let row = |sizes: &[Number]| {
+            sizes
+                .iter()
+                .position(|upper| millimeters <= *upper)
+                .filter(|_| millimeters > Number::ZERO)
+                .ok_or_else(|| FitError::NominalSize {
+                    nominal,
+                    max: Length::from_scalar::<Millimeters>(num!(500)),
+                })
+        };
+        Ok(Self {
+            millimeters,
+            row: row(&ISO_SIZES)?,
+            main_row: row(&ISO_MAIN_SIZES)?,
+        })
+    }
+
+    /// The standard tolerance of a grade, in micrometers.
+    fn standard_tolerance(&self, grade: u8) -> Number {
+        STANDARD_TOLERANCES[self.main_row][grade as usize - 4]
+    }
+}
+
+/// The tolerance zone from a lower deviation in micrometers.
+fn micrometers(lower: Number, tolerance: Number) -> Interval<Length> {
+    let micrometer = Length::from_scalar::<Millimeters>(num!(0.001));
+    Interval::new(micrometer * lower, micrometer * (lower + tolerance))
+}
+
+/// A length in thousandths of an inch.
+fn thousandths(value: Number) -> Length {
+    Length::from_scalar::<Inches>(value) / num!(1000)
+}
+
+/// The symmetric tolerance zone of `js` and `JS`, which for grades IT7 to IT11 is rounded
+/// down to a whole micrometer either side.
+fn js(tolerance: Number, grade: u8) -> Interval<Length> {
+    let tolerance = if (7..=11).contains(&grade) {
+        tolerance - tolerance.rem_euclid(num!(2))
+    } else {
+        tolerance
+    };
+    micrometers(-tolerance * Number::HALF, tolerance)
+}
+
+/// An array of numbers, for the tables below.
+macro_rules! numbers {
+    ($($value:literal),* $(,)?) => {
+        [$(num!($value)),*]
+    };
+}
+
+/// The upper limits in millimeters of the nominal size ranges of the fundamental deviations.
+const ISO_SIZES: [Number; 23] = numbers![
+    3, 6, 10, 18, 30, 40, 50, 65, 80, 100, 120, 140, 160, 180, 200, 225, 250, 280, 315, 355, 400,
+    450, 500,
+];
+
+/// The upper limits in millimeters of the nominal size ranges of the standard tolerances.
+const ISO_MAIN_SIZES: [Number; 13] =
+    numbers![3, 6, 10, 18, 30, 50, 80, 120, 180, 250, 315, 400, 500];
+
+/// The standard tolerances in micrometers of grades IT4 to IT16 (ISO 286-1, table 1).
+#[rustfmt::skip]
+const STANDARD_TOLERANCES: [[Number; 13]; 13] = [
+    numbers![ 3,  4,  6, 10, 14,  25,  40,  60, 100, 140,  250,  400,  600],
+    numbers![ 4,  5,  8, 12, 18,  30,  48,  75, 120, 180,  300,  480,  750],
+    numbers![ 4,  6,  9, 15, 22,  36,  58,  90, 150, 220,  360,  580,  900],
+    numbers![ 5,  8, 11, 18, 27,  43,  70, 110, 180, 270,  430,  700, 1100],
+    numbers![ 6,  9, 13, 21, 33,  52,  84, 130, 210, 330,  520,  840, 1300],
+    numbers![ 7, 11, 16, 25, 39,  62, 100, 160, 250, 390,  620, 1000, 1600],
+    numbers![ 8, 13, 19, 30, 46,  74, 120, 190, 300, 460,  740, 1200, 1900],
+    numbers![10, 15, 22, 35, 54,  87, 140, 220, 350, 540,  870, 1400, 2200],
+    numbers![12, 18, 25, 40, 63, 100, 160, 250, 400, 630, 1000, 1600, 2500],
+    numbers![14, 20, 29, 46, 72, 115, 185, 290, 460, 720, 1150, 1850, 2900],
+    numbers![16, 23, 32, 52, 81, 130, 210, 320, 520, 810, 1300, 2100, 3200],
+    numbers![18, 25, 36, 57, 89, 140, 230, 360, 570, 890, 1400, 2300, 3600],
+    numbers![20, 27, 40, 63, 97, 155, 250, 400, 630, 970, 1550, 2500, 4000],
+];
+
+/// The fundamental deviations of shafts in micrometers, in the order of [`Deviation`]: the
+/// upper deviations of `c` to `h` and the lower deviations of `k` to `s`, with `k` for
+/// grades IT4 to IT7 (ISO 286-1, tables 2 and 3). `js` has none.
+#[rustfmt::skip]
+const SHAFT_DEVIATIONS: [[Number; 13]; 23] = [
+    numbers![ -60,  -20,  -14,  -6,  -2, 0, 0, 0,  2,  4,  6,  10,  14],
+    numbers![ -70,  -30,  -20, -10,  -4, 0, 0, 1,  4,  8, 12,  15,  19],
+    numbers![ -80,  -40,  -25, -13,  -5, 0, 0, 1,  6, 10, 15,  19,  23],
+    numbers![ -95,  -50,  -32, -16,  -6, 0, 0, 1,  7, 12, 18,  23,  28],
+    numbers![-110,  -65,  -40, -20,  -7, 0, 0, 2,  8, 15, 22,  28,  35],
+    numbers![-120,  -80,  -50, -25,  -9, 0, 0, 2,  9, 17, 26,  34,  43],
+    numbers![-130,  -80,  -50, -25,  -9, 0, 0, 2,  9, 17, 26,  34,  43],
+    numbers![-140, -100,  -60, -30, -10, 0, 0, 2, 11, 20, 32,  41,  53],
+    numbers![-150, -100,  -60, -30, -10, 0, 0, 2, 11, 20, 32,  43,  59],
+    numbers![-170, -120,  -72, -36, -12, 0, 0, 3, 13, 23, 37,  51,  71],
+    numbers![-180, -120,  -72, -36, -12, 0, 0, 3, 13, 23, 37,  54,  79],
+    numbers![-200, -145,  -85, -43, -14, 0, 0, 3, 15, 27, 43,  63,  92],
+    numbers![-210, -145,  -85, -43, -14, 0, 0, 3, 15, 27, 43,  65, 100],
+    numbers![-230, -145,  -85, -43, -14, 0, 0, 3, 15, 27, 43,  68, 108],
+    numbers![-240, -170, -100, -50, -15, 0, 0, 4, 17, 31, 50,  77, 122],
+    numbers![-260, -170, -100, -50, -15, 0, 0, 4, 17, 31, 50,  80, 130],
+    numbers![-280, -170, -100, -50, -15, 0, 0, 4, 17, 31, 50,  84, 140],
+    numbers![-300, -190, -110, -56, -17, 0, 0, 4, 20, 34, 56,  94, 158],
+    numbers![-330, -190, -110, -56, -17, 0, 0, 4, 20, 34, 56,  98, 170],
+    numbers![-360, -210, -125, -62, -18, 0, 0, 4, 21, 37, 62, 108, 190],
+    numbers![-400, -210, -125, -62, -18, 0, 0, 4, 21, 37, 62, 114, 208],
+    numbers![-440, -230, -135, -68, -20, 0, 0, 5, 23, 40, 68, 126, 232],
+    numbers![-480, -230, -135, -68, -20, 0, 0, 5, 23, 40, 68, 132, 252],
+];
+
+/// The upper limits in inches of the nominal size ranges of the running and sliding fits.
+const RUNNING_FIT_SIZES: [Number; 11] =
+    numbers![0.12, 0.24, 0.4, 0.71, 1.19, 1.97, 3.15, 4.73, 7.09, 9.85, 12.41];
+
+/// The limits of the running and sliding fits in thousandths of an inch, as the upper limit
+/// of the hole and the upper and lower limits of the shaft (ANSI B4.1, table 1).
+#[rustfmt::skip]
+const RUNNING_FITS: [[[Number; 3]; 9]; 11] = [
+    [
+        numbers![0.2, -0.1, -0.25], numbers![0.25, -0.1, -0.3], numbers![0.4, -0.3, -0.55],
+        numbers![0.6, -0.3, -0.7], numbers![0.6, -0.6, -1.0], numbers![1.0, -0.6, -1.2],
+        numbers![1.0, -1.0, -1.6], numbers![1.6, -2.5, -3.5], numbers![2.5, -4.0, -5.6],
+    ],
+    [
+        numbers![0.2, -0.15, -0.3], numbers![0.3, -0.15, -0.35], numbers![0.5, -0.4, -0.7],
+        numbers![0.7, -0.4, -0.9], numbers![0.7, -0.8, -1.3], numbers![1.2, -0.8, -1.5],
+        numbers![1.2, -1.2, -1.9], numbers![1.8, -2.8, -4.0], numbers![3.0, -4.5, -6.0],
+    ],
+    [
+        numbers![0.25, -0.2, -0.35], numbers![0.4, -0.2, -0.45], numbers![0.6, -0.5, -0.9],
+        numbers![0.9, -0.5, -1.1], numbers![0.9, -1.0, -1.6], numbers![1.4, -1.0, -1.9],
+        numbers![1.4, -1.6, -2.5], numbers![2.2, -3.0, -4.4], numbers![3.5, -5.0, -7.2],
+    ],
+    [
+        numbers![0.3, -0.25, -0.45], numbers![0.4, -0.25, -0.55], numbers![0.7, -0.6, -1.0],
+        numbers![1.0, -0.6, -1.3], numbers![1.0, -1.2, -1.9], numbers![1.6, -1.2, -2.2],
+        numbers![1.6, -2.0, -3.0], numbers![2.8, -3.5, -5.1], numbers![4.0, -6.0, -8.8],
+    ],
+    [
+        numbers![0.4, -0.3, -0.55], numbers![0.5, -0.3, -0.7], numbers![0.8, -0.8, -1.3],
+        numbers![1.2, -0.8, -1.6], numbers![1.2, -1.6, -2.4], numbers![2.0, -1.6, -2.8],
+        numbers![2.0, -2.5, -3.7], numbers![3.5, -4.5, -6.5], numbers![5.0, -7.0, -10.5],
+    ],
+    [
+        numbers![0.4, -0.4, -0.7], numbers![0.6, -0.4, -0.8], numbers![1.0, -1.0, -1.6],
+        numbers![1.6, -1.0, -2.0], numbers![1.6, -2.0, -3.0], numbers![2.5, -2.0, -3.6],
+        numbers![2.5, -3.0, -4.6], numbers![4.0, -5.0, -7.5], numbers![6.0, -8.0, -12.0],
+    ],
+    [
+        numbers![0.5, -0.4, -0.7], numbers![0.7, -0.4, -0.9], numbers![1.2, -1.2, -1.9],
+        numbers![1.8, -1.2, -2.4], numbers![1.8, -2.5, -3.7], numbers![3.0, -2.5, -4.3],
+        numbers![3.0, -4.0, -5.8], numbers![4.5, -6.0, -9.0], numbers![7.0, -9.0, -13.5],
+    ],
+    [
+        numbers![0.6, -0.5, -0.9], numbers![0.9, -0.5, -1.1], numbers![1.4, -1.4, -2.3],
+        numbers![2.2, -1.4, -2.8], numbers![2.2, -3.0, -4.4], numbers![3.5, -3.0, -5.2],
+        numbers![3.5, -5.0, -7.2], numbers![5.0, -7.0, -10.5], numbers![9.0, -10.0, -15.0],
+    ],
+    [
+        numbers![0.7, -0.6, -1.1], numbers![1.0, -0.6, -1.3], numbers![1.6, -1.6, -2.6],
+        numbers![2.5, -1.6, -3.2], numbers![2.5, -3.5, -5.1], numbers![4.0, -3.5, -6.0],
+        numbers![4.0, -6.0, -8.5], numbers![6.0, -8.0, -12.0], numbers![10.0, -12.0, -18.0],
+    ],
+    [
+        numbers![0.8, -0.6, -1.2], numbers![1.2, -0.6, -1.4], numbers![1.8, -2.0, -3.2],
+        numbers![2.8, -2.0, -3.8], numbers![2.8, -4.0, -5.8], numbers![4.5, -4.0, -6.8],
+        numbers![4.5, -7.0, -9.8], numbers![7.0, -10.0, -14.5], numbers![12.0, -15.0, -22.0],
+    ],
+    [
+        numbers![0.9, -0.8, -1.4], numbers![1.2, -0.8, -1.7], numbers![2.0, -2.5, -3.7],
+        numbers![3.0, -2.5, -4.5], numbers![3.0, -5.0, -7.0], numbers![5.0, -5.0, -8.0],
+        numbers![5.0, -8.0, -11.0], numbers![8.0, -12.0, -17.0], numbers![12.0, -18.0, -26.0],
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::traits::ApproxEq;
+
+    use super::*;
+    use crate::qty;
+
+    /// Asserts the deviations in micrometers of a hole or shaft.
+    fn assert_deviations(
+        deviations: Result<Interval<Length>, FitError>,
+        upper: Number,
+        lower: Number,
+    ) {
+        let deviations = deviations.unwrap();
+        let expected = micrometers(lower, upper - lower);
+        assert!(
+            deviations.approx_eq(&expected),
+            "{:?} != {:?}",
+            deviations,
+            expected
+        );
+    }
+
+    fn class(deviation: Deviation, grade: u8) -> ToleranceClass {
+        ToleranceClass::new(deviation, grade)
+    }
+
+    #[test]
+    fn shaft_deviations() {
+        // Published values of ISO 286-2 at a nominal size of 25 mm.
+        let size = qty!(25 mm);
+        let shaft = |deviation, grade| class(deviation, grade).shaft_deviations(size);
+        assert_deviations(shaft(Deviation::C, 11), num!(-110), num!(-240));
+        assert_deviations(shaft(Deviation::D, 9), num!(-65), num!(-117));
+        assert_deviations(shaft(Deviation::F, 7), num!(-20), num!(-41));
+        assert_deviations(shaft(Deviation::G, 6), num!(-7), num!(-20));
+        assert_deviations(shaft(Deviation::H, 6), num!(0), num!(-13));
+        assert_deviations(shaft(Deviation::Js, 6), num!(6.5), num!(-6.5));
+        assert_deviations(shaft(Deviation::Js, 7), num!(10), num!(-10));
+        assert_deviations(shaft(Deviation::K, 6), num!(15), num!(2));
+        assert_deviations(shaft(Deviation::K, 9), num!(52), num!(0));
+        assert_deviations(shaft(Deviation::N, 6), num!(28), num!(15));
+        assert_deviations(shaft(Deviation::P, 6), num!(35), num!(22));
+        assert_deviations(shaft(Deviation::S, 6), num!(48), num!(35));
+
+        let shaft = |deviation, grade| class(deviation, grade).shaft_deviations(qty!(70 mm));
+        assert_deviations(shaft(Deviation::R, 6), num!(62), num!(43));
+        assert_deviations(shaft(Deviation::C, 11), num!(-150), num!(-340));
+    }
+
+    #[test]
+    fn hole_deviations() {
+        let size = qty!(25 mm);
+        let hole = |deviation, grade| class(deviation, grade).hole_deviations(size);
+        assert_deviations(hole(Deviation::H, 7), num!(21), num!(0));
+        assert_deviations(hole(Deviation::F, 8), num!(53), num!(20));
+        assert_deviations(hole(Deviation::Js, 7), num!(10), num!(-10));
+        assert_deviations(hole(Deviation::K, 7), num!(6), num!(-15));
+        assert_deviations(hole(Deviation::K, 8), num!(10), num!(-23));
+        assert_deviations(hole(Deviation::M, 7), num!(0), num!(-21));
+        assert_deviations(hole(Deviation::M, 8), num!(4), num!(-29));
+        assert_deviations(hole(Deviation::N, 7), num!(-7), num!(-28));
+        assert_deviations(hole(Deviation::N, 9), num!(0), num!(-52));
+        assert_deviations(hole(Deviation::P, 7), num!(-14), num!(-35));
+        assert_deviations(hole(Deviation::S, 7), num!(-27), num!(-48));
+
+        assert_deviations(
+            class(Deviation::R, 7).hole_deviations(qty!(70 mm)),
+            num!(-32),
+            num!(-62),
+        );
+        // Below 3 mm there is no extra tolerance for the wider hole.
+        let hole = |deviation, grade| class(deviation, grade).hole_deviations(qty!(2 mm));
+        assert_deviations(hole(Deviation::K, 7), num!(0), num!(-10));
+        assert_deviations(hole(Deviation::N, 7), num!(-4), num!(-14));
+        assert_deviations(hole(Deviation::P, 7), num!(-6), num!(-16));
+    }
+
+    #[test]
+    fn iso_fits() {
+        let fit: IsoFit = "H7/g6".parse().unwrap();
+        assert_eq!(fit, IsoFit::SLIDING);
+        assert_eq!(fit.to_string(), "H7/g6");
+        let clearance = fit.clearance(qty!(10 mm)).unwrap();
+        assert!(clearance.approx_eq(&Interval::new(qty!(0.005 mm), qty!(0.029 mm))));
+
+        let dowel = IsoFit::LOCATIONAL_INTERFERENCE
+            .clearance(qty!(50 mm))
+            .unwrap();
+        assert!(dowel.approx_eq(&Interval::new(qty!(-0.042 mm), qty!(-0.001 mm))));
+
+        assert_eq!("JS7/js6".parse::<IsoFit>().unwrap().to_string(), "JS7/js6");
+        assert!(matches!(
+            "h7/G6".parse::<IsoFit>(),
+            Err(FitError::InvalidFit(_))
+        ));
+        assert_eq!(
+            class(Deviation::K, 9).hole_deviations(qty!(25 mm)),
+            Err(FitError::UnsupportedClass("K9".to_string()))
+        );
+        assert!(matches!(
+            IsoFit::SLIDING.hole(qty!(600 mm)),
+            Err(FitError::NominalSize { .. })
+        ));
+    }
+
+    #[test]
+    fn running_fits() {
+        // Published limits of ANSI B4.1 at a nominal size of 1 in.
+        let size = qty!(1 in);
+        let clearance = RunningFit::RC4.clearance(size).unwrap();
+        assert!(clearance.approx_eq(&Interval::new(qty!(0.0008 in), qty!(0.0028 in))));
+        let shaft = RunningFit::RC8.shaft(size).unwrap();
+        assert!(shaft.approx_eq(&Interval::new(qty!(0.9935 in), qty!(0.9955 in))));
+        let hole = RunningFit::RC1.hole(qty!(0.1 in)).unwrap();
+        assert!(hole.approx_eq(&Interval::new(qty!(0.1 in), qty!(0.1002 in))));
+        assert_eq!(RunningFit::RC9.to_string(), "RC9");
+        assert!(RunningFit::RC1.hole(qty!(13 in)).is_err());
+    }
+}
@@ -0,0 +1,563 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use villagekit_number::{
+    num,
+    traits::{Abs, Log, Pow, Round},
+    Number,
+};
+
+use crate::{
+    parse::{lookup, UnitEntry},
+    Acceleration, Angle, Area, Density, Dimension, Energy, Force, Length, LinearVelocity, Mass,
+    Money, MoneyPerLength, Pressure, Temperature, TemperatureDelta, Time, UnitRegistry, Volume,
+};
+
+/// The units quantities are shown in.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnitSystem {
+    /// Millimeters, square meters, liters and degrees.
+    #[default]
+    Metric,
+    /// Feet and fractional inches, square feet, gallons and degrees.
+    Imperial,
+    /// A chosen unit for each dimension.
+    Custom(CustomUnits),
+}
+
+/// The unit symbols, as accepted by [`qty!`], to show each dimension in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomUnits {
+    pub length: String,
+    pub area: String,
+    pub volume: String,
+    pub angle: String,
+}
+
+/// How many digits of a decimal value to show. Trailing zeros are never shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Precision {
+    /// Digits after the decimal point.
+    Decimals(u8),
+    /// Significant digits.
+    Significant(u8),
+}
+
+/// Whether to show units as symbols (`mm`) or names (`millimeters`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnitStyle {
+    #[default]
+    Symbol,
+    Name,
+}
+
+/// A preference for showing quantities, shared by everything which displays
+/// them to users.
+///
+/// ```
+/// use villagekit_unit::*;
+///
+/// let imperial = QuantityFormat::new(UnitSystem::Imperial);
+/// assert_eq!(imperial.format(qty!(15.625 in)), "1' 3-5/8\"");
+/// assert_eq!(QuantityFormat::default().format(qty!(0.412 m)), "412 mm");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuantityFormat {
+    pub system: UnitSystem,
+    pub precision: Precision,
+    /// Imperial lengths are rounded to this fraction of an inch, e.g. 16 for 1/16".
+    pub fraction_denominator: u32,
+    pub unit_style: UnitStyle,
+    /// Runtime units which custom unit systems can use.
+    pub units: UnitRegistry,
+}
+
+impl Default for QuantityFormat {
+    fn default() -> Self {
+        Self::new(UnitSystem::Metric)
+    }
+}
+
+impl QuantityFormat {
+    pub fn new(system: UnitSystem) -> Self {
+        Self {
+            system,
+            precision: Precision::Decimals(3),
+            fraction_denominator: 16,
+            unit_style: UnitStyle::Symbol,
+            units: UnitRegistry::new(),
+        }
+    }
+
+    pub fn format<T: FormatQuantity>(&self, value: T) -> String {
+        value.format_with(self)
+    }
+
+    fn round(&self, value: Number) -> Number {
+        let decimals = match self.precision {
+            Precision::Decimals(decimals) => decimals as i32,
+            Precision::Significant(digits) => {
+                if value.is_zero() {
+                    return Number::ZERO;
+                }
+                let magnitude = f32::from(value.abs().log10().floor()) as i32;
+                digits.max(1) as i32 - 1 - magnitude
+            }
+        };
+        let scale = num!(10).powi(decimals);
+        let rounded = (value * scale).round() / scale;
+        if rounded.is_zero() {
+            Number::ZERO
+        } else {
+            rounded.normalize()
+        }
+    }
+
+    /// Formats a canonical value in the given unit.
+    fn format_in(&self, canonical: Number, unit: UnitEntry) -> String {
+        let value = self.round(canonical / unit.coefficient - unit.constant);
+        let name = self.unit_name(unit, value.abs() == Number::ONE);
+        if name == "°" {
+            format!("{}{}", value, name)
+        } else {
+            format!("{} {}", value, name)
+        }
+    }
+
+    fn unit_name(&self, unit: UnitEntry, singular: bool) -> String {
+        if let Some(name) = unit.name {
+            return match self.unit_style {
+                UnitStyle::Symbol => unit.symbol.into(),
+                UnitStyle::Name if singular => name.into(),
+                UnitStyle::Name => format!("{}s", name),
+            };
+        }
+        let (_, display, one, many) = UNIT_NAMES
+            .iter()
+            .find(|(symbol, ..)| *symbol == unit.symbol)
+            .copied()
+            .unwrap_or((unit.symbol, unit.symbol, unit.symbol, unit.symbol));
+        match self.unit_style {
+            UnitStyle::Symbol => display,
+            UnitStyle::Name if singular => one,
+            UnitStyle::Name => many,
+        }
+        .into()
+    }
+
+    /// Formats a length as feet and inches, rounded to a fraction of an inch, e.g. `1' 3-5/8"`.
+    fn format_feet_inches(&self, length: Length) -> String {
+        let denominator = self.fraction_denominator.max(1);
+        let steps = (length.to::<crate::Inches>() * Number::from(denominator))
+            .round()
+            .abs();
+        let steps_per_foot = Number::from(12 * denominator);
+        let feet = (steps / steps_per_foot).floor();
+        let rest = f32::from(steps - feet * steps_per_foot) as u32;
+        let (inches, mut numerator, mut denominator) =
+            (rest / denominator, rest % denominator, denominator);
+        let divisor = gcd(numerator, denominator);
+        numerator /= divisor;
+        denominator /= divisor;
+
+        let mut inches_text = match (inches, numerator) {
+            (0, 0) => "0".to_string(),
+            (inches, 0) => inches.to_string(),
+            (0, numerator) => format!("{}/{}", numerator, denominator),
+            (inches, numerator) => format!("{}-{}/{}", inches, numerator, denominator),
+        };
+        let sign = if length < Length::default() && !steps.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        let singular_inch = inches == 1 && numerator == 0;
+        match self.unit_style {
+            UnitStyle::Symbol if feet.is_zero() => format!("{}{}\"", sign, inches_text),
+            UnitStyle::Symbol => format!("{}{}' {}\"", sign, feet, inches_text),
+            UnitStyle::Name => {
+                let inch = lookup("in", None).expect("inches are a unit");
+                inches_text = format!("{} {}", inches_text, self.unit_name(inch, singular_inch));
+                if feet.is_zero() {
+                    format!("{}{}", sign, inches_text)
+                } else {
+                    let foot = lookup("ft", None).expect("feet are a unit");
+                    let feet_name = self.unit_name(foot, feet == Number::ONE);
+                    format!("{}{} {} {}", sign, feet, feet_name, inches_text)
+                }
+            }
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a.max(1)
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Display symbols and singular and plural names of units, by their [`qty!`] symbol.
+const UNIT_NAMES: &[(&str, &str, &str, &str)] = &[
+    ("m", "m", "meter", "meters"),
+    ("cm", "cm", "centimeter", "centimeters"),
+    ("mm", "mm", "millimeter", "millimeters"),
+    ("km", "km", "kilometer", "kilometers"),
+    ("in", "in", "inch", "inches"),
+    ("ft", "ft", "foot", "feet"),
+    ("yd", "yd", "yard", "yards"),
+    ("mi", "mi", "mile", "miles"),
+    ("nmi", "nmi", "nautical mile", "nautical miles"),
+    ("m2", "m²", "square meter", "square meters"),
+    ("cm2", "cm²", "square centimeter", "square centimeters"),
+    ("mm2", "mm²", "square millimeter", "square millimeters"),
+    ("km2", "km²", "square kilometer", "square kilometers"),
+    ("in2", "in²", "square inch", "square inches"),
+    ("ft2", "ft²", "square foot", "square feet"),
+    ("yd2", "yd²", "square yard", "square yards"),
+    ("ac", "ac", "acre", "acres"),
+    ("m3", "m³", "cubic meter", "cubic meters"),
+    ("cm3", "cm³", "cubic centimeter", "cubic centimeters"),
+    ("mm3", "mm³", "cubic millimeter", "cubic millimeters"),
+    ("km3", "km³", "cubic kilometer", "cubic kilometers"),
+    ("ml", "ml", "milliliter", "milliliters"),
+    ("l", "l", "liter", "liters"),
+    ("in3", "in³", "cubic inch", "cubic inches"),
+    ("ft3", "ft³", "cubic foot", "cubic feet"),
+    ("yd3", "yd³", "cubic yard", "cubic yards"),
+    ("floz", "fl oz", "fluid ounce", "fluid ounces"),
+    ("pt", "pt", "pint", "pints"),
+    ("qt", "qt", "quart", "quarts"),
+    ("gal", "gal", "gallon", "gallons"),
+    ("rad", "rad", "radian", "radians"),
+    ("rot", "rot", "rotation", "rotations"),
+    ("deg", "°", "degree", "degrees"),
+    ("grad", "grad", "gradian", "gradians"),
+    ("kg", "kg", "kilogram", "kilograms"),
+    ("g", "g", "gram", "grams"),
+    ("t", "t", "tonne", "tonnes"),
+    ("lb", "lb", "pound", "pounds"),
+    ("oz", "oz", "ounce", "ounces"),
+    (
+        "kg_per_m3",
+        "kg/m³",
+        "kilogram per cubic meter",
+        "kilograms per cubic meter",
+    ),
+    (
+        "g_per_cm3",
+        "g/cm³",
+        "gram per cubic centimeter",
+        "grams per cubic centimeter",
+    ),
+    (
+        "lb_per_ft3",
+        "lb/ft³",
+        "pound per cubic foot",
+        "pounds per cubic foot",
+    ),
+    ("s", "s", "second", "seconds"),
+    ("ms", "ms", "millisecond", "milliseconds"),
+    ("min", "min", "minute", "minutes"),
+    ("h", "h", "hour", "hours"),
+    ("d", "d", "day", "days"),
+    ("m_per_s", "m/s", "meter per second", "meters per second"),
+    (
+        "km_per_h",
+        "km/h",
+        "kilometer per hour",
+        "kilometers per hour",
+    ),
+    ("mph", "mph", "mile per hour", "miles per hour"),
+    (
+        "m_per_s2",
+        "m/s²",
+        "meter per second squared",
+        "meters per second squared",
+    ),
+    ("gn", "g₀", "standard gravity", "standard gravities"),
+    ("N", "N", "newton", "newtons"),
+    ("kN", "kN", "kilonewton", "kilonewtons"),
+    ("lbf", "lbf", "pound-force", "pounds-force"),
+    ("Pa", "Pa", "pascal", "pascals"),
+    ("kPa", "kPa", "kilopascal", "kilopascals"),
+    ("MPa", "MPa", "megapascal", "megapascals"),
+    ("bar", "bar", "bar", "bars"),
+    (
+        "psi",
+        "psi",
+        "pound per square inch",
+        "pounds per square inch",
+    ),
+    ("J", "J", "joule", "joules"),
+    ("kJ", "kJ", "kilojoule", "kilojoules"),
+    ("Wh", "Wh", "watt-hour", "watt-hours"),
+    ("kWh", "kWh", "kilowatt-hour", "kilowatt-hours"),
+    ("usd", "USD", "dollar", "dollars"),
+    ("cent", "¢", "cent", "cents"),
+    (
+        "usd_per_m",
+        "USD/m",
+        "dollar per meter",
+        "dollars per meter",
+    ),
+    (
+        "usd_per_ft",
+        "USD/ft",
+        "dollar per foot",
+        "dollars per foot",
+    ),
+    ("K", "K", "kelvin", "kelvin"),
+    ("degC", "°C", "degree Celsius", "degrees Celsius"),
+    ("degF", "°F", "degree Fahrenheit", "degrees Fahrenheit"),
+    ("delta_K", "K", "kelvin", "kelvin"),
+    ("delta_degC", "Δ°C", "degree Celsius", "degrees Celsius"),
+    (
+        "delta_degF",
+        "Δ°F",
+        "degree Fahrenheit",
+        "degrees Fahrenheit",
+    ),
+];
+
+/// A quantity which can be shown with a [`QuantityFormat`].
+pub trait FormatQuantity: Dimension {
+    /// The symbol of the unit to show the quantity in for the given system.
+    fn unit_for(system: &UnitSystem) -> &str;
+
+    fn format_with(&self, format: &QuantityFormat) -> String {
+        format.format_in(self.canonical(), unit::<Self>(format))
+    }
+}
+
+/// The unit to show a quantity in, falling back to the canonical unit if a
+/// custom unit is not one of its units.
+fn unit<T: FormatQuantity + ?Sized>(format: &QuantityFormat) -> UnitEntry<'_> {
+    lookup(T::unit_for(&format.system), Some(&format.units))
+        .filter(|unit| unit.dimension == T::NAME)
+        .unwrap_or_else(|| canonical::<T>())
+}
+
+/// The unit of [`qty!`] a quantity is stored in, i.e. the one without a coefficient or
+/// constant.
+pub(crate) fn canonical<T: Dimension + ?Sized>() -> UnitEntry<'static> {
+    crate::system::UNITS
+        .iter()
+        .find(|unit| {
+            unit.dimension == T::NAME
+                && unit.coefficient == Number::ONE
+                && unit.constant == Number::ZERO
+        })
+        .copied()
+        .expect("canonical units are known")
+}
+
+impl FormatQuantity for Length {
+    fn unit_for(system: &UnitSystem) -> &str {
+        match system {
+            UnitSystem::Metric => "mm",
+            UnitSystem::Imperial => "in",
+            UnitSystem::Custom(units) => &units.length,
+        }
+    }
+
+    fn format_with(&self, format: &QuantityFormat) -> String {
+        match format.system {
+            UnitSystem::Imperial => format.format_feet_inches(*self),
+            _ => format.format_in(self.canonical(), unit::<Self>(format)),
+        }
+    }
+}
+
+impl FormatQuantity for Area {
+    fn unit_for(system: &UnitSystem) -> &str {
+        match system {
+            UnitSystem::Metric => "m2",
+            UnitSystem::Imperial => "ft2",
+            UnitSystem::Custom(units) => &units.area,
+        }
+    }
+}
+
+impl FormatQuantity for Volume {
+    fn unit_for(system: &UnitSystem) -> &str {
+        match system {
+            UnitSystem::Metric => "l",
+            UnitSystem::Imperial => "gal",
+            UnitSystem::Custom(units) => &units.volume,
+        }
+    }
+}
+
+impl FormatQuantity for Angle {
+    fn unit_for(system: &UnitSystem) -> &str {
+        match system {
+            UnitSystem::Metric | UnitSystem::Imperial => "deg",
+            UnitSystem::Custom(units) => &units.angle,
+        }
+    }
+}
+
+/// Implements [`FormatQuantity`] for dimensions without a [`CustomUnits`] field,
+/// which use their metric unit in a custom system.
+macro_rules! format_quantity {
+    ($($dimension:ident => $metric:literal, $imperial:literal;)*) => {
+        $(
+            impl FormatQuantity for $dimension {
+                fn unit_for(system: &UnitSystem) -> &str {
+                    match system {
+                        UnitSystem::Metric | UnitSystem::Custom(_) => $metric,
+                        UnitSystem::Imperial => $imperial,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+format_quantity! {
+    Mass => "kg", "lb";
+    Density => "kg_per_m3", "lb_per_ft3";
+    Time => "s", "s";
+    LinearVelocity => "m_per_s", "mph";
+    Acceleration => "m_per_s2", "m_per_s2";
+    Force => "N", "lbf";
+    Pressure => "kPa", "psi";
+    Energy => "J", "J";
+    Money => "usd", "usd";
+    MoneyPerLength => "usd_per_m", "usd_per_ft";
+    Temperature => "degC", "degF";
+    TemperatureDelta => "delta_degC", "delta_degF";
+}
+
+macro_rules! display_quantity {
+    ($($dimension:ident),*) => {
+        $(
+            /// Shows the quantity in the default [`QuantityFormat`].
+            impl Display for $dimension {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&QuantityFormat::default().format(*self))
+                }
+            }
+        )*
+    };
+}
+
+display_quantity!(
+    Length,
+    Area,
+    Volume,
+    Angle,
+    Mass,
+    Density,
+    Time,
+    LinearVelocity,
+    Acceleration,
+    Force,
+    Pressure,
+    Energy,
+    Money,
+    MoneyPerLength,
+    Temperature,
+    TemperatureDelta
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn custom(length: &str) -> QuantityFormat {
+        QuantityFormat::new(UnitSystem::Custom(CustomUnits {
+            length: length.into(),
+            area: "cm2".into(),
+            volume: "m3".into(),
+            angle: "rad".into(),
+        }))
+    }
+
+    #[test]
+    fn metric() {
+        let metric = QuantityFormat::default();
+        assert_eq!(metric.format(qty!(412 mm)), "412 mm");
+        assert_eq!(metric.format(qty!(0.4125 mm)), "0.413 mm");
+        assert_eq!(metric.format(qty!(-2 m)), "-2000 mm");
+        assert_eq!(metric.format(qty!(1.5 m2)), "1.5 m²");
+        assert_eq!(metric.format(qty!(2 l)), "2 l");
+        assert_eq!(metric.format(qty!(90 deg)), "90°");
+        assert_eq!(qty!(412 mm).to_string(), "412 mm");
+    }
+
+    #[test]
+    fn imperial() {
+        let imperial = QuantityFormat::new(UnitSystem::Imperial);
+        assert_eq!(imperial.format(qty!(15.625 in)), "1' 3-5/8\"");
+        assert_eq!(imperial.format(qty!(15.63 in)), "1' 3-5/8\"");
+        assert_eq!(imperial.format(qty!(12 in)), "1' 0\"");
+        assert_eq!(imperial.format(qty!(0.5 in)), "1/2\"");
+        assert_eq!(imperial.format(qty!(-3 in)), "-3\"");
+        assert_eq!(imperial.format(qty!(412 mm)), "1' 4-1/4\"");
+        assert_eq!(imperial.format(qty!(2 ft2)), "2 ft²");
+
+        let eighths = QuantityFormat {
+            fraction_denominator: 8,
+            ..imperial
+        };
+        assert_eq!(eighths.format(qty!(3.06 in)), "3\"");
+    }
+
+    #[test]
+    fn custom_units_and_precision() {
+        assert_eq!(custom("m").format(qty!(412 mm)), "0.412 m");
+        assert_eq!(custom("m").format(qty!(1 m2)), "10000 cm²");
+        assert_eq!(custom("m").format(qty!(0.5 rad)), "0.5 rad");
+        // Units of another dimension fall back to the canonical unit.
+        assert_eq!(custom("m2").format(qty!(412 mm)), "0.412 m");
+        assert_eq!(custom("K").format(qty!(412 mm)), "0.412 m");
+
+        let significant = QuantityFormat {
+            precision: Precision::Significant(2),
+            ..custom("m")
+        };
+        assert_eq!(significant.format(qty!(412 mm)), "0.41 m");
+        assert_eq!(significant.format(qty!(41.2 m)), "41 m");
+        assert_eq!(significant.format(qty!(4120 m)), "4100 m");
+    }
+
+    #[test]
+    fn unit_names() {
+        let names = QuantityFormat {
+            unit_style: UnitStyle::Name,
+            ..QuantityFormat::default()
+        };
+        assert_eq!(names.format(qty!(1 mm)), "1 millimeter");
+        assert_eq!(names.format(qty!(2 mm)), "2 millimeters");
+        assert_eq!(names.format(qty!(90 deg)), "90 degrees");
+
+        let imperial = QuantityFormat {
+            unit_style: UnitStyle::Name,
+            ..QuantityFormat::new(UnitSystem::Imperial)
+        };
+        assert_eq!(imperial.format(qty!(13 in)), "1 foot 1 inch");
+        assert_eq!(imperial.format(qty!(27.5 in)), "2 feet 3-1/2 inches");
+    }
+
+    #[test]
+    fn other_dimensions() {
+        let metric = QuantityFormat::default();
+        assert_eq!(metric.format(qty!(2500 g)), "2.5 kg");
+        assert_eq!(metric.format(qty!(600 kg_per_m3)), "600 kg/m³");
+        assert_eq!(metric.format(qty!(1.5 MPa)), "1500 kPa");
+        assert_eq!(qty!(13.5 usd).to_string(), "13.5 USD");
+
+        let imperial = QuantityFormat::new(UnitSystem::Imperial);
+        assert_eq!(imperial.format(qty!(36 oz)), "2.25 lb");
+        assert_eq!(imperial.format(qty!(10 lbf)), "10 lbf");
+        assert_eq!(imperial.format(qty!(4.5 usd_per_ft)), "4.5 USD/ft");
+        // Custom systems show dimensions without a custom unit in their metric unit.
+        assert_eq!(custom("m").format(qty!(1 lb)), "0.454 kg");
+
+        assert_eq!(qty!(293.15 K).to_string(), "20 °C");
+        assert_eq!(imperial.format(qty!(100 degC)), "212 °F");
+        assert_eq!(imperial.format(qty!(10 delta_K)), "18 Δ°F");
+    }
+}
@@ -0,0 +1,716 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display},
+    iter::Sum,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+use villagekit_number::{
+    num,
+    traits::{
+        Abs, ApproxEq, CheckedDiv, CheckedSqrt, CheckedTrigInv, Euclid, RoundedAdd, RoundedDiv,
+        RoundedMul, RoundedSqrt, RoundedSub, Rounding, Sqrt, Trig, TrigInv, Validate, Zero,
+    },
+    Number, NumberError,
+};
+
+use crate::Angle;
+
+/// A closed range of values between a lower and an upper bound, e.g. a manufacturing
+/// tolerance.
+///
+/// Arithmetic on intervals gives the range of every possible result, so a
+/// stack-up of toleranced parts can be checked against the space it must fit in.
+/// The bounds are rounded outwards, the lower down and the upper up, so the range
+/// always holds the exact result. Functions such as `sin`, whose results are
+/// themselves approximate, are widened by a few digits of precision.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Interval<T> {
+    pub lower: T,
+    pub upper: T,
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// Creates an interval between two bounds, in either order.
+    pub fn new(a: T, b: T) -> Self {
+        if a <= b {
+            Self { lower: a, upper: b }
+        } else {
+            Self { lower: b, upper: a }
+        }
+    }
+
+    /// Creates an interval containing only the given value.
+    pub fn point(value: T) -> Self {
+        Self {
+            lower: value,
+            upper: value,
+        }
+    }
+
+    /// Returns the smallest interval containing all the given values.
+    fn enclosing<const N: usize>(values: [T; N]) -> Self {
+        let mut interval = Self::point(values[0]);
+        for value in values {
+            if value < interval.lower {
+                interval.lower = value;
+            }
+            if value > interval.upper {
+                interval.upper = value;
+            }
+        }
+        interval
+    }
+
+    /// Returns the interval from the least of the values rounded down to the greatest of
+    /// the values rounded up.
+    fn outward<const N: usize>(down: [T; N], up: [T; N]) -> Self {
+        Self {
+            lower: Self::enclosing(down).lower,
+            upper: Self::enclosing(up).upper,
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+
+    /// Whether every value in this interval is also in the other.
+    pub fn is_within(&self, other: &Self) -> bool {
+        other.lower <= self.lower && self.upper <= other.upper
+    }
+
+    /// Whether every value in this interval is less than or equal to every value in the other,
+    /// e.g. a stack-up of parts which will always fit in a gap.
+    pub fn certainly_le(&self, other: &Self) -> bool {
+        self.upper <= other.lower
+    }
+
+    /// Whether some value in this interval is less than or equal to some value in the other,
+    /// e.g. a stack-up of parts which might fit in a gap.
+    pub fn possibly_le(&self, other: &Self) -> bool {
+        self.lower <= other.upper
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lower = if self.lower > other.lower {
+            self.lower
+        } else {
+            other.lower
+        };
+        let upper = if self.upper < other.upper {
+            self.upper
+        } else {
+            other.upper
+        };
+        (lower <= upper).then_some(Self { lower, upper })
+    }
+
+    /// Returns the smallest interval containing both intervals.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self::enclosing([self.lower, self.upper, other.lower, other.upper])
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + PartialOrd + RoundedAdd<Output = T>,
+{
+    /// Creates an interval from a nominal value and its deviations, e.g. a beam
+    /// of 1 m +0 / -0.5 mm is `Interval::with_deviations(qty!(1 m), qty!(-0.5 mm), qty!(0 m))`.
+    pub fn with_deviations(nominal: T, lower_deviation: T, upper_deviation: T) -> Self {
+        Self::new(
+            nominal.add_rounded(lower_deviation, Rounding::Down),
+            nominal.add_rounded(upper_deviation, Rounding::Up),
+        )
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + PartialOrd + RoundedAdd<Output = T> + Neg<Output = T>,
+{
+    /// Creates an interval from a nominal value and a symmetric tolerance.
+    pub fn plus_minus(nominal: T, tolerance: T) -> Self {
+        Self::with_deviations(nominal, -tolerance, tolerance)
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    pub fn width(&self) -> T {
+        self.upper - self.lower
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Add<Output = T> + Mul<Number, Output = T>,
+{
+    pub fn midpoint(&self) -> T {
+        (self.lower + self.upper) * Number::HALF
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.lower, self.upper)
+    }
+}
+
+impl<A, B, C> Add<Interval<B>> for Interval<A>
+where
+    A: RoundedAdd<B, Output = C>,
+{
+    type Output = Interval<C>;
+
+    fn add(self, rhs: Interval<B>) -> Self::Output {
+        Interval {
+            lower: self.lower.add_rounded(rhs.lower, Rounding::Down),
+            upper: self.upper.add_rounded(rhs.upper, Rounding::Up),
+        }
+    }
+}
+
+impl<A, B, C> Sub<Interval<B>> for Interval<A>
+where
+    A: RoundedSub<B, Output = C>,
+{
+    type Output = Interval<C>;
+
+    fn sub(self, rhs: Interval<B>) -> Self::Output {
+        Interval {
+            lower: self.lower.sub_rounded(rhs.upper, Rounding::Down),
+            upper: self.upper.sub_rounded(rhs.lower, Rounding::Up),
+        }
+    }
+}
+
+impl<T> Neg for Interval<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Interval {
+            lower: -self.upper,
+            upper: -self.lower,
+        }
+    }
+}
+
+impl<A, B, C> Mul<Interval<B>> for Interval<A>
+where
+    A: Copy + RoundedMul<B, Output = C>,
+    B: Copy,
+    C: Copy + PartialOrd,
+{
+    type Output = Interval<C>;
+
+    fn mul(self, rhs: Interval<B>) -> Self::Output {
+        let products = |rounding| {
+            [
+                self.lower.mul_rounded(rhs.lower, rounding),
+                self.lower.mul_rounded(rhs.upper, rounding),
+                self.upper.mul_rounded(rhs.lower, rounding),
+                self.upper.mul_rounded(rhs.upper, rounding),
+            ]
+        };
+        Interval::outward(products(Rounding::Down), products(Rounding::Up))
+    }
+}
+
+/// Division by an interval containing zero has no bounds: use
+/// [`CheckedDiv`] to catch it.
+impl<A, B, C> Div<Interval<B>> for Interval<A>
+where
+    A: Copy + RoundedDiv<B, Output = C>,
+    B: Copy,
+    C: Copy + PartialOrd,
+{
+    type Output = Interval<C>;
+
+    fn div(self, rhs: Interval<B>) -> Self::Output {
+        let quotients = |rounding| {
+            [
+                self.lower.div_rounded(rhs.lower, rounding),
+                self.lower.div_rounded(rhs.upper, rounding),
+                self.upper.div_rounded(rhs.lower, rounding),
+                self.upper.div_rounded(rhs.upper, rounding),
+            ]
+        };
+        Interval::outward(quotients(Rounding::Down), quotients(Rounding::Up))
+    }
+}
+
+impl<A, B, C> CheckedDiv<Interval<B>> for Interval<A>
+where
+    A: Copy + RoundedDiv<B, Output = C>,
+    B: Copy + PartialOrd + Zero,
+    C: Copy + PartialOrd,
+{
+    type Output = Interval<C>;
+
+    fn checked_div(self, rhs: Interval<B>) -> Result<Self::Output, NumberError> {
+        if rhs.contains(B::zero()) {
+            return Err(NumberError::DivideByZero);
+        }
+        Ok(self / rhs)
+    }
+}
+
+impl<T> Mul<Number> for Interval<T>
+where
+    T: Copy + PartialOrd + RoundedMul<Number, Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Number) -> Self::Output {
+        let products = |rounding| {
+            [
+                self.lower.mul_rounded(rhs, rounding),
+                self.upper.mul_rounded(rhs, rounding),
+            ]
+        };
+        Self::outward(products(Rounding::Down), products(Rounding::Up))
+    }
+}
+
+impl<T> Mul<Interval<T>> for Number
+where
+    T: Copy + PartialOrd + RoundedMul<Number, Output = T>,
+{
+    type Output = Interval<T>;
+
+    fn mul(self, rhs: Interval<T>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<T> Div<Number> for Interval<T>
+where
+    T: Copy + PartialOrd + RoundedDiv<Number, Output = T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Number) -> Self::Output {
+        let quotients = |rounding| {
+            [
+                self.lower.div_rounded(rhs, rounding),
+                self.upper.div_rounded(rhs, rounding),
+            ]
+        };
+        Self::outward(quotients(Rounding::Down), quotients(Rounding::Up))
+    }
+}
+
+impl<T> Sum for Interval<T>
+where
+    T: Zero + RoundedAdd<Output = T>,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(
+            Interval {
+                lower: T::zero(),
+                upper: T::zero(),
+            },
+            |sum, interval| sum + interval,
+        )
+    }
+}
+
+impl<T> Zero for Interval<T>
+where
+    T: Zero,
+{
+    fn zero() -> Self {
+        Interval {
+            lower: T::zero(),
+            upper: T::zero(),
+        }
+    }
+}
+
+impl<T> Abs for Interval<T>
+where
+    T: Copy + PartialOrd + Zero + Abs<Output = T>,
+{
+    type Output = Self;
+
+    fn abs(self) -> Self::Output {
+        if self.lower >= T::zero() {
+            self
+        } else if self.upper <= T::zero() {
+            Self::new(self.lower.abs(), self.upper.abs())
+        } else {
+            let lower = self.lower.abs();
+            let upper = self.upper.abs();
+            Self::new(T::zero(), if lower > upper { lower } else { upper })
+        }
+    }
+}
+
+/// The square root of the non-negative part of the interval. Use
+/// [`CheckedSqrt`] to catch intervals with negative values.
+impl<T, U> Sqrt for Interval<T>
+where
+    T: Copy + PartialOrd + Zero + RoundedSqrt<Output = U>,
+{
+    type Output = Interval<U>;
+
+    fn sqrt(self) -> Self::Output {
+        let lower = if self.lower < T::zero() {
+            T::zero()
+        } else {
+            self.lower
+        };
+        let upper = if self.upper < T::zero() {
+            T::zero()
+        } else {
+            self.upper
+        };
+        Interval {
+            lower: lower.sqrt_rounded(Rounding::Down),
+            upper: upper.sqrt_rounded(Rounding::Up),
+        }
+    }
+}
+
+impl<T, U> CheckedSqrt for Interval<T>
+where
+    T: Copy + PartialOrd + Zero + RoundedSqrt<Output = U>,
+{
+    type Output = Interval<U>;
+
+    fn checked_sqrt(self) -> Result<Self::Output, NumberError> {
+        if self.lower < T::zero() {
+            return Err(NumberError::Domain);
+        }
+        Ok(self.sqrt())
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Interval<T> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.lower.approx_eq(&rhs.lower) && self.upper.approx_eq(&rhs.upper)
+    }
+}
+
+impl<T: Validate> Validate for Interval<T> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.lower.validate()?;
+        self.upper.validate()
+    }
+}
+
+/// Moves the result of an approximate function outwards by a few digits of precision, so it
+/// bounds the exact result.
+///
+/// The decimal backends may be off in the last couple of digits, and floating point by an
+/// ulp or two.
+fn widen(value: Number, rounding: Rounding) -> Number {
+    const DIGITS: Number = Number::EPSILON.mul(if Number::DECIMAL { num!(100) } else { num!(4) });
+    let margin = value.abs().mul_rounded(DIGITS, Rounding::Up);
+    match rounding {
+        Rounding::Down => value.sub_rounded(margin, Rounding::Down),
+        Rounding::Up => value.add_rounded(margin, Rounding::Up),
+    }
+}
+
+impl Interval<Number> {
+    const FULL_TURN: Number = Number::PI.mul(Number::TWO);
+
+    /// Returns the smallest interval containing all the results of an approximate
+    /// function, widened by a few digits.
+    fn widened<const N: usize>(values: [Number; N]) -> Self {
+        let interval = Self::enclosing(values);
+        Self {
+            lower: widen(interval.lower, Rounding::Down),
+            upper: widen(interval.upper, Rounding::Up),
+        }
+    }
+
+    /// Whether the interval contains `offset + k * period` for some integer `k`.
+    fn contains_periodic(&self, offset: Number, period: Number) -> bool {
+        let k = (self.lower - offset).div_euclid(period);
+        let candidate = offset + k * period;
+        self.contains(candidate) || self.contains(candidate + period)
+    }
+
+    fn sin(&self) -> Self {
+        if self.width() >= Self::FULL_TURN {
+            return Self::new(-Number::ONE, Number::ONE);
+        }
+        let mut interval = Self::widened([self.lower.sin(), self.upper.sin()]).clamp_unit();
+        if self.contains_periodic(Number::FRAC_PI_2, Self::FULL_TURN) {
+            interval.upper = Number::ONE;
+        }
+        if self.contains_periodic(-Number::FRAC_PI_2, Self::FULL_TURN) {
+            interval.lower = -Number::ONE;
+        }
+        interval
+    }
+
+    fn cos(&self) -> Self {
+        let quarter = Self::point(Number::FRAC_PI_2);
+        (*self + quarter).sin()
+    }
+
+    fn tan(&self) -> Self {
+        if self.width() >= Number::PI || self.contains_periodic(Number::FRAC_PI_2, Number::PI) {
+            return Self::new(Number::MIN, Number::MAX);
+        }
+        Self::widened([self.lower.tan(), self.upper.tan()])
+    }
+
+    /// The smallest and largest absolute values in the interval.
+    fn magnitude(&self) -> Self {
+        Abs::abs(*self)
+    }
+}
+
+impl Trig for Interval<Number> {
+    type Output = Interval<Number>;
+
+    fn hypot(&self, other: Self) -> Self::Output {
+        let (x, y) = (self.magnitude(), other.magnitude());
+        Interval::widened([x.lower.hypot(y.lower), x.upper.hypot(y.upper)])
+    }
+
+    fn sin(&self) -> Self::Output {
+        Interval::sin(self)
+    }
+
+    fn cos(&self) -> Self::Output {
+        Interval::cos(self)
+    }
+
+    fn tan(&self) -> Self::Output {
+        Interval::tan(self)
+    }
+
+    fn sin_cos(&self) -> (Self::Output, Self::Output) {
+        (Interval::sin(self), Interval::cos(self))
+    }
+}
+
+impl TrigInv for Interval<Number> {
+    type Output = Interval<Number>;
+
+    /// Arguments outside `[-1, 1]` have no bounds, as for [`Number::asin`]: use
+    /// [`CheckedTrigInv`] to catch them.
+    fn asin(&self) -> Self::Output {
+        Interval::widened([self.lower.asin(), self.upper.asin()])
+    }
+
+    /// Arguments outside `[-1, 1]` have no bounds, as for [`Number::acos`]: use
+    /// [`CheckedTrigInv`] to catch them.
+    fn acos(&self) -> Self::Output {
+        Interval::widened([self.upper.acos(), self.lower.acos()])
+    }
+
+    fn atan(&self) -> Self::Output {
+        Interval::widened([self.lower.atan(), self.upper.atan()])
+    }
+
+    /// The range of angles of every point in the box `self` (y) by `other` (x).
+    /// If the box touches the origin or crosses the branch cut along the negative
+    /// x axis, this is the full range `[-π, π]`.
+    fn atan2(&self, other: Self) -> Self::Output {
+        let (y, x) = (self, other);
+        let touches_origin = x.contains(Number::ZERO) && y.contains(Number::ZERO);
+        let crosses_cut = x.lower < Number::ZERO && y.contains(Number::ZERO);
+        if touches_origin || crosses_cut {
+            return Interval::widened([-Number::PI, Number::PI]);
+        }
+        Interval::widened([
+            y.lower.atan2(x.lower),
+            y.lower.atan2(x.upper),
+            y.upper.atan2(x.lower),
+            y.upper.atan2(x.upper),
+        ])
+    }
+}
+
+impl CheckedTrigInv for Interval<Number> {
+    type Output = Interval<Number>;
+
+    fn checked_asin(&self) -> Result<Self::Output, NumberError> {
+        self.check_unit()?;
+        Ok(TrigInv::asin(self))
+    }
+
+    fn checked_acos(&self) -> Result<Self::Output, NumberError> {
+        self.check_unit()?;
+        Ok(TrigInv::acos(self))
+    }
+}
+
+impl Interval<Number> {
+    fn check_unit(&self) -> Result<(), NumberError> {
+        if self.is_within(&Self::new(-Number::ONE, Number::ONE)) {
+            Ok(())
+        } else {
+            Err(NumberError::Domain)
+        }
+    }
+
+    fn clamp_unit(&self) -> Self {
+        Self::new(
+            self.lower.clamp(-Number::ONE, Number::ONE),
+            self.upper.clamp(-Number::ONE, Number::ONE),
+        )
+    }
+}
+
+impl Interval<Angle> {
+    fn radians(&self) -> Interval<Number> {
+        Interval::new(self.lower.canonical(), self.upper.canonical())
+    }
+}
+
+impl Trig for Interval<Angle> {
+    type Output = Interval<Number>;
+
+    fn hypot(&self, other: Self) -> Self::Output {
+        self.radians().hypot(other.radians())
+    }
+
+    fn sin(&self) -> Self::Output {
+        self.radians().sin()
+    }
+
+    fn cos(&self) -> Self::Output {
+        self.radians().cos()
+    }
+
+    fn tan(&self) -> Self::Output {
+        self.radians().tan()
+    }
+
+    fn sin_cos(&self) -> (Self::Output, Self::Output) {
+        self.radians().sin_cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use villagekit_number::{num, CheckedTrigInv, Trig, TrigInv};
+
+    use crate::*;
+
+    /// Exactly equal under a decimal backend, and approximately under `f64`, where the
+    /// decimal values are rounded and so the bounds are a step further out.
+    macro_rules! assert_exact_eq {
+        ($given:expr, $expected:expr) => {
+            if Number::DECIMAL {
+                assert_eq!($given, $expected);
+            } else {
+                assert!($given.approx_eq(&$expected));
+            }
+        };
+    }
+
+    #[test]
+    fn arithmetic_bounds() {
+        let a = Interval::new(num!(1), num!(2));
+        let b = Interval::new(num!(-3), num!(4));
+        assert_eq!(a + b, Interval::new(num!(-2), num!(6)));
+        assert_eq!(a - b, Interval::new(num!(-3), num!(5)));
+        assert_eq!(a * b, Interval::new(num!(-6), num!(8)));
+        assert_eq!(-a, Interval::new(num!(-2), num!(-1)));
+        assert_eq!(a * num!(-2), Interval::new(num!(-4), num!(-2)));
+        assert_eq!(a.checked_div(b), Err(NumberError::DivideByZero));
+        assert_eq!(
+            b.checked_div(Interval::new(num!(2), num!(4))),
+            Ok(Interval::new(num!(-1.5), num!(2)))
+        );
+    }
+
+    #[test]
+    fn stack_up_of_beams() {
+        // Three 1 m beams, each within +0 / -0.5 mm.
+        let beam = Interval::with_deviations(qty!(1 m), qty!(-0.5 mm), qty!(0 mm));
+        let stack: Interval<Length> = [beam, beam, beam].into_iter().sum();
+        assert_exact_eq!(stack, Interval::new(qty!(2998.5 mm), qty!(3 m)));
+
+        let gap = Interval::plus_minus(qty!(3001 mm), qty!(0.5 mm));
+        assert!(stack.certainly_le(&gap));
+        assert!(!stack.certainly_le(&Interval::point(qty!(2999 mm))));
+        assert!(stack.possibly_le(&Interval::point(qty!(2999 mm))));
+    }
+
+    #[test]
+    fn dimensioned_products() {
+        let width = Interval::plus_minus(qty!(2 m), qty!(1 mm));
+        let depth = Interval::plus_minus(qty!(3 m), qty!(1 mm));
+        let area = width * depth;
+        assert_exact_eq!(area.lower, qty!(1.999 m) * qty!(2.999 m));
+        assert_exact_eq!(area.upper, qty!(2.001 m) * qty!(3.001 m));
+        let side = Interval::new(qty!(4 m2), qty!(9 m2)).sqrt();
+        assert_eq!(side, Interval::new(qty!(2 m), qty!(3 m)));
+        assert_eq!(
+            Interval::new(qty!(-1 m2), qty!(9 m2)).checked_sqrt(),
+            Err(NumberError::Domain)
+        );
+    }
+
+    #[test]
+    fn trig_bounds() {
+        let angle = Interval::new(qty!(80 deg), qty!(100 deg));
+        let sin = angle.sin();
+        assert!(sin.upper.approx_eq(&num!(1)));
+        // sin(80°) = sin(100°), up to the precision of the degree conversion.
+        assert!((sin.lower - qty!(80 deg).sin()).abs() < num!(1e-15));
+
+        let cos = angle.cos();
+        assert!(cos.lower.approx_eq(&qty!(100 deg).cos()));
+        assert!(cos.upper.approx_eq(&qty!(80 deg).cos()));
+
+        let full = Interval::new(qty!(0 deg), qty!(400 deg)).sin();
+        assert_eq!(full, Interval::new(num!(-1), num!(1)));
+
+        let quadrant = Interval::new(num!(1), num!(2)).atan2(Interval::new(num!(2), num!(4)));
+        assert!(quadrant.lower.approx_eq(&num!(1).atan2(num!(4))));
+        assert!(quadrant.upper.approx_eq(&num!(2).atan2(num!(2))));
+    }
+
+    #[test]
+    fn outward_rounding() {
+        let third = Interval::point(num!(1)) / Interval::point(num!(3));
+        assert!(third.lower < third.upper);
+        assert!(third.contains(num!(1) / num!(3)));
+        assert!((third * Interval::point(num!(3))).contains(num!(1)));
+        assert!((third * num!(3)).contains(num!(1)));
+
+        let root = Interval::point(num!(2)).sqrt();
+        assert!(root.lower < root.upper);
+        assert!((root * root).contains(num!(2)));
+
+        let tiny = Interval::point(num!(1e-90));
+        let sum = Interval::point(num!(0.1)) + tiny;
+        assert!(sum.lower <= num!(0.1) && num!(0.1) < sum.upper);
+        let difference = Interval::point(num!(0.1)) - tiny;
+        assert!(difference.lower < num!(0.1) && num!(0.1) <= difference.upper);
+
+        let sin = Interval::new(num!(0.5), num!(0.6)).sin();
+        assert!(sin.lower < num!(0.5).sin() && num!(0.6).sin() < sin.upper);
+    }
+
+    #[test]
+    fn inverse_trig_domain() {
+        let half = Interval::new(num!(-0.5), num!(0.5));
+        let asin = half.checked_asin().unwrap();
+        assert!(asin.contains(Number::PI / num!(6)));
+        assert!(asin.contains(-Number::PI / num!(6)));
+        assert!(half.checked_acos().unwrap().contains(Number::PI / num!(2)));
+        let beyond = Interval::new(num!(0.5), num!(1.5));
+        assert_eq!(beyond.checked_asin(), Err(NumberError::Domain));
+        assert_eq!(beyond.checked_acos(), Err(NumberError::Domain));
+    }
+}
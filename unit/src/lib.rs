@@ -1,17 +1,43 @@
+mod dual;
+mod dynamic;
+mod expr;
+pub mod fits;
+mod format;
+mod interval;
+mod parse;
+mod quantity;
+mod span;
 pub mod system;
-
+pub mod with_units;
+
+pub use dual::Dual;
+pub use dynamic::{DynamicUnit, UnitRegistry};
+pub use expr::{Bindings, Expr, ExprError, ExprValue};
+pub use format::{CustomUnits, FormatQuantity, Precision, QuantityFormat, UnitStyle, UnitSystem};
+pub use interval::Interval;
+pub use parse::ParseQuantityError;
+pub use quantity::{
+    Dim, DimDiv, DimMul, DimRoot, Dimensionless, Exponents, Quantity, Reduce, ReduceExponents,
+};
 pub use serde::{Deserialize, Serialize};
+pub use span::Span;
 pub use system::*;
 pub use villagekit_number::{
     num,
-    traits::{Abs, ApproxEq, One, Sqrt, Zero},
-    Number,
+    traits::{
+        Abs, ApproxEq, Cbrt, CheckedAdd, CheckedDiv, CheckedMul, CheckedSqrt, CheckedSub, Euclid,
+        One, Recip, RoundTo, Signum, Sqrt, Validate, Zero,
+    },
+    Number, NumberError,
 };
 
 /// A trait implemented by all physical quantities.
 pub trait Dimension {
     type CanonicalUnit: UnitOf<Dim = Self>;
 
+    /// The name of the dimension, e.g. `"Length"`.
+    const NAME: &'static str;
+
     /// Returns the canonical representation of the dimension.
     fn canonical(&self) -> Number;
     /// Creates a new dimension from the canonical representation.
@@ -34,44 +60,9 @@ pub trait UnitOf {
     const CONVERSION_CONSTANT: Number;
 }
 
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __measure_conversions {
-    {} => {};
-    {$self:ty,} => {};
-    ($self:ident, Self * $rhs:ident => $output:ident, $($rest:tt)*) => {
-        impl core::ops::Mul<$rhs> for $self {
-            type Output = $output;
-            fn mul(self, rhs: $rhs) -> Self::Output {
-                use $crate::Dimension;
-                $output::from_scalar::<<$output as Dimension>::CanonicalUnit>(self.canonical() * rhs.canonical())
-            }
-        }
-
-        $crate::__measure_conversions!($self, $($rest)*);
-    };
-    ($self:ident, Self / $rhs:ident => $output:ident, $($rest:tt)*) => {
-        impl core::ops::Div<$rhs> for $self {
-            type Output = $output;
-            fn div(self, rhs: $rhs) -> Self::Output {
-                use $crate::Dimension;
-                $output::from_scalar::<<$output as Dimension>::CanonicalUnit>(self.canonical() / rhs.canonical())
-            }
-        }
-
-        $crate::__measure_conversions!($self, $($rest)*);
-    };
-    ($self:ident, Sqrt => $output:ident, $($rest:tt)*) => {
-        impl villagekit_number::traits::Sqrt for $self {
-            type Output = $output;
-            fn sqrt(self) -> Self::Output {
-                use $crate::Dimension;
-                $output::from_scalar::<<$output as Dimension>::CanonicalUnit>(self.canonical().sqrt())
-            }
-        }
-
-        $crate::__measure_conversions!($self, $($rest)*);
-    };
+/// A unit with a short symbol, as accepted by [`qty!`].
+pub trait UnitSymbol {
+    const SYMBOL: &'static str;
 }
 
 #[macro_export]
@@ -135,53 +126,141 @@ macro_rules! unit_type {
 
 /// A macro for creating a new unit type with simple conversions. Used internally by [`dimension!`](dimension)
 ///
-/// Conversions are implemented by multiplying or dividing by a scalar value.
+/// Conversions are implemented by multiplying or dividing by a scalar value, after adding
+/// an optional offset for units with a different zero, e.g. `offset 273.15` for Celsius.
+///
+/// The `per ... canonical` scalar may also be a [`Number`] constant scaled by a literal, e.g.
+/// `per (PI / 180) canonical`, so irrational factors keep the full precision of the backend.
 #[macro_export]
 macro_rules! unit {
     (
         $(#[$meta:meta])*
-        $vis:vis $unit:ident of dimension $dimension:ident = $($rhsper:literal per canonical)? $(per $lhsper:literal canonical)?
+        $vis:vis $unit:ident of dimension $dimension:ident = $($rhsper:literal per canonical)? $(per $lhsper:tt canonical)? $(offset $offset:literal)?
     ) => {
         $crate::unit_type!(
             $(#[$meta])*
             $vis $unit of dimension $dimension
         );
 
-        $(
-            impl $crate::UnitOf for $unit {
-                type Dim = $dimension;
+        impl $crate::UnitOf for $unit {
+            type Dim = $dimension;
+
+            const CONVERSION_COEFFICIENT: $crate::Number = $crate::Number::ONE
+                $(.div($crate::num!($rhsper)))?
+                $(.mul($crate::__coefficient!($lhsper)))?;
+            const CONVERSION_CONSTANT: $crate::Number = $crate::num!(0)
+                $(.add($crate::num!($offset)))?;
+        }
+    };
+}
 
-                const CONVERSION_COEFFICIENT: $crate::Number = $crate::num!($rhsper);
-                const CONVERSION_CONSTANT: $crate::Number = $crate::num!(0);
+/// Evaluates the scalar of a `per ... canonical` conversion. Used internally by [`unit!`](unit)
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __coefficient {
+    ($literal:literal) => {
+        $crate::num!($literal)
+    };
+    (($constant:ident * $factor:literal)) => {
+        $crate::Number::$constant.mul($crate::num!($factor))
+    };
+    (($constant:ident / $divisor:literal)) => {
+        $crate::Number::$constant.div($crate::num!($divisor))
+    };
+}
+
+/// Implements the arithmetic of a point dimension with the given delta dimension.
+/// Used internally by [`dimension!`](dimension)
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __point_ops {
+    ($name:ident, $delta:ident) => {
+        impl core::ops::Sub<$name> for $name {
+            type Output = $delta;
+            fn sub(self, rhs: $name) -> $delta {
+                $delta::from_canonical(self.0 - rhs.0)
             }
-        )?
-        $(
-            impl $crate::UnitOf for $unit {
-                type Dim = $dimension;
+        }
+        impl core::ops::Add<$delta> for $name {
+            type Output = $name;
+            fn add(self, rhs: $delta) -> $name {
+                $name(self.0 + rhs.canonical())
+            }
+        }
+        impl core::ops::Add<$name> for $delta {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name(self.canonical() + rhs.0)
+            }
+        }
+        impl core::ops::AddAssign<$delta> for $name {
+            fn add_assign(&mut self, rhs: $delta) {
+                self.0 = self.0 + rhs.canonical();
+            }
+        }
+        impl core::ops::Sub<$delta> for $name {
+            type Output = $name;
+            fn sub(self, rhs: $delta) -> $name {
+                $name(self.0 - rhs.canonical())
+            }
+        }
+        impl core::ops::SubAssign<$delta> for $name {
+            fn sub_assign(&mut self, rhs: $delta) {
+                self.0 = self.0 - rhs.canonical();
+            }
+        }
 
-                const CONVERSION_COEFFICIENT: $crate::Number = $crate::num!($lhsper);
-                const CONVERSION_CONSTANT: $crate::Number = $crate::num!(0);
+        impl $crate::CheckedSub for $name {
+            type Output = $delta;
+            fn checked_sub(self, rhs: $name) -> Result<$delta, $crate::NumberError> {
+                Ok($delta::from_canonical($crate::CheckedSub::checked_sub(
+                    self.0, rhs.0,
+                )?))
+            }
+        }
+        impl $crate::CheckedAdd<$delta> for $name {
+            type Output = $name;
+            fn checked_add(self, rhs: $delta) -> Result<$name, $crate::NumberError> {
+                Ok($name($crate::CheckedAdd::checked_add(
+                    self.0,
+                    rhs.canonical(),
+                )?))
+            }
+        }
+        impl $crate::CheckedSub<$delta> for $name {
+            type Output = $name;
+            fn checked_sub(self, rhs: $delta) -> Result<$name, $crate::NumberError> {
+                Ok($name($crate::CheckedSub::checked_sub(
+                    self.0,
+                    rhs.canonical(),
+                )?))
             }
-        )?
+        }
     };
 }
 
 /// A macro for creating a new dimension type and any simple associated unit types.
 /// Associated unit types are parsed using similar syntax to the [`unit!`] macro.
+///
+/// A dimension is a [`Quantity`] of the given [`Dim`] exponents, so its products and
+/// quotients with other quantities follow from the exponents, e.g.
+/// `pub Area = Dim<P2, Z0, Z0, Z0, Z0, Z0>` is what `Length * Length` gives.
+///
+/// A dimension declared `as point of` another, e.g. `Temperature as point of TemperatureDelta`,
+/// is measured from an arbitrary zero: it cannot be scaled or summed, subtracting two points
+/// gives a delta, and only deltas can be added to a point.
 #[macro_export]
 macro_rules! dimension {
     (
         $(#[$meta:meta])*
-        $vis:vis $name:ident {
+        $vis:vis $name:ident as point of $delta:ident {
             canonical: $canonical_unit:ident,
 
             $(
                 $(#[$unit_meta:meta])*
-                $unit:ident: $($rhsper:literal per canonical)? $(per $lhsper:literal canonical)?,
+                $unit:ident: $($rhsper:literal per canonical)? $(per $lhsper:tt canonical)? $(offset $offset:literal)?,
             )+
-        } $(where {
-            $($converts:tt)*
-        })?
+        }
     ) => {
         $(#[$meta])*
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, $crate::Serialize, $crate::Deserialize)]
@@ -190,31 +269,35 @@ macro_rules! dimension {
         impl $name {
             /// Converts the dimension to the given unit as number.
             #[inline]
-            pub const fn to<U: $crate::UnitOf<Dim = Self>>(&self) -> Number
-            where
-                Self: Sized,
-            {
+            pub const fn to<U: $crate::UnitOf<Dim = Self>>(&self) -> Number {
                 (self.0.div(U::CONVERSION_COEFFICIENT)).sub(U::CONVERSION_CONSTANT)
             }
 
             /// Creates a new dimension from the given number and unit.
             #[inline]
-            pub const fn from_scalar<U: $crate::UnitOf<Dim = Self>>(value: Number) -> Self
-            where
-                Self: Sized,
-            {
+            pub const fn from_scalar<U: $crate::UnitOf<Dim = Self>>(value: Number) -> Self {
                 Self((value.add(U::CONVERSION_CONSTANT)).mul(U::CONVERSION_COEFFICIENT))
             }
 
-        }
+            /// Converts the dimension to the given runtime unit as number.
+            pub fn to_dynamic(&self, unit: &$crate::DynamicUnit<Self>) -> Number {
+                self.0 / unit.coefficient() - unit.constant()
+            }
 
+            /// Creates a new dimension from the given number of a runtime unit.
+            pub fn from_dynamic(value: Number, unit: &$crate::DynamicUnit<Self>) -> Self {
+                Self((value + unit.constant()) * unit.coefficient())
+            }
+        }
 
         impl $crate::Dimension for $name {
             type CanonicalUnit = $canonical_unit;
 
+            const NAME: &'static str = stringify!($name);
+
             #[inline]
             fn canonical(&self) -> $crate::Number {
-                self.0.clone()
+                self.0
             }
             #[inline]
             fn from_canonical(value: $crate::Number) -> Self {
@@ -228,100 +311,61 @@ macro_rules! dimension {
             }
         }
 
-        impl core::ops::Add<$name> for $name {
-            type Output = $name;
-            fn add(self, rhs: $name) -> $name {
-                $name(self.0 + rhs.0)
-            }
-        }
-        impl core::ops::AddAssign<$name> for $name {
-            fn add_assign(&mut self, rhs: $name) {
-                self.0 = self.0.clone() + rhs.0;
-            }
-        }
-        impl core::ops::Sub<$name> for $name {
-            type Output = $name;
-            fn sub(self, rhs: $name) -> $name {
-                $name(self.0 - rhs.0)
-            }
-        }
-        impl core::ops::SubAssign<$name> for $name {
-            fn sub_assign(&mut self, rhs: $name) {
-                self.0 = self.0.clone() - rhs.0;
+        impl $crate::ApproxEq for $name {
+            fn approx_eq(&self, other: &Self) -> bool {
+                self.0.approx_eq(&other.0)
             }
         }
 
-        impl core::ops::Mul<$crate::Number> for $name {
-            type Output = $name;
-            fn mul(self, rhs: $crate::Number) -> $name {
-                $name(self.0 * rhs)
-            }
-        }
-        impl core::ops::MulAssign<$crate::Number> for $name {
-            fn mul_assign(&mut self, rhs: $crate::Number) {
-                self.0 = self.0.clone() * rhs;
-            }
-        }
-        impl core::ops::Mul<$name> for $crate::Number {
-            type Output = $name;
-            fn mul(self, rhs: $name) -> $name {
-                $name(self * rhs.0)
+        impl $crate::Validate for $name {
+            fn validate(&self) -> Result<(), $crate::NumberError> {
+                $crate::Validate::validate(&self.0)
             }
         }
 
-        impl core::ops::Div<$crate::Number> for $name {
-            type Output = $name;
-            fn div(self, rhs: $crate::Number) -> $name {
-                $name(self.0 / rhs)
-            }
-        }
-        impl core::ops::DivAssign<$crate::Number> for $name {
-            fn div_assign(&mut self, rhs: $crate::Number) {
-                self.0 = self.0.clone() / rhs;
-            }
-        }
-        impl core::ops::Neg for $name {
-            type Output = $name;
-            fn neg(self) -> $name {
-                $name(self.0.neg())
-            }
-        }
+        $crate::__point_ops!($name, $delta);
 
-        impl $crate::Zero for $name {
-            fn zero() -> Self {
-                $name($crate::Number::ZERO)
-            }
-        }
+        $(
+            $crate::unit!(
+                $(#[$unit_meta])*
+                $vis $unit of dimension $name = $($rhsper per canonical)? $(per $lhsper canonical)? $(offset $offset)?
+            );
+        )*
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis $name:ident = $dim:ty {
+            canonical: $canonical_unit:ident,
 
-        impl $crate::One for $name {
-            fn one() -> Self {
-                $name($crate::Number::ONE)
-            }
+            $(
+                $(#[$unit_meta:meta])*
+                $unit:ident: $($rhsper:literal per canonical)? $(per $lhsper:tt canonical)? $(offset $offset:literal)?,
+            )+
         }
+    ) => {
+        $(#[$meta])*
+        $vis type $name = $crate::Quantity<$dim>;
 
-        impl $crate::Abs for $name {
-            type Output = $name;
+        impl $crate::Dimension for $name {
+            type CanonicalUnit = $canonical_unit;
 
-            fn abs(self) -> Self {
-                $name(self.0.abs())
-            }
-        }
+            const NAME: &'static str = stringify!($name);
 
-        impl $crate::ApproxEq for $name {
-            fn approx_eq(&self, other: &Self) -> bool {
-                self.0.approx_eq(&other.0)
+            #[inline]
+            fn canonical(&self) -> $crate::Number {
+                $crate::Quantity::canonical(self)
+            }
+            #[inline]
+            fn from_canonical(value: $crate::Number) -> Self {
+                $crate::Quantity::from_canonical(value)
             }
         }
 
         $(
             $crate::unit!(
                 $(#[$unit_meta])*
-                $vis $unit of dimension $name = $($rhsper per canonical)? $(per $lhsper canonical)?
+                $vis $unit of dimension $name = $($rhsper per canonical)? $(per $lhsper canonical)? $(offset $offset)?
             );
         )*
-
-        $(
-            $crate::__measure_conversions!($name, $($converts)*);
-        )?
     };
 }
@@ -0,0 +1,461 @@
+use std::fmt::{self, Display};
+use villagekit_number::{num, traits::CheckedDiv, Number};
+
+use crate::UnitRegistry;
+
+/// A unit accepted when parsing quantities, either generated from the abbreviations
+/// in [`qty!`] or registered at runtime.
+#[derive(Clone, Copy)]
+pub struct UnitEntry<'a> {
+    pub symbol: &'a str,
+    /// The name of a runtime unit, for formatting.
+    pub name: Option<&'a str>,
+    pub dimension: &'a str,
+    pub coefficient: Number,
+    pub constant: Number,
+}
+
+/// Alternative spellings of the unit abbreviations.
+const ALIASES: &[(&str, &str)] = &[
+    ("°", "deg"),
+    ("'", "ft"),
+    ("′", "ft"),
+    ("\"", "in"),
+    ("″", "in"),
+    ("meter", "m"),
+    ("meters", "m"),
+    ("metre", "m"),
+    ("metres", "m"),
+    ("millimeter", "mm"),
+    ("millimeters", "mm"),
+    ("millimetre", "mm"),
+    ("millimetres", "mm"),
+    ("centimeter", "cm"),
+    ("centimeters", "cm"),
+    ("centimetre", "cm"),
+    ("centimetres", "cm"),
+    ("inch", "in"),
+    ("inches", "in"),
+    ("foot", "ft"),
+    ("feet", "ft"),
+    ("yard", "yd"),
+    ("yards", "yd"),
+    ("mile", "mi"),
+    ("miles", "mi"),
+    ("liter", "l"),
+    ("liters", "l"),
+    ("litre", "l"),
+    ("litres", "l"),
+    ("radian", "rad"),
+    ("radians", "rad"),
+    ("degree", "deg"),
+    ("degrees", "deg"),
+    ("turn", "rot"),
+    ("turns", "rot"),
+    ("gram", "g"),
+    ("grams", "g"),
+    ("kilogram", "kg"),
+    ("kilograms", "kg"),
+    ("tonne", "t"),
+    ("tonnes", "t"),
+    ("pound", "lb"),
+    ("pounds", "lb"),
+    ("lbs", "lb"),
+    ("ounce", "oz"),
+    ("ounces", "oz"),
+    ("second", "s"),
+    ("seconds", "s"),
+    ("sec", "s"),
+    ("minute", "min"),
+    ("minutes", "min"),
+    ("hour", "h"),
+    ("hours", "h"),
+    ("hr", "h"),
+    ("day", "d"),
+    ("days", "d"),
+    ("newton", "N"),
+    ("newtons", "N"),
+    ("pascal", "Pa"),
+    ("pascals", "Pa"),
+    ("joule", "J"),
+    ("joules", "J"),
+    ("dollar", "usd"),
+    ("dollars", "usd"),
+    ("cents", "cent"),
+    ("°C", "degC"),
+    ("℃", "degC"),
+    ("°F", "degF"),
+    ("℉", "degF"),
+    ("Δ°C", "delta_degC"),
+    ("Δ°F", "delta_degF"),
+    ("ΔK", "delta_K"),
+];
+
+/// Unicode vulgar fractions, as numerator and denominator.
+const FRACTIONS: &[(char, Number, Number)] = &[
+    ('½', num!(1), num!(2)),
+    ('⅓', num!(1), num!(3)),
+    ('⅔', num!(2), num!(3)),
+    ('¼', num!(1), num!(4)),
+    ('¾', num!(3), num!(4)),
+    ('⅛', num!(1), num!(8)),
+    ('⅜', num!(3), num!(8)),
+    ('⅝', num!(5), num!(8)),
+    ('⅞', num!(7), num!(8)),
+];
+
+/// A string which is not a quantity of the expected dimension.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseQuantityError {
+    /// An empty string.
+    Empty,
+    /// A value which is not a number or fraction.
+    InvalidNumber(String),
+    /// A value without a unit.
+    MissingUnit,
+    /// A unit which is not known.
+    UnknownUnit(String),
+    /// A unit of a different dimension, e.g. `"2 m2"` parsed as a length.
+    MismatchedUnit {
+        unit: String,
+        expected: &'static str,
+        found: String,
+    },
+    /// A unit with an offset in a sum of values, e.g. `"20 degC 5 degC"`, as the offset
+    /// would be counted more than once.
+    OffsetInSum(String),
+}
+
+impl Display for ParseQuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseQuantityError::Empty => write!(f, "empty quantity"),
+            ParseQuantityError::InvalidNumber(number) => write!(f, "invalid number `{}`", number),
+            ParseQuantityError::MissingUnit => write!(f, "missing unit after number"),
+            ParseQuantityError::UnknownUnit(unit) => write!(f, "unknown unit `{}`", unit),
+            ParseQuantityError::MismatchedUnit {
+                unit,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` is a unit of {}, expected a unit of {}",
+                unit, found, expected
+            ),
+            ParseQuantityError::OffsetInSum(unit) => write!(
+                f,
+                "`{}` has an offset, so it cannot be summed with other values",
+                unit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseQuantityError {}
+
+/// Finds a unit by symbol, checking the units of [`qty!`] before the registry.
+pub(crate) fn lookup<'a>(
+    symbol: &str,
+    registry: Option<&'a UnitRegistry>,
+) -> Option<UnitEntry<'a>> {
+    let normalized = symbol
+        .replace('²', "2")
+        .replace('³', "3")
+        .replace('/', "_per_");
+    let find = |symbol: &str| {
+        let symbol = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == symbol)
+            .map_or(symbol, |(_, symbol)| *symbol);
+        crate::system::UNITS
+            .iter()
+            .find(|unit| unit.symbol == symbol)
+            .copied()
+    };
+    find(&normalized)
+        .or_else(|| find(&normalized.to_lowercase()))
+        .or_else(|| registry?.lookup(symbol))
+}
+
+/// Parses a sum of values with units, e.g. `"3 ft 4-1/2 in"`, as the canonical
+/// number of the given dimension.
+pub(crate) fn parse_quantity(
+    s: &str,
+    dimension: &'static str,
+    registry: Option<&UnitRegistry>,
+) -> Result<Number, ParseQuantityError> {
+    let mut scanner = Scanner {
+        rest: s.trim(),
+        registry,
+    };
+    if scanner.rest.is_empty() {
+        return Err(ParseQuantityError::Empty);
+    }
+    let negative = scanner.eat('-') || scanner.eat('−');
+    if !negative {
+        scanner.eat('+');
+    }
+
+    // The sign applies to the values, not to the offset of units such as Celsius, which may
+    // only be given alone.
+    let (mut total, mut offset) = (Number::ZERO, Number::ZERO);
+    let (mut terms, mut offset_unit) = (0, None);
+    while !scanner.rest.is_empty() {
+        scanner.skip_whitespace();
+        let value = scanner.number()?;
+        scanner.skip_whitespace();
+        let symbol = scanner.unit_symbol();
+        if symbol.is_empty() {
+            return Err(ParseQuantityError::MissingUnit);
+        }
+        let unit = lookup(symbol, registry)
+            .ok_or_else(|| ParseQuantityError::UnknownUnit(symbol.into()))?;
+        if unit.dimension != dimension {
+            return Err(ParseQuantityError::MismatchedUnit {
+                unit: symbol.into(),
+                expected: dimension,
+                found: unit.dimension.into(),
+            });
+        }
+        if unit.constant != Number::ZERO {
+            offset_unit = Some(symbol);
+        }
+        terms += 1;
+        if let (Some(symbol), true) = (offset_unit, terms > 1) {
+            return Err(ParseQuantityError::OffsetInSum(symbol.into()));
+        }
+        total = total + value * unit.coefficient;
+        offset = offset + unit.constant * unit.coefficient;
+        scanner.skip_whitespace();
+    }
+    Ok(if negative { -total } else { total } + offset)
+}
+
+struct Scanner<'a> {
+    rest: &'a str,
+    registry: Option<&'a UnitRegistry>,
+}
+
+impl<'a> Scanner<'a> {
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let end = self
+            .rest
+            .find(|c: char| !predicate(c))
+            .unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        taken
+    }
+
+    fn digits(&mut self) -> &'a str {
+        self.take_while(|c| c.is_ascii_digit() || c == '.' || c == '_')
+    }
+
+    fn unicode_fraction(&mut self) -> Option<Number> {
+        let c = self.rest.chars().next()?;
+        let (_, numerator, denominator) = FRACTIONS.iter().find(|(fraction, ..)| *fraction == c)?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Some(*numerator / *denominator)
+    }
+
+    fn decimal(&self, digits: &str) -> Result<Number, ParseQuantityError> {
+        Number::parse(digits).map_err(|_| ParseQuantityError::InvalidNumber(digits.into()))
+    }
+
+    fn fraction(&mut self, numerator: &str) -> Result<Number, ParseQuantityError> {
+        let denominator = self.digits();
+        let invalid =
+            || ParseQuantityError::InvalidNumber(format!("{}/{}", numerator, denominator));
+        self.decimal(numerator)?
+            .checked_div(self.decimal(denominator).map_err(|_| invalid())?)
+            .map_err(|_| invalid())
+    }
+
+    /// Parses a decimal, a fraction such as `3/8`, or a mixed number such as
+    /// `1 3/8`, `1-3/8` or `1⅜`.
+    fn number(&mut self) -> Result<Number, ParseQuantityError> {
+        if let Some(fraction) = self.unicode_fraction() {
+            return Ok(fraction);
+        }
+        let digits = self.digits();
+        if digits.is_empty() {
+            let token = self.rest.split_whitespace().next().unwrap_or_default();
+            return Err(ParseQuantityError::InvalidNumber(token.into()));
+        }
+        if self.eat('/') {
+            return self.fraction(digits);
+        }
+        let whole = self.decimal(digits)?;
+        if let Some(fraction) = self.unicode_fraction() {
+            return Ok(whole + fraction);
+        }
+
+        let checkpoint = self.rest;
+        if !self.eat('-') {
+            self.skip_whitespace();
+        }
+        let numerator = self.digits();
+        if !numerator.is_empty() && self.eat('/') {
+            return Ok(whole + self.fraction(numerator)?);
+        }
+        self.rest = checkpoint;
+        Ok(whole)
+    }
+
+    /// Takes a unit symbol, including a trailing power such as the `2` in `m2` and
+    /// a quotient such as `kg/m3`.
+    fn unit_symbol(&mut self) -> &'a str {
+        let start = self.rest;
+        let part = |c: char| {
+            !(c.is_whitespace() || c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | '/'))
+        };
+        let mut symbol = self.take_while(part);
+        while !symbol.is_empty() && self.quotient_follows() {
+            self.eat('/');
+            let denominator = self.take_while(part);
+            symbol = &start[..symbol.len() + 1 + denominator.len()];
+        }
+        if let Some(power) = self.rest.chars().next().filter(|c| matches!(c, '2' | '3')) {
+            let extended = &start[..symbol.len() + power.len_utf8()];
+            if lookup(extended, self.registry).is_some() {
+                self.rest = &self.rest[power.len_utf8()..];
+                return extended;
+            }
+        }
+        symbol
+    }
+
+    fn quotient_follows(&self) -> bool {
+        let mut chars = self.rest.chars();
+        chars.next() == Some('/') && chars.next().is_some_and(char::is_alphabetic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::*;
+
+    macro_rules! assert_approx_eq {
+        ($left:expr, $right:expr) => {
+            let (left, right) = ($left, $right);
+            assert!(left.approx_eq(&right), "{:?} != {:?}", left, right);
+        };
+    }
+
+    #[test]
+    fn parse_metric() {
+        assert_eq!(Length::parse("25.4mm"), Ok(qty!(25.4 mm)));
+        assert_eq!(Length::parse(" 25.4 mm "), Ok(qty!(25.4 mm)));
+        assert_eq!("-1.5 m".parse::<Length>(), Ok(qty!(-1.5 m)));
+        assert_eq!(Length::from_str("2 metres"), Ok(qty!(2 m)));
+        assert_eq!(Area::parse("3 m2"), Ok(qty!(3 m2)));
+        assert_eq!(Area::parse("3 m²"), Ok(qty!(3 m2)));
+        assert_approx_eq!(Volume::parse("1.5 L").unwrap(), qty!(1.5 l));
+    }
+
+    #[test]
+    fn parse_imperial() {
+        assert_approx_eq!(Length::parse("3 ft 4 in").unwrap(), qty!(40 in));
+        assert_approx_eq!(Length::parse("3' 4\"").unwrap(), qty!(40 in));
+        assert_approx_eq!(Length::parse("3ft4in").unwrap(), qty!(40 in));
+        assert_approx_eq!(Length::parse("1 3/8\"").unwrap(), qty!(1.375 in));
+        assert_approx_eq!(Length::parse("1-3/8 in").unwrap(), qty!(1.375 in));
+        assert_approx_eq!(Length::parse("1⅜″").unwrap(), qty!(1.375 in));
+        assert_approx_eq!(Length::parse("3/8 inch").unwrap(), qty!(0.375 in));
+        assert_approx_eq!(Length::parse("-1 ft 6 in").unwrap(), qty!(-18 in));
+    }
+
+    #[test]
+    fn parse_angles() {
+        assert_approx_eq!(Angle::parse("90°").unwrap(), qty!(90 deg));
+        assert_approx_eq!(Angle::parse("0.25 rot").unwrap(), qty!(0.25 rot));
+        assert_approx_eq!(Angle::parse("½ turn").unwrap(), qty!(0.5 rot));
+    }
+
+    #[test]
+    fn parse_temperatures() {
+        assert_approx_eq!(Temperature::parse("20 degC").unwrap(), qty!(20 degC));
+        assert_approx_eq!(Temperature::parse("-40 ℃").unwrap(), qty!(-40 degC));
+        assert_approx_eq!(Temperature::parse("20 K 5 K").unwrap(), qty!(25 K));
+        assert_approx_eq!(
+            TemperatureDelta::parse("20 delta_degC 9 delta_degF").unwrap(),
+            qty!(25 delta_degC)
+        );
+    }
+
+    #[test]
+    fn parse_compound_units() {
+        assert_approx_eq!(Mass::parse("2 lb 4 oz").unwrap(), qty!(36 oz));
+        assert_approx_eq!(Density::parse("600 kg/m3").unwrap(), qty!(600 kg_per_m3));
+        assert_approx_eq!(Density::parse("0.6 g/cm³").unwrap(), qty!(600 kg_per_m3));
+        assert_approx_eq!(Acceleration::parse("9.8 m/s²").unwrap(), qty!(9.8 m_per_s2));
+        assert_approx_eq!(LinearVelocity::parse("36 km/h").unwrap(), qty!(10 m_per_s));
+        assert_approx_eq!(Pressure::parse("10 MPa").unwrap(), qty!(10000 kPa));
+        assert_approx_eq!(
+            MoneyPerLength::parse("3 usd/ft").unwrap(),
+            qty!(3 usd_per_ft)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Length::parse(""), Err(ParseQuantityError::Empty));
+        assert_eq!(Length::parse("25"), Err(ParseQuantityError::MissingUnit));
+        assert_eq!(
+            Length::parse("5 furlongs"),
+            Err(ParseQuantityError::UnknownUnit("furlongs".into()))
+        );
+        assert_eq!(
+            Length::parse("5 m2"),
+            Err(ParseQuantityError::MismatchedUnit {
+                unit: "m2".into(),
+                expected: "Length",
+                found: "Area".into(),
+            })
+        );
+        assert_eq!(
+            Length::parse("5 kg"),
+            Err(ParseQuantityError::MismatchedUnit {
+                unit: "kg".into(),
+                expected: "Length",
+                found: "Mass".into(),
+            })
+        );
+        assert_eq!(
+            Length::parse("abc m"),
+            Err(ParseQuantityError::InvalidNumber("abc".into()))
+        );
+        assert_eq!(
+            Length::parse("1/0 in"),
+            Err(ParseQuantityError::InvalidNumber("1/0".into()))
+        );
+        assert_eq!(
+            Temperature::parse("20 degC 5 degC"),
+            Err(ParseQuantityError::OffsetInSum("degC".into()))
+        );
+        assert_eq!(
+            Temperature::parse("20 K 5 degF"),
+            Err(ParseQuantityError::OffsetInSum("degF".into()))
+        );
+        assert_eq!(
+            Length::parse("5 m2").unwrap_err().to_string(),
+            "`m2` is a unit of Area, expected a unit of Length"
+        );
+    }
+}
@@ -0,0 +1,612 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+use typenum::{Integer, NInt, NonZero, PInt, PartialDiv, PartialQuot, Unsigned, Z0};
+use villagekit_number::{
+    traits::{
+        Abs, ApproxEq, Cbrt, CheckedAdd, CheckedDiv, CheckedMul, CheckedSqrt, CheckedSub, Euclid,
+        One, Recip, RoundTo, RoundedAdd, RoundedDiv, RoundedMul, RoundedSqrt, RoundedSub, Rounding,
+        Signum, Sqrt, Validate, Zero,
+    },
+    Number, NumberError,
+};
+
+use crate::{DynamicUnit, UnitOf};
+
+/// The exponents of the base dimensions of a quantity, as [`typenum`] integers, in order:
+/// length, mass, time, temperature, angle and money.
+///
+/// For example a pressure, force per area, is `Dim<N1, P1, N2, Z0, Z0, Z0>`. Only used as
+/// a type, to tag a [`Quantity`].
+pub struct Dim<L, M, T, K, A, C>(PhantomData<(L, M, T, K, A, C)>);
+
+/// The exponents of a [`Dim`].
+pub trait Exponents {
+    const EXPONENTS: [i32; 6];
+}
+
+impl<L, M, T, K, A, C> Exponents for Dim<L, M, T, K, A, C>
+where
+    L: Integer,
+    M: Integer,
+    T: Integer,
+    K: Integer,
+    A: Integer,
+    C: Integer,
+{
+    const EXPONENTS: [i32; 6] = [L::I32, M::I32, T::I32, K::I32, A::I32, C::I32];
+}
+
+/// The symbols of the canonical units of the base dimensions, in the order of [`Dim`].
+const BASE_SYMBOLS: [&str; 6] = ["m", "kg", "s", "K", "rad", "usd"];
+
+/// The dimension of the product of two quantities.
+pub trait DimMul<Rhs> {
+    type Output;
+}
+
+impl<L1, M1, T1, K1, A1, C1, L2, M2, T2, K2, A2, C2> DimMul<Dim<L2, M2, T2, K2, A2, C2>>
+    for Dim<L1, M1, T1, K1, A1, C1>
+where
+    L1: Add<L2>,
+    M1: Add<M2>,
+    T1: Add<T2>,
+    K1: Add<K2>,
+    A1: Add<A2>,
+    C1: Add<C2>,
+{
+    type Output = Dim<L1::Output, M1::Output, T1::Output, K1::Output, A1::Output, C1::Output>;
+}
+
+/// The dimension of the quotient of two quantities.
+pub trait DimDiv<Rhs> {
+    type Output;
+}
+
+impl<L1, M1, T1, K1, A1, C1, L2, M2, T2, K2, A2, C2> DimDiv<Dim<L2, M2, T2, K2, A2, C2>>
+    for Dim<L1, M1, T1, K1, A1, C1>
+where
+    L1: Sub<L2>,
+    M1: Sub<M2>,
+    T1: Sub<T2>,
+    K1: Sub<K2>,
+    A1: Sub<A2>,
+    C1: Sub<C2>,
+{
+    type Output = Dim<L1::Output, M1::Output, T1::Output, K1::Output, A1::Output, C1::Output>;
+}
+
+/// The dimension of the `N`th root of a quantity, if every exponent divides by `N`.
+pub trait DimRoot<N> {
+    type Output;
+}
+
+impl<N, L, M, T, K, A, C> DimRoot<N> for Dim<L, M, T, K, A, C>
+where
+    L: PartialDiv<N>,
+    M: PartialDiv<N>,
+    T: PartialDiv<N>,
+    K: PartialDiv<N>,
+    A: PartialDiv<N>,
+    C: PartialDiv<N>,
+{
+    type Output = Dim<
+        PartialQuot<L, N>,
+        PartialQuot<M, N>,
+        PartialQuot<T, N>,
+        PartialQuot<K, N>,
+        PartialQuot<A, N>,
+        PartialQuot<C, N>,
+    >;
+}
+
+/// The value of an operation giving a dimension: a [`Quantity`] of it, or a [`Number`]
+/// once every exponent has cancelled out, e.g. for `Length / Length`.
+pub trait Reduce {
+    type Output;
+
+    fn reduce(value: Number) -> Self::Output;
+}
+
+impl<L, M, T, K, A, C> Reduce for Dim<L, M, T, K, A, C>
+where
+    (L, (M, (T, (K, (A, (C, ())))))): ReduceExponents<Self>,
+{
+    type Output = <(L, (M, (T, (K, (A, (C, ())))))) as ReduceExponents<Self>>::Output;
+
+    #[inline]
+    fn reduce(value: Number) -> Self::Output {
+        <(L, (M, (T, (K, (A, (C, ())))))) as ReduceExponents<Self>>::reduce(value)
+    }
+}
+
+/// Walks the exponents of `D` as a list, giving a [`Quantity`] at the first non-zero one.
+#[doc(hidden)]
+pub trait ReduceExponents<D> {
+    type Output;
+
+    fn reduce(value: Number) -> Self::Output;
+}
+
+impl<D> ReduceExponents<D> for () {
+    type Output = Number;
+
+    #[inline]
+    fn reduce(value: Number) -> Number {
+        value
+    }
+}
+
+impl<D, Rest: ReduceExponents<D>> ReduceExponents<D> for (Z0, Rest) {
+    type Output = Rest::Output;
+
+    #[inline]
+    fn reduce(value: Number) -> Self::Output {
+        Rest::reduce(value)
+    }
+}
+
+impl<D, U: Unsigned + NonZero, Rest> ReduceExponents<D> for (PInt<U>, Rest) {
+    type Output = Quantity<D>;
+
+    #[inline]
+    fn reduce(value: Number) -> Quantity<D> {
+        Quantity::from_canonical(value)
+    }
+}
+
+impl<D, U: Unsigned + NonZero, Rest> ReduceExponents<D> for (NInt<U>, Rest) {
+    type Output = Quantity<D>;
+
+    #[inline]
+    fn reduce(value: Number) -> Quantity<D> {
+        Quantity::from_canonical(value)
+    }
+}
+
+/// The dimension of a plain [`Number`].
+pub type Dimensionless = Dim<Z0, Z0, Z0, Z0, Z0, Z0>;
+
+/// A physical quantity of the dimension `D`, stored in canonical units.
+///
+/// Named dimensions such as [`Length`](crate::Length) and [`Area`](crate::Area) are
+/// aliases of this, created by [`dimension!`](crate::dimension). Multiplying or dividing
+/// any two quantities gives a quantity of the combined dimension, or a [`Number`] when
+/// the dimensions cancel out:
+///
+/// ```
+/// use villagekit_unit::*;
+///
+/// let area: Area = qty!(2 m) * qty!(3 m);
+/// let ratio: Number = qty!(1 m) / qty!(250 mm);
+/// let second_moment = area * area / qty!(1 m2);
+/// assert_eq!(area, qty!(6 m2));
+/// assert_eq!(ratio, num!(4));
+/// assert_eq!(second_moment, qty!(36 m2));
+/// ```
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+pub struct Quantity<D>(Number, #[serde(skip)] PhantomData<fn() -> D>);
+
+impl<D> Quantity<D> {
+    /// Creates a quantity from its value in canonical units.
+    #[inline]
+    pub const fn from_canonical(value: Number) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Returns the value of the quantity in canonical units.
+    #[inline]
+    pub const fn canonical(&self) -> Number {
+        self.0
+    }
+
+    /// Converts the dimension to the given unit as number.
+    #[inline]
+    pub const fn to<U: UnitOf<Dim = Self>>(&self) -> Number {
+        (self.0.div(U::CONVERSION_COEFFICIENT)).sub(U::CONVERSION_CONSTANT)
+    }
+
+    /// Creates a new dimension from the given number and unit.
+    #[inline]
+    pub const fn from_scalar<U: UnitOf<Dim = Self>>(value: Number) -> Self {
+        Self::from_canonical((value.add(U::CONVERSION_CONSTANT)).mul(U::CONVERSION_COEFFICIENT))
+    }
+
+    /// Converts the dimension to the given runtime unit as number.
+    pub fn to_dynamic(&self, unit: &DynamicUnit<Self>) -> Number {
+        self.0 / unit.coefficient() - unit.constant()
+    }
+
+    /// Creates a new dimension from the given number of a runtime unit.
+    pub fn from_dynamic(value: Number, unit: &DynamicUnit<Self>) -> Self {
+        Self::from_canonical((value + unit.constant()) * unit.coefficient())
+    }
+}
+
+impl<D> Clone for Quantity<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for Quantity<D> {}
+
+impl<D> Default for Quantity<D> {
+    fn default() -> Self {
+        Self::from_canonical(Number::default())
+    }
+}
+
+impl<D> PartialEq for Quantity<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<D> Eq for Quantity<D> {}
+
+impl<D> PartialOrd for Quantity<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for Quantity<D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<D> Hash for Quantity<D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<D: Exponents> Debug for Quantity<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quantity({:?}", self.0)?;
+        for (symbol, exponent) in BASE_SYMBOLS.iter().zip(D::EXPONENTS) {
+            match exponent {
+                0 => {}
+                1 => write!(f, " {}", symbol)?,
+                _ => write!(f, " {}^{}", symbol, exponent)?,
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+impl<D> ApproxEq for Quantity<D> {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.0.approx_eq(&other.0)
+    }
+}
+
+impl<D> Validate for Quantity<D> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.0.validate()
+    }
+}
+
+impl<D> Add for Quantity<D> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_canonical(self.0 + rhs.0)
+    }
+}
+impl<D> AddAssign for Quantity<D> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = self.0 + rhs.0;
+    }
+}
+impl<D> Sub for Quantity<D> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_canonical(self.0 - rhs.0)
+    }
+}
+impl<D> SubAssign for Quantity<D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = self.0 - rhs.0;
+    }
+}
+impl<D> Neg for Quantity<D> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_canonical(self.0.neg())
+    }
+}
+
+impl<D> Mul<Number> for Quantity<D> {
+    type Output = Self;
+    fn mul(self, rhs: Number) -> Self {
+        Self::from_canonical(self.0 * rhs)
+    }
+}
+impl<D> MulAssign<Number> for Quantity<D> {
+    fn mul_assign(&mut self, rhs: Number) {
+        self.0 = self.0 * rhs;
+    }
+}
+impl<D> Mul<Quantity<D>> for Number {
+    type Output = Quantity<D>;
+    fn mul(self, rhs: Quantity<D>) -> Quantity<D> {
+        Quantity::from_canonical(self * rhs.0)
+    }
+}
+impl<D> Div<Number> for Quantity<D> {
+    type Output = Self;
+    fn div(self, rhs: Number) -> Self {
+        Self::from_canonical(self.0 / rhs)
+    }
+}
+impl<D> DivAssign<Number> for Quantity<D> {
+    fn div_assign(&mut self, rhs: Number) {
+        self.0 = self.0 / rhs;
+    }
+}
+impl<D> Div<Quantity<D>> for Number
+where
+    Dimensionless: DimDiv<D>,
+{
+    type Output = Quantity<<Dimensionless as DimDiv<D>>::Output>;
+    fn div(self, rhs: Quantity<D>) -> Self::Output {
+        Quantity::from_canonical(self / rhs.0)
+    }
+}
+
+impl<D1, D2> Mul<Quantity<D2>> for Quantity<D1>
+where
+    D1: DimMul<D2>,
+    D1::Output: Reduce,
+{
+    type Output = <D1::Output as Reduce>::Output;
+    fn mul(self, rhs: Quantity<D2>) -> Self::Output {
+        <D1::Output as Reduce>::reduce(self.0 * rhs.0)
+    }
+}
+impl<D1, D2> Div<Quantity<D2>> for Quantity<D1>
+where
+    D1: DimDiv<D2>,
+    D1::Output: Reduce,
+{
+    type Output = <D1::Output as Reduce>::Output;
+    fn div(self, rhs: Quantity<D2>) -> Self::Output {
+        <D1::Output as Reduce>::reduce(self.0 / rhs.0)
+    }
+}
+
+impl<D> Sqrt for Quantity<D>
+where
+    D: DimRoot<typenum::P2>,
+{
+    type Output = Quantity<D::Output>;
+    fn sqrt(self) -> Self::Output {
+        Quantity::from_canonical(self.0.sqrt())
+    }
+}
+impl<D> Cbrt for Quantity<D>
+where
+    D: DimRoot<typenum::P3>,
+{
+    type Output = Quantity<D::Output>;
+    fn cbrt(self) -> Self::Output {
+        Quantity::from_canonical(self.0.cbrt())
+    }
+}
+impl<D> Recip for Quantity<D>
+where
+    Dimensionless: DimDiv<D>,
+{
+    type Output = Quantity<<Dimensionless as DimDiv<D>>::Output>;
+    fn recip(self) -> Self::Output {
+        Quantity::from_canonical(self.0.recip())
+    }
+}
+
+impl<D> Zero for Quantity<D> {
+    fn zero() -> Self {
+        Self::from_canonical(Number::ZERO)
+    }
+}
+
+impl<D> One for Quantity<D> {
+    fn one() -> Self {
+        Self::from_canonical(Number::ONE)
+    }
+}
+
+impl<D> Abs for Quantity<D> {
+    type Output = Self;
+    fn abs(self) -> Self {
+        Self::from_canonical(self.0.abs())
+    }
+}
+
+impl<D> CheckedAdd for Quantity<D> {
+    type Output = Self;
+    fn checked_add(self, rhs: Self) -> Result<Self, NumberError> {
+        Ok(Self::from_canonical(self.0.checked_add(rhs.0)?))
+    }
+}
+impl<D> CheckedSub for Quantity<D> {
+    type Output = Self;
+    fn checked_sub(self, rhs: Self) -> Result<Self, NumberError> {
+        Ok(Self::from_canonical(self.0.checked_sub(rhs.0)?))
+    }
+}
+impl<D> CheckedMul<Number> for Quantity<D> {
+    type Output = Self;
+    fn checked_mul(self, rhs: Number) -> Result<Self, NumberError> {
+        Ok(Self::from_canonical(self.0.checked_mul(rhs)?))
+    }
+}
+impl<D> CheckedDiv<Number> for Quantity<D> {
+    type Output = Self;
+    fn checked_div(self, rhs: Number) -> Result<Self, NumberError> {
+        Ok(Self::from_canonical(self.0.checked_div(rhs)?))
+    }
+}
+impl<D1, D2> CheckedMul<Quantity<D2>> for Quantity<D1>
+where
+    D1: DimMul<D2>,
+    D1::Output: Reduce,
+{
+    type Output = <D1::Output as Reduce>::Output;
+    fn checked_mul(self, rhs: Quantity<D2>) -> Result<Self::Output, NumberError> {
+        Ok(<D1::Output as Reduce>::reduce(self.0.checked_mul(rhs.0)?))
+    }
+}
+impl<D1, D2> CheckedDiv<Quantity<D2>> for Quantity<D1>
+where
+    D1: DimDiv<D2>,
+    D1::Output: Reduce,
+{
+    type Output = <D1::Output as Reduce>::Output;
+    fn checked_div(self, rhs: Quantity<D2>) -> Result<Self::Output, NumberError> {
+        Ok(<D1::Output as Reduce>::reduce(self.0.checked_div(rhs.0)?))
+    }
+}
+impl<D> CheckedSqrt for Quantity<D>
+where
+    D: DimRoot<typenum::P2>,
+{
+    type Output = Quantity<D::Output>;
+    fn checked_sqrt(self) -> Result<Self::Output, NumberError> {
+        Ok(Quantity::from_canonical(self.0.checked_sqrt()?))
+    }
+}
+
+impl<D> RoundedAdd for Quantity<D> {
+    type Output = Self;
+    fn add_rounded(self, rhs: Self, rounding: Rounding) -> Self {
+        Self::from_canonical(self.0.add_rounded(rhs.0, rounding))
+    }
+}
+impl<D> RoundedSub for Quantity<D> {
+    type Output = Self;
+    fn sub_rounded(self, rhs: Self, rounding: Rounding) -> Self {
+        Self::from_canonical(self.0.sub_rounded(rhs.0, rounding))
+    }
+}
+impl<D> RoundedMul<Number> for Quantity<D> {
+    type Output = Self;
+    fn mul_rounded(self, rhs: Number, rounding: Rounding) -> Self {
+        Self::from_canonical(self.0.mul_rounded(rhs, rounding))
+    }
+}
+impl<D> RoundedDiv<Number> for Quantity<D> {
+    type Output = Self;
+    fn div_rounded(self, rhs: Number, rounding: Rounding) -> Self {
+        Self::from_canonical(self.0.div_rounded(rhs, rounding))
+    }
+}
+impl<D1, D2> RoundedMul<Quantity<D2>> for Quantity<D1>
+where
+    D1: DimMul<D2>,
+    D1::Output: Reduce,
+{
+    type Output = <D1::Output as Reduce>::Output;
+    fn mul_rounded(self, rhs: Quantity<D2>, rounding: Rounding) -> Self::Output {
+        <D1::Output as Reduce>::reduce(self.0.mul_rounded(rhs.0, rounding))
+    }
+}
+impl<D1, D2> RoundedDiv<Quantity<D2>> for Quantity<D1>
+where
+    D1: DimDiv<D2>,
+    D1::Output: Reduce,
+{
+    type Output = <D1::Output as Reduce>::Output;
+    fn div_rounded(self, rhs: Quantity<D2>, rounding: Rounding) -> Self::Output {
+        <D1::Output as Reduce>::reduce(self.0.div_rounded(rhs.0, rounding))
+    }
+}
+impl<D> RoundedSqrt for Quantity<D>
+where
+    D: DimRoot<typenum::P2>,
+{
+    type Output = Quantity<D::Output>;
+    fn sqrt_rounded(self, rounding: Rounding) -> Self::Output {
+        Quantity::from_canonical(self.0.sqrt_rounded(rounding))
+    }
+}
+
+impl<D> RoundTo for Quantity<D> {
+    fn floor_to(self, step: Self) -> Self {
+        Self::from_canonical(self.0.floor_to(step.0))
+    }
+    fn ceil_to(self, step: Self) -> Self {
+        Self::from_canonical(self.0.ceil_to(step.0))
+    }
+    fn round_to(self, step: Self) -> Self {
+        Self::from_canonical(self.0.round_to(step.0))
+    }
+    fn trunc_to(self, step: Self) -> Self {
+        Self::from_canonical(self.0.trunc_to(step.0))
+    }
+}
+
+impl<D> Signum for Quantity<D> {
+    fn signum(self) -> Number {
+        self.0.signum()
+    }
+    fn is_sign_positive(&self) -> bool {
+        self.0.is_sign_positive()
+    }
+    fn is_sign_negative(&self) -> bool {
+        self.0.is_sign_negative()
+    }
+}
+
+impl<D> Euclid for Quantity<D> {
+    fn div_euclid(self, rhs: Self) -> Number {
+        self.0.div_euclid(rhs.0)
+    }
+    fn rem_euclid(self, rhs: Self) -> Self {
+        Self::from_canonical(self.0.rem_euclid(rhs.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn products_and_quotients() {
+        let area: Area = qty!(2 m) * qty!(3 m);
+        let volume: Volume = area * qty!(0.5 m);
+        assert_eq!(volume, qty!(3 m3));
+        assert_eq!(volume / area, qty!(0.5 m));
+        assert_eq!(volume / qty!(0.5 m) / qty!(2 m), qty!(3 m));
+
+        let ratio: Number = qty!(1 m) / qty!(250 mm);
+        assert_eq!(ratio, num!(4));
+        let angle_ratio: Number = qty!(180 deg) / qty!(90 deg);
+        assert!(angle_ratio.approx_eq(&num!(2)));
+
+        // Dimensions without a name still cancel back out.
+        let hyper = area * area;
+        assert_eq!(hyper / area, area);
+        assert_eq!((hyper / qty!(1 m2)).sqrt(), qty!(6 m));
+        assert_eq!(
+            format!("{:?}", hyper),
+            format!("Quantity({:?} m^4)", num!(36))
+        );
+    }
+
+    #[test]
+    fn roots_and_reciprocals() {
+        assert_eq!(qty!(9 m2).sqrt(), qty!(3 m));
+        assert!(qty!(8 m3).cbrt().approx_eq(&qty!(2 m)));
+        let per_second = qty!(4 s).recip();
+        assert_eq!(per_second * qty!(2 m), qty!(0.5 m_per_s));
+        assert_eq!(num!(2) / qty!(4 s) * qty!(2 m), qty!(1 m_per_s));
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Sub},
+};
+use villagekit_number::{
+    traits::{Abs, ApproxEq, Euclid, RoundTo, Validate, Zero},
+    Number, NumberError,
+};
+
+use crate::{
+    with_units::{SerdeUnits, WithUnits},
+    Interval,
+};
+
+/// A directed stretch of values from a start to an end, e.g. where a beam runs along one
+/// axis of a grid.
+///
+/// Unlike an [`Interval`], a span keeps its direction: the span from 2 m to 0 m runs
+/// backwards, over the same values as the span from 0 m to 2 m.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Span<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T> Span<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the span running the other way.
+    pub fn reversed(self) -> Self {
+        Self {
+            start: self.end,
+            end: self.start,
+        }
+    }
+
+    pub fn map<U>(self, mapper: impl Fn(T) -> U) -> Span<U> {
+        Span {
+            start: mapper(self.start),
+            end: mapper(self.end),
+        }
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// The lower of the start and end.
+    pub fn min(&self) -> T {
+        if self.end < self.start {
+            self.end
+        } else {
+            self.start
+        }
+    }
+
+    /// The higher of the start and end.
+    pub fn max(&self) -> T {
+        if self.end < self.start {
+            self.start
+        } else {
+            self.end
+        }
+    }
+
+    /// Whether the span runs from a higher value to a lower one.
+    pub fn is_reversed(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// The direction from the start to the end: `1` forwards, `-1` backwards, or `0` if the
+    /// span is empty.
+    pub fn direction(&self) -> Number {
+        if self.start < self.end {
+            Number::ONE
+        } else if self.end < self.start {
+            -Number::ONE
+        } else {
+            Number::ZERO
+        }
+    }
+
+    /// Returns the same span, running forwards.
+    pub fn ascending(&self) -> Self {
+        Self::new(self.min(), self.max())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.min() <= value && value <= self.max()
+    }
+
+    /// Returns the values in both spans, running forwards, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (self.ascending(), other.ascending());
+        let start = if a.start > b.start { a.start } else { b.start };
+        let end = if a.end < b.end { a.end } else { b.end };
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// Returns the smallest span containing both spans, running forwards.
+    pub fn union(&self, other: &Self) -> Self {
+        let (a, b) = (self.ascending(), other.ascending());
+        let start = if a.start < b.start { a.start } else { b.start };
+        let end = if a.end > b.end { a.end } else { b.end };
+        Self { start, end }
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    /// The signed length from the start to the end, negative if the span is reversed.
+    pub fn length(&self) -> T {
+        self.end - self.start
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Copy + Sub<Output = T> + Abs<Output = T>,
+{
+    pub fn abs_length(&self) -> T {
+        self.length().abs()
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Copy + Add<Output = T> + Mul<Number, Output = T>,
+{
+    pub fn midpoint(&self) -> T {
+        (self.start + self.end) * Number::HALF
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Copy
+        + PartialOrd
+        + Zero
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Number, Output = T>
+        + Abs<Output = T>
+        + Euclid
+        + ApproxEq,
+{
+    /// Returns the values from the start towards the end, every `step`, e.g. the grid
+    /// positions along a beam. The end is included if the span is a whole number of steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn step_by(&self, step: T) -> impl Iterator<Item = T> {
+        let step = step.abs();
+        assert!(step != T::zero(), "a span can't be stepped by zero");
+        let length = self.abs_length();
+        let mut count = length.div_euclid(step);
+        // Include an end which is only short of a whole step by rounding.
+        if (step * (count + Number::ONE)).approx_eq(&length) {
+            count = count + Number::ONE;
+        }
+        let (start, step) = (self.start, step * self.direction());
+        let mut index = Number::ZERO;
+        std::iter::from_fn(move || {
+            if index > count {
+                return None;
+            }
+            let value = start + step * index;
+            index = index + Number::ONE;
+            Some(value)
+        })
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Copy + RoundTo,
+{
+    /// Rounds the start and end to the nearest multiple of `grid`.
+    pub fn snap_to(&self, grid: T) -> Self {
+        Self::new(self.start.round_to(grid), self.end.round_to(grid))
+    }
+}
+
+impl<T> From<(T, T)> for Span<T> {
+    fn from((start, end): (T, T)) -> Self {
+        Self::new(start, end)
+    }
+}
+
+impl<T> From<Span<T>> for Interval<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn from(span: Span<T>) -> Self {
+        Interval::new(span.start, span.end)
+    }
+}
+
+impl<T: Display> Display for Span<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to {}", self.start, self.end)
+    }
+}
+
+/// Moves both ends of the span by an offset.
+impl<T> Add<T> for Span<T>
+where
+    T: Copy + Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Self::new(self.start + rhs, self.end + rhs)
+    }
+}
+
+impl<T> Sub<T> for Span<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        Self::new(self.start - rhs, self.end - rhs)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Span<T> {
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.start.approx_eq(&rhs.start) && self.end.approx_eq(&rhs.end)
+    }
+}
+
+impl<T: Validate> Validate for Span<T> {
+    fn validate(&self) -> Result<(), NumberError> {
+        self.start.validate()?;
+        self.end.validate()
+    }
+}
+
+impl<T> SerdeUnits for Span<T>
+where
+    T: SerdeUnits + Copy,
+{
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.map(WithUnits).serialize(serializer)
+    }
+
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Span::<WithUnits<T>>::deserialize(deserializer)?.map(|WithUnits(value)| value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{qty, Length};
+
+    #[test]
+    fn direction_and_length() {
+        let forwards = Span::new(qty!(0 m), qty!(2 m));
+        let backwards = forwards.reversed();
+        assert_eq!(forwards.length(), qty!(2 m));
+        assert_eq!(backwards.length(), qty!(-2 m));
+        assert_eq!(backwards.abs_length(), qty!(2 m));
+        assert_eq!(forwards.direction(), Number::ONE);
+        assert_eq!(backwards.direction(), -Number::ONE);
+        assert_eq!(Span::new(qty!(1 m), qty!(1 m)).direction(), Number::ZERO);
+        assert!(backwards.is_reversed());
+        assert_eq!(backwards.ascending(), forwards);
+        assert!(backwards.contains(qty!(1.5 m)));
+        assert!(!backwards.contains(qty!(2.5 m)));
+        assert_eq!(backwards.midpoint(), qty!(1 m));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = Span::new(qty!(3 m), qty!(0 m));
+        let b = Span::new(qty!(2 m), qty!(5 m));
+        assert_eq!(a.intersection(&b), Some(Span::new(qty!(2 m), qty!(3 m))));
+        assert_eq!(a.union(&b), Span::new(qty!(0 m), qty!(5 m)));
+        assert_eq!(a.intersection(&(b + qty!(2 m))), None);
+        assert_eq!(
+            Interval::from(a),
+            Interval::new(qty!(0 m), qty!(3 m)),
+            "an interval forgets the direction"
+        );
+    }
+
+    #[test]
+    fn grid_positions() {
+        let grid = qty!(0.5 m);
+        let positions: Vec<Length> = Span::new(qty!(0 m), qty!(1.25 m)).step_by(grid).collect();
+        assert_eq!(positions, vec![qty!(0 m), qty!(0.5 m), qty!(1 m)]);
+
+        let positions: Vec<Length> = Span::new(qty!(1.5 m), qty!(0.5 m)).step_by(grid).collect();
+        assert_eq!(positions, vec![qty!(1.5 m), qty!(1 m), qty!(0.5 m)]);
+
+        let snapped = Span::new(qty!(0.2 m), qty!(-1.3 m)).snap_to(grid);
+        assert_eq!(snapped, Span::new(qty!(0 m), qty!(-1.5 m)));
+    }
+
+    #[test]
+    fn serialize_with_units() {
+        let span = Span::new(qty!(40 mm), qty!(0 m));
+        let json = crate::with_units::serialize(&span, serde_json::value::Serializer).unwrap();
+        assert_eq!(json, serde_json::json!({ "start": "40 mm", "end": "0 mm" }));
+        let read: Span<Length> = crate::with_units::deserialize(json).unwrap();
+        assert_eq!(read, span);
+    }
+}
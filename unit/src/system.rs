@@ -1,12 +1,13 @@
+use typenum::{N1, N2, N3, P1, P2, P3, Z0};
 use villagekit_number::{Number, Trig};
 
-use crate::dimension;
+use crate::{dimension, Dim};
 
 dimension!(
     /// Represents a distance.
     ///
     /// Canonically represented in meters.
-    pub Length {
+    pub Length = Dim<P1, Z0, Z0, Z0, Z0, Z0> {
         canonical: Meters,
 
         /// Represents the millimeter unit of length.
@@ -20,7 +21,7 @@ dimension!(
         Kilometers: per 1000.0 canonical,
 
         /// Represents the inch unit of length.
-        Inches: 39.3700787401575 per canonical,
+        Inches: per 0.0254 canonical,
         /// Represents the foot unit of length.
         Feet: per 0.3048 canonical,
         /// Represents the yard unit of length.
@@ -29,16 +30,11 @@ dimension!(
         Miles: per 1609.344 canonical,
         /// Represents the nautical mile unit of length.
         NauticalMiles: per 1852.0 canonical,
-    } where {
-        // Self / Time => LinearVelocity in MetersPerSecond,
-        // Self * Force => Energy in Joules,
-        Self * Length => Area,
-        Self * Area => Volume,
     }
 );
 
 dimension!(
-    pub Area {
+    pub Area = Dim<P2, Z0, Z0, Z0, Z0, Z0> {
         canonical: SquareMeters,
 
         /// Represents the square millimeter unit of area.
@@ -52,22 +48,18 @@ dimension!(
         SquareKilometers: per 1_000_000.0 canonical,
 
         /// Represents the square inch unit of area.
-        SquareInches: 1550.0031000062 per canonical,
+        SquareInches: per 0.00064516 canonical,
         /// Represents the square foot unit of area.
-        SquareFeet: 10.7639104167097 per canonical,
+        SquareFeet: per 0.09290304 canonical,
         /// Represents the square yard unit of area.
         SquareYards: per 0.83612736 canonical,
         /// Represents the acre unit of area.
         Acres: per 4046.8564224 canonical,
-    } where {
-        Self / Length => Length,
-        Self * Length => Volume,
-        Sqrt => Length,
     }
 );
 
 dimension!(
-    pub Volume {
+    pub Volume = Dim<P3, Z0, Z0, Z0, Z0, Z0> {
         canonical: CubicMeters,
 
         /// Represents the cubic millimeter unit of volume.
@@ -85,39 +77,255 @@ dimension!(
         CubicKilometers: per 1_000_000_000.0 canonical,
 
         /// Represents the cubic inch unit of volume.
-        CubicInches: 61023.7440947323 per canonical,
+        CubicInches: per 0.000016387064 canonical,
         /// Represents the cubic foot unit of volume.
-        CubicFeet: 35.3146667214886 per canonical,
+        CubicFeet: per 0.028316846592 canonical,
         /// Represents the cubic yard unit of volume.
-        CubicYards: 1.30795061931439 per canonical,
+        CubicYards: per 0.764554857984 canonical,
 
         /// Represents the fluid ounce unit of volume.
-        FluidOunces: 33814.022701843 per canonical,
+        FluidOunces: per 0.0000295735295625 canonical,
         /// Represents the pint unit of volume.
-        Pints: 2113.37641886519 per canonical,
+        Pints: per 0.000473176473 canonical,
         /// Represents the quart unit of volume.
-        Quarts: 1056.68820943259 per canonical,
+        Quarts: per 0.000946352946 canonical,
         /// Represents the gallon unit of volume.
-        Gallons: 264.172052358148 per canonical,
+        Gallons: per 0.003785411784 canonical,
 
-    } where {
-        Self / Length => Area,
-        Self / Area => Length,
     }
 );
 
 dimension!(
-    pub Angle {
+    pub Angle = Dim<Z0, Z0, Z0, Z0, P1, Z0> {
         canonical: Radians,
 
         /// Represents the radian unit of angle.
         Radians: 1.0 per canonical,
+        /// Represents the rotation (full turn) unit of angle.
+        Rotations: per (PI * 2) canonical,
         /// Represents the degree unit of angle.
-        Rotations: per 6.28318530717959 canonical,
-        /// Represents the degree unit of angle.
-        Degrees: 57.2957795130823 per canonical,
+        Degrees: per (PI / 180) canonical,
         /// Represents the gradian unit of angle.
-        Gradians: 63.6619772367581 per canonical,
+        Gradians: per (PI / 200) canonical,
+    }
+);
+
+dimension!(
+    /// Represents an amount of matter.
+    ///
+    /// Canonically represented in kilograms.
+    pub Mass = Dim<Z0, P1, Z0, Z0, Z0, Z0> {
+        canonical: Kilograms,
+
+        /// Represents the gram unit of mass.
+        Grams: 1000.0 per canonical,
+        /// Represents the kilogram unit of mass.
+        /// This is the standard SI unit of mass.
+        Kilograms: 1.0 per canonical,
+        /// Represents the metric ton (tonne) unit of mass.
+        Tonnes: per 1000.0 canonical,
+
+        /// Represents the ounce unit of mass.
+        Ounces: per 0.028349523125 canonical,
+        /// Represents the pound unit of mass.
+        Pounds: per 0.45359237 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a mass per volume.
+    ///
+    /// Canonically represented in kilograms per cubic meter.
+    pub Density = Dim<N3, P1, Z0, Z0, Z0, Z0> {
+        canonical: KilogramsPerCubicMeter,
+
+        /// Represents the kilogram per cubic meter unit of density.
+        /// This is the standard SI unit of density.
+        KilogramsPerCubicMeter: 1.0 per canonical,
+        /// Represents the gram per cubic centimeter unit of density.
+        GramsPerCubicCentimeter: per 1000.0 canonical,
+
+        /// Represents the pound per cubic foot unit of density.
+        PoundsPerCubicFoot: per 16.01846337396013958 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a duration.
+    ///
+    /// Canonically represented in seconds.
+    pub Time = Dim<Z0, Z0, P1, Z0, Z0, Z0> {
+        canonical: Seconds,
+
+        /// Represents the millisecond unit of time.
+        Milliseconds: 1000.0 per canonical,
+        /// Represents the second unit of time.
+        /// This is the standard SI unit of time.
+        Seconds: 1.0 per canonical,
+        /// Represents the minute unit of time.
+        Minutes: per 60.0 canonical,
+        /// Represents the hour unit of time.
+        Hours: per 3600.0 canonical,
+        /// Represents the day unit of time.
+        Days: per 86400.0 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a speed.
+    ///
+    /// Canonically represented in meters per second.
+    pub LinearVelocity = Dim<P1, Z0, N1, Z0, Z0, Z0> {
+        canonical: MetersPerSecond,
+
+        /// Represents the meter per second unit of velocity.
+        /// This is the standard SI unit of velocity.
+        MetersPerSecond: 1.0 per canonical,
+        /// Represents the kilometer per hour unit of velocity.
+        KilometersPerHour: 3.6 per canonical,
+
+        /// Represents the mile per hour unit of velocity.
+        MilesPerHour: per 0.44704 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a rate of change of velocity.
+    ///
+    /// Canonically represented in meters per second squared.
+    pub Acceleration = Dim<P1, Z0, N2, Z0, Z0, Z0> {
+        canonical: MetersPerSecondSquared,
+
+        /// Represents the meter per second squared unit of acceleration.
+        /// This is the standard SI unit of acceleration.
+        MetersPerSecondSquared: 1.0 per canonical,
+        /// Represents standard gravity, the acceleration of a falling body at sea level.
+        StandardGravity: per 9.80665 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a push or pull, e.g. a load on a beam.
+    ///
+    /// Canonically represented in newtons.
+    pub Force = Dim<P1, P1, N2, Z0, Z0, Z0> {
+        canonical: Newtons,
+
+        /// Represents the newton unit of force.
+        /// This is the standard SI unit of force.
+        Newtons: 1.0 per canonical,
+        /// Represents the kilonewton unit of force.
+        Kilonewtons: per 1000.0 canonical,
+
+        /// Represents the pound-force unit of force.
+        PoundsForce: per 4.4482216152605 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a force per area, e.g. a stress within a material.
+    ///
+    /// Canonically represented in pascals.
+    pub Pressure = Dim<N1, P1, N2, Z0, Z0, Z0> {
+        canonical: Pascals,
+
+        /// Represents the pascal unit of pressure.
+        /// This is the standard SI unit of pressure.
+        Pascals: 1.0 per canonical,
+        /// Represents the kilopascal unit of pressure.
+        Kilopascals: per 1000.0 canonical,
+        /// Represents the megapascal unit of pressure.
+        Megapascals: per 1_000_000.0 canonical,
+        /// Represents the bar unit of pressure.
+        Bars: per 100_000.0 canonical,
+
+        /// Represents the pound-force per square inch unit of pressure.
+        PoundsPerSquareInch: per 6894.757293168361337 canonical,
+    }
+);
+
+dimension!(
+    /// Represents work done by a force.
+    ///
+    /// Canonically represented in joules.
+    pub Energy = Dim<P2, P1, N2, Z0, Z0, Z0> {
+        canonical: Joules,
+
+        /// Represents the joule unit of energy.
+        /// This is the standard SI unit of energy.
+        Joules: 1.0 per canonical,
+        /// Represents the kilojoule unit of energy.
+        Kilojoules: per 1000.0 canonical,
+        /// Represents the watt-hour unit of energy.
+        WattHours: per 3600.0 canonical,
+        /// Represents the kilowatt-hour unit of energy.
+        KilowattHours: per 3_600_000.0 canonical,
+    }
+);
+
+dimension!(
+    /// Represents a cost.
+    ///
+    /// Canonically represented in the major unit of a single currency, called
+    /// dollars here. Other currencies can be added as a [`DynamicUnit`](crate::DynamicUnit)
+    /// with an exchange rate.
+    pub Money = Dim<Z0, Z0, Z0, Z0, Z0, P1> {
+        canonical: Dollars,
+
+        /// Represents the cent unit of money.
+        Cents: 100.0 per canonical,
+        /// Represents the dollar unit of money.
+        Dollars: 1.0 per canonical,
+    }
+);
+
+dimension!(
+    /// Represents a cost per length, e.g. the price of timber or tubing.
+    ///
+    /// Canonically represented in dollars per meter.
+    pub MoneyPerLength = Dim<N1, Z0, Z0, Z0, Z0, P1> {
+        canonical: DollarsPerMeter,
+
+        /// Represents the dollar per meter unit of cost per length.
+        DollarsPerMeter: 1.0 per canonical,
+        /// Represents the dollar per foot unit of cost per length.
+        DollarsPerFoot: 0.3048 per canonical,
+    }
+);
+
+dimension!(
+    /// Represents a temperature, e.g. the melting point of a filament.
+    ///
+    /// Temperatures are points on a scale with an arbitrary zero, so subtracting two
+    /// temperatures gives a [`TemperatureDelta`], and only a delta can be added to one.
+    ///
+    /// Canonically represented in kelvin.
+    pub Temperature as point of TemperatureDelta {
+        canonical: Kelvin,
+
+        /// Represents the kelvin unit of temperature.
+        /// This is the standard SI unit of temperature.
+        Kelvin: 1.0 per canonical,
+        /// Represents the degree Celsius unit of temperature.
+        Celsius: 1.0 per canonical offset 273.15,
+        /// Represents the degree Fahrenheit unit of temperature.
+        Fahrenheit: 1.8 per canonical offset 459.67,
+    }
+);
+
+dimension!(
+    /// Represents a difference between two temperatures.
+    ///
+    /// Canonically represented in kelvin.
+    pub TemperatureDelta = Dim<Z0, Z0, Z0, P1, Z0, Z0> {
+        canonical: DeltaKelvin,
+
+        /// Represents a difference of one kelvin.
+        DeltaKelvin: 1.0 per canonical,
+        /// Represents a difference of one degree Celsius.
+        DeltaCelsius: 1.0 per canonical,
+        /// Represents a difference of one degree Fahrenheit.
+        DeltaFahrenheit: 1.8 per canonical,
     }
 );
 
@@ -125,41 +333,182 @@ impl Trig for Angle {
     type Output = Number;
 
     fn hypot(&self, other: Self) -> Self::Output {
-        self.0.hypot(other.0)
+        self.canonical().hypot(other.canonical())
     }
 
     fn sin(&self) -> Self::Output {
-        self.0.sin()
+        self.canonical().sin()
     }
 
     fn cos(&self) -> Self::Output {
-        self.0.cos()
+        self.canonical().cos()
     }
 
     fn tan(&self) -> Self::Output {
-        self.0.tan()
+        self.canonical().tan()
     }
 
     fn sin_cos(&self) -> (Self::Output, Self::Output) {
-        self.0.sin_cos()
+        self.canonical().sin_cos()
     }
 }
 
+/// Creates a quantity from a value and a unit abbreviation or unit type, e.g.
+/// `qty!(25.4 mm)` or `qty!(1 Meters)`.
+///
+/// Values can be decimal literals, fractions such as `3/8` or `1 3/8`, or
+/// [`Number`] expressions as a variable or in parentheses, e.g. `qty!(width m)` or
+/// `qty!((count * spacing) mm)`. Several values are summed, e.g. `qty!(1 ft 3 1/2 in)`,
+/// and a leading `-` negates the whole sum, the same as when parsing.
+///
+/// A single literal value with a unit can be used in a constant.
+#[macro_export]
+macro_rules! qty {
+    (- $value:literal $unit:ident) => {
+        $crate::__qty_term!($crate::num!(-$value), $unit)
+    };
+    (- $($rest:tt)+) => {
+        -$crate::qty!($($rest)+)
+    };
+    ($value:literal $unit:ident) => {
+        $crate::__qty_term!($crate::num!($value), $unit)
+    };
+    ($($tokens:tt)+) => {
+        $crate::__qty_sum!([] $($tokens)+)
+    };
+}
+
+/// Sums the terms of [`qty!`], one value and unit at a time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __qty_sum {
+    ([$sum:expr]) => {
+        $sum
+    };
+    ([$($sum:expr)?] $whole:literal $numerator:literal / $denominator:literal $unit:ident $($rest:tt)*) => {
+        $crate::__qty_sum!([$($sum +)? $crate::__qty_term!(
+            $crate::num!($whole).add($crate::num!($numerator).div($crate::num!($denominator))),
+            $unit
+        )] $($rest)*)
+    };
+    ([$($sum:expr)?] $numerator:tt / $denominator:tt $unit:ident $($rest:tt)*) => {
+        $crate::__qty_sum!([$($sum +)? $crate::__qty_term!(
+            $crate::__qty_value!($numerator).div($crate::__qty_value!($denominator)),
+            $unit
+        )] $($rest)*)
+    };
+    ([$($sum:expr)?] $value:tt $unit:ident $($rest:tt)*) => {
+        $crate::__qty_sum!([$($sum +)? $crate::__qty_term!($crate::__qty_value!($value), $unit)] $($rest)*)
+    };
+    ([$($sum:expr)?] $($rest:tt)+) => {
+        compile_error!(concat!(
+            "expected a value followed by a unit, e.g. `25.4 mm`, found `",
+            stringify!($($rest)+),
+            "`"
+        ))
+    };
+}
+
+/// A value of [`qty!`], as a [`Number`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __qty_value {
+    ($value:literal) => {
+        $crate::num!($value)
+    };
+    ($value:expr) => {
+        $value
+    };
+}
+
+/// A value of [`qty!`] with a unit, as a quantity of the unit's dimension.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __qty_term {
+    ($value:expr, in) => {
+        $crate::__qty_term!($value, r#in)
+    };
+    ($value:expr, $unit:ident) => {{
+        #[allow(unused_parens, unused_braces)]
+        let value: $crate::Number = $value;
+        {
+            #[allow(unused_imports)]
+            use $crate::system::abbreviations::*;
+            <$unit as $crate::UnitOf>::Dim::from_scalar::<$unit>(value)
+        }
+    }};
+}
+
+/// Declares the type alias of a unit abbreviation, which may be a keyword.
+macro_rules! abbreviation {
+    (in => $unit:ident) => {
+        pub type r#in = super::$unit;
+    };
+    ($abbrev:ident => $unit:ident) => {
+        pub type $abbrev = super::$unit;
+    };
+}
+
 macro_rules! system_qty_macro {
     ($macro_name:ident, $( $dimension:ident { $( $abbrev:ident => $unit:ident ),* $(,)? } )* ) => {
-        #[macro_export]
-        macro_rules! qty {
+        /// The unit types by their [`qty!`] abbreviation.
+        pub mod abbreviations {
+            #![allow(non_camel_case_types)]
+
             $(
                 $(
-                    ($scalar:literal $abbrev) => {
-                        $dimension::from_scalar::<$crate::$unit>(num!($scalar))
-                    };
+                    abbreviation!($abbrev => $unit);
                 )*
             )*
-            ($scalar:literal $other_unit:ident) => {
-                <$other_unit as UnitOf>::Dim::from_scalar::<$other_unit>(num!($scalar))
-            };
         }
+
+        $(
+            $(
+                impl $crate::UnitSymbol for $unit {
+                    const SYMBOL: &'static str = stringify!($abbrev);
+                }
+            )*
+
+            impl $dimension {
+                /// Parses a quantity with units, e.g. `"25.4 mm"` or `"3 ft 4-1/2 in"`.
+                pub fn parse(s: &str) -> Result<Self, $crate::ParseQuantityError> {
+                    $crate::parse::parse_quantity(s, stringify!($dimension), None)
+                        .map(<Self as $crate::Dimension>::from_canonical)
+                }
+
+                /// Parses a quantity with units, including the runtime units in the registry.
+                pub fn parse_with(
+                    s: &str,
+                    registry: &$crate::UnitRegistry,
+                ) -> Result<Self, $crate::ParseQuantityError> {
+                    $crate::parse::parse_quantity(s, stringify!($dimension), Some(registry))
+                        .map(<Self as $crate::Dimension>::from_canonical)
+                }
+            }
+
+            impl core::str::FromStr for $dimension {
+                type Err = $crate::ParseQuantityError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Self::parse(s)
+                }
+            }
+        )*
+
+        /// The units accepted by [`qty!`], for parsing.
+        pub(crate) const UNITS: &[$crate::parse::UnitEntry<'static>] = &[
+            $(
+                $(
+                    $crate::parse::UnitEntry {
+                        symbol: stringify!($abbrev),
+                        name: None,
+                        dimension: stringify!($dimension),
+                        coefficient: <$unit as $crate::UnitOf>::CONVERSION_COEFFICIENT,
+                        constant: <$unit as $crate::UnitOf>::CONVERSION_CONSTANT,
+                    },
+                )*
+            )*
+        ];
     };
 }
 
@@ -207,6 +556,70 @@ system_qty_macro! {
         deg  => Degrees,
         grad => Gradians,
     }
+    Mass {
+        kg => Kilograms,
+        g  => Grams,
+        t  => Tonnes,
+        lb => Pounds,
+        oz => Ounces,
+    }
+    Density {
+        kg_per_m3  => KilogramsPerCubicMeter,
+        g_per_cm3  => GramsPerCubicCentimeter,
+        lb_per_ft3 => PoundsPerCubicFoot,
+    }
+    Time {
+        s   => Seconds,
+        ms  => Milliseconds,
+        min => Minutes,
+        h   => Hours,
+        d   => Days,
+    }
+    LinearVelocity {
+        m_per_s  => MetersPerSecond,
+        km_per_h => KilometersPerHour,
+        mph      => MilesPerHour,
+    }
+    Acceleration {
+        m_per_s2 => MetersPerSecondSquared,
+        gn       => StandardGravity,
+    }
+    Force {
+        N   => Newtons,
+        kN  => Kilonewtons,
+        lbf => PoundsForce,
+    }
+    Pressure {
+        Pa  => Pascals,
+        kPa => Kilopascals,
+        MPa => Megapascals,
+        bar => Bars,
+        psi => PoundsPerSquareInch,
+    }
+    Energy {
+        J   => Joules,
+        kJ  => Kilojoules,
+        Wh  => WattHours,
+        kWh => KilowattHours,
+    }
+    Money {
+        usd  => Dollars,
+        cent => Cents,
+    }
+    MoneyPerLength {
+        usd_per_m  => DollarsPerMeter,
+        usd_per_ft => DollarsPerFoot,
+    }
+    Temperature {
+        K    => Kelvin,
+        degC => Celsius,
+        degF => Fahrenheit,
+    }
+    TemperatureDelta {
+        delta_K    => DeltaKelvin,
+        delta_degC => DeltaCelsius,
+        delta_degF => DeltaFahrenheit,
+    }
 }
 
 #[cfg(test)]
@@ -230,4 +643,192 @@ mod tests {
         let expected_m = Length::from_scalar::<Meters>(num!(1.0));
         assert_eq!(length_m, expected_m);
     }
+
+    macro_rules! assert_approx_eq {
+        ($given:expr, $expected:expr) => {
+            assert!(
+                ApproxEq::approx_eq(&$given, &$expected),
+                "assert_approx_eq!({}, {})\n\n    left  = {:?}\n    right = {:?}\n",
+                stringify!($given),
+                stringify!($expected),
+                $given,
+                $expected
+            )
+        };
+    }
+
+    #[test]
+    fn test_qty_expressions() {
+        let width = num!(1.5);
+        let count = num!(3);
+        assert_eq!(qty!(width m), qty!(1.5 m));
+        assert_eq!(qty!(-width mm), qty!(-1.5 mm));
+        assert_eq!(qty!((count * width) in), qty!(4.5 in));
+        assert_eq!(qty!({ width + count } ft), qty!(4.5 ft));
+        assert_eq!(qty!(-2 m), -qty!(2 m));
+        assert_eq!(qty!(3/8 in), qty!(0.375 in));
+        assert_eq!(qty!(count / 8 in), qty!(0.375 in));
+        assert_eq!(qty!(1 3/8 in), qty!(1.375 in));
+        assert_approx_eq!(qty!(1 ft 3 in), qty!(15 in));
+        assert_approx_eq!(qty!(2 m 5 cm width mm), qty!(2051.5 mm));
+        assert_approx_eq!(qty!(-1 ft 6 in), qty!(-18 in));
+        assert_approx_eq!(qty!(1 Feet 1 1/2 Inches), qty!(13.5 in));
+
+        const SPACING: Length = qty!(40 mm);
+        assert_eq!(SPACING, qty!(4 cm));
+    }
+
+    /// Exactly equal under a decimal backend, and approximately under `f64`.
+    macro_rules! assert_exact_eq {
+        ($given:expr, $expected:expr) => {
+            if Number::DECIMAL {
+                assert_eq!($given, $expected);
+            } else {
+                assert_approx_eq!($given, $expected);
+            }
+        };
+    }
+
+    #[test]
+    fn test_unit_conversions() {
+        assert_exact_eq!(qty!(1 mm), qty!(0.001 m));
+        assert_exact_eq!(qty!(1 km), qty!(1000 m));
+        assert_exact_eq!(qty!(1 in), qty!(25.4 mm));
+        assert_exact_eq!(qty!(1 ft), qty!(12 in));
+        assert_exact_eq!(qty!(1 ft2), qty!(144 in2));
+        assert_exact_eq!(qty!(1 gal), qty!(4 qt));
+        let tolerance = if Number::DECIMAL {
+            qty!(1e-30 rad)
+        } else {
+            qty!(1e-15 rad)
+        };
+        assert!((qty!(180 deg) - Radians::PI).abs() < tolerance);
+        assert!((qty!(0.25 rot) - qty!(90 deg)).abs() < tolerance);
+        assert_exact_eq!(qty!(1 in).to::<Millimeters>(), num!(25.4));
+        assert_approx_eq!(qty!(1 lb), qty!(16 oz));
+        assert_approx_eq!(qty!(1 t), qty!(1000000 g));
+        assert_approx_eq!(qty!(1 h), qty!(3600000 ms));
+        assert_approx_eq!(qty!(1 g_per_cm3), qty!(1000 kg_per_m3));
+        assert!((qty!(1 psi) - qty!(1 lbf) / qty!(1 in2)).abs() < qty!(1e-12 Pa));
+        assert_approx_eq!(qty!(1 kWh), qty!(3.6 MPa) * qty!(1 m2) * qty!(1 m));
+        assert_approx_eq!(qty!(100 cent), qty!(1 usd));
+    }
+
+    #[test]
+    fn test_cross_dimension_algebra() {
+        let timber = qty!(500 kg_per_m3);
+        let mass: Mass = qty!(0.01 m3) * timber;
+        assert_approx_eq!(mass, qty!(5 kg));
+        assert_approx_eq!(mass / qty!(0.01 m3), timber);
+        assert_approx_eq!(mass / timber, qty!(10 l));
+
+        let weight: Force = mass * qty!(1 gn);
+        assert_approx_eq!(weight, qty!(49.03325 N));
+        assert_approx_eq!(weight / mass, qty!(9.80665 m_per_s2));
+        assert_approx_eq!(weight / qty!(0.01 m2), qty!(4.903325 kPa));
+        assert_approx_eq!(qty!(2 kPa) * qty!(0.5 m2), qty!(1 kN));
+        assert_approx_eq!(weight * qty!(2 m), qty!(98.0665 J));
+
+        let speed: LinearVelocity = qty!(90 km) / qty!(1 h);
+        assert_approx_eq!(speed, qty!(25 m_per_s));
+        assert_approx_eq!(speed * qty!(1 min), qty!(1.5 km));
+        assert_approx_eq!(speed / qty!(10 s), qty!(2.5 m_per_s2));
+
+        let price = qty!(4.5 usd_per_m);
+        let cost: Money = qty!(3 m) * price;
+        assert_approx_eq!(cost, qty!(13.5 usd));
+        assert_approx_eq!(cost / qty!(3 m), price);
+        assert_approx_eq!(cost / price, qty!(3 m));
+        assert_approx_eq!(qty!(1 usd_per_ft) * qty!(1 ft), qty!(1 usd));
+    }
+
+    #[test]
+    fn test_temperature() {
+        // 1 / 1.8 is not exact, so Fahrenheit is only compared to within a tolerance.
+        let close = |a: Temperature, b: Temperature| (a - b).abs() < qty!(1e-12 delta_K);
+
+        assert_approx_eq!(qty!(0 degC), qty!(273.15 K));
+        assert!(close(qty!(32 degF), qty!(0 degC)));
+        assert!(close(qty!(-40 degF), qty!(-40 degC)));
+        assert!(close(qty!(0 K), qty!(-459.67 degF)));
+        assert!((qty!(100 degC).to::<Fahrenheit>() - num!(212)).abs() < num!(1e-12));
+        assert_approx_eq!(qty!(20 degC).to::<Kelvin>(), num!(293.15));
+        assert_approx_eq!(qty!(20 degC).to::<Celsius>(), num!(20));
+
+        let delta: TemperatureDelta = qty!(60 degC) - qty!(20 degC);
+        assert_approx_eq!(delta, qty!(40 delta_K));
+        assert!((delta - qty!(72 delta_degF)).abs() < qty!(1e-12 delta_K));
+        assert!(close(qty!(20 degC) + qty!(18 delta_degF), qty!(30 degC)));
+        assert!(close(qty!(20 degC) - qty!(10 delta_degC), qty!(50 degF)));
+        assert!(qty!(210 degC) < qty!(220 degC));
+
+        // Thermal expansion of a 6 m steel beam, at 12 µm per meter per kelvin.
+        let growth = qty!(6 m) * num!(0.000012) * delta.to::<DeltaKelvin>();
+        assert_approx_eq!(growth, qty!(2.88 mm));
+    }
+
+    #[test]
+    fn test_normalized_hash() {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        fn hash_of(value: &Length) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = qty!(1000 mm);
+        let b = qty!(1.0 m);
+        let c = num!(0.5) * qty!(2.00 m);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&c));
+    }
+
+    #[test]
+    fn test_checked_operations() {
+        assert_eq!(
+            qty!(1 m).checked_div(num!(0)),
+            Err(NumberError::DivideByZero)
+        );
+        assert_eq!(qty!(1 m).checked_div(num!(4)), Ok(qty!(0.25 m)));
+        assert_eq!(
+            qty!(1 m2).checked_div(qty!(0 m)),
+            Err(NumberError::DivideByZero)
+        );
+        assert_eq!(qty!(6 m2).checked_div(qty!(2 m)), Ok(qty!(3 m)));
+        assert_eq!(qty!(-4 m2).checked_sqrt(), Err(NumberError::Domain));
+        assert_eq!(qty!(2 m).checked_mul(qty!(3 m)), Ok(qty!(6 m2)));
+        assert_eq!(qty!(2 m).checked_sub(qty!(3 m)), Ok(qty!(-1 m)));
+        assert!(qty!(2 m).validate().is_ok());
+    }
+
+    #[test]
+    fn test_round_to_step() {
+        assert_eq!(qty!(107 mm).round_to(qty!(40 mm)), qty!(120 mm));
+        assert_eq!(qty!(107 mm).floor_to(qty!(40 mm)), qty!(80 mm));
+        assert_eq!(qty!(-107 mm).trunc_to(qty!(40 mm)), qty!(-80 mm));
+        assert_eq!(qty!(1 in).ceil_to(qty!(1 cm)), qty!(3 cm));
+    }
+
+    #[test]
+    fn test_min_max_clamp() {
+        assert_eq!(qty!(1 m).min(qty!(1 ft)), qty!(1 ft));
+        assert_eq!(qty!(1 m).max(qty!(1 ft)), qty!(1 m));
+        assert_eq!(qty!(2 m).clamp(qty!(0 m), qty!(1 yd)), qty!(1 yd));
+    }
+
+    #[test]
+    fn test_sign_and_euclid() {
+        assert_eq!(qty!(-3 mm).signum(), num!(-1));
+        assert!(qty!(-3 mm).is_sign_negative());
+        assert_eq!(qty!(-50 mm).rem_euclid(qty!(40 mm)), qty!(30 mm));
+        assert_eq!(qty!(-50 mm).div_euclid(qty!(40 mm)), num!(-2));
+    }
+
+    #[test]
+    fn test_cbrt_volume() {
+        let side = qty!(8 m3).cbrt();
+        assert!(side.approx_eq(&qty!(2 m)));
+    }
 }
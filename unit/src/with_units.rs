@@ -0,0 +1,251 @@
+//! Serializes quantities with their units, e.g. `"40 mm"`, for fields which opt in with
+//! `#[serde(with = "villagekit_unit::with_units")]`.
+//!
+//! Quantities are written in their metric unit, or in their canonical unit if the metric
+//! unit would not read back exactly. Besides strings with units, reading accepts objects
+//! such as `{ "value": 40, "unit": "mm" }` and the bare canonical numbers which quantities
+//! are serialized as by default, so data stored before a field opted in keeps loading.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use villagekit_unit::*;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Beam {
+//!     #[serde(with = "villagekit_unit::with_units")]
+//!     length: Length,
+//! }
+//!
+//! let json = r#"{ "length": { "value": 40, "unit": "mm" } }"#;
+//! let beam: Beam = serde_json::from_str(json).unwrap();
+//! assert_eq!(beam.length, qty!(40 mm));
+//! assert_eq!(serde_json::to_string(&beam).unwrap(), r#"{"length":"40 mm"}"#);
+//! ```
+
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{fmt, marker::PhantomData};
+use villagekit_number::Number;
+
+use crate::{
+    format,
+    parse::{lookup, parse_quantity},
+    Dimension, FormatQuantity, UnitSystem,
+};
+
+/// A value which can be serialized with units.
+pub trait SerdeUnits: Sized {
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+pub fn serialize<T: SerdeUnits, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_units(serializer)
+}
+
+pub fn deserialize<'de, T: SerdeUnits, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_units(deserializer)
+}
+
+/// Serializes the inner value with units, for implementing [`SerdeUnits`] on containers.
+pub struct WithUnits<T>(pub T);
+
+impl<T: SerdeUnits> Serialize for WithUnits<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_units(serializer)
+    }
+}
+
+impl<'de, T: SerdeUnits> Deserialize<'de> for WithUnits<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_units(deserializer).map(WithUnits)
+    }
+}
+
+impl<T: FormatQuantity> SerdeUnits for T {
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let canonical = self.canonical();
+        let metric =
+            lookup(T::unit_for(&UnitSystem::Metric), None).expect("metric units are known");
+        let value = canonical / metric.coefficient - metric.constant;
+        if (value + metric.constant) * metric.coefficient == canonical {
+            serializer.collect_str(&format_args!("{} {}", value.normalize(), metric.symbol))
+        } else {
+            serializer.collect_str(&format_args!(
+                "{} {}",
+                canonical.normalize(),
+                format::canonical::<T>().symbol
+            ))
+        }
+    }
+
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(QuantityVisitor(PhantomData))
+    }
+}
+
+impl<T: SerdeUnits + Clone> SerdeUnits for Vec<T> {
+    fn serialize_units<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().cloned().map(WithUnits))
+    }
+
+    fn deserialize_units<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<WithUnits<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|WithUnits(value)| value).collect())
+    }
+}
+
+struct QuantityVisitor<T>(PhantomData<T>);
+
+impl<T: FormatQuantity> QuantityVisitor<T> {
+    fn parse<E: de::Error>(value: Number, unit: &str) -> Result<T, E> {
+        parse_quantity(&format!("{} {}", value, unit), T::NAME, None)
+            .map(<T as Dimension>::from_canonical)
+            .map_err(E::custom)
+    }
+
+    fn number<E: de::Error>(value: impl fmt::Display) -> Result<T, E> {
+        Number::parse(&value.to_string())
+            .map(<T as Dimension>::from_canonical)
+            .map_err(E::custom)
+    }
+}
+
+impl<'de, T: FormatQuantity> Visitor<'de> for QuantityVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a {} with units, e.g. \"1 {}\"",
+            T::NAME,
+            format::canonical::<T>().symbol
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        // A bare number is the canonical value, as written without units.
+        match Number::parse(value.trim()) {
+            Ok(canonical) => Ok(<T as Dimension>::from_canonical(canonical)),
+            Err(_) => parse_quantity(value, T::NAME, None)
+                .map(<T as Dimension>::from_canonical)
+                .map_err(E::custom),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<T, E> {
+        Self::number(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        Self::number(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        Self::number(value)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let (mut value, mut unit) = (None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "value" => value = Some(map.next_value::<Number>()?),
+                "unit" => unit = Some(map.next_value::<String>()?),
+                other => return Err(de::Error::unknown_field(other, &["value", "unit"])),
+            }
+        }
+        let value = value.ok_or_else(|| de::Error::missing_field("value"))?;
+        let unit = unit.ok_or_else(|| de::Error::missing_field("unit"))?;
+        Self::parse(value, &unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Part {
+        #[serde(with = "crate::with_units")]
+        length: Length,
+        #[serde(with = "crate::with_units")]
+        angle: Angle,
+        #[serde(with = "crate::with_units")]
+        melting_point: Temperature,
+    }
+
+    #[test]
+    fn round_trip() {
+        let part = Part {
+            length: qty!(40 mm),
+            angle: qty!(0.5 rad),
+            melting_point: qty!(210 degC),
+        };
+        let json = serde_json::to_string(&part).unwrap();
+        assert!(json.contains(r#""length":"40 mm""#), "{}", json);
+        // Whichever unit the angle is written in, it reads back exactly.
+        assert_eq!(serde_json::from_str::<Part>(&json).unwrap(), part);
+    }
+
+    #[test]
+    fn accepted_forms() {
+        let part: Part = serde_json::from_str(
+            r#"{
+                "length": { "value": 1.5, "unit": "in" },
+                "angle": "90 deg",
+                "melting_point": { "value": "410", "unit": "degF" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(part.length, qty!(1.5 in));
+        assert_eq!(part.angle, qty!(90 deg));
+        assert!((part.melting_point - qty!(410 degF)).abs() < qty!(1e-9 delta_K));
+
+        // The bare canonical numbers written without units still load.
+        let json = format!(
+            r#"{{ "length": {}, "angle": {}, "melting_point": {} }}"#,
+            serde_json::to_string(&qty!(40 mm)).unwrap(),
+            serde_json::to_string(&qty!(0.5 rad)).unwrap(),
+            serde_json::to_string(&qty!(300 K)).unwrap(),
+        );
+        let part: Part = serde_json::from_str(&json).unwrap();
+        assert_eq!(part.length, qty!(40 mm));
+        assert_eq!(part.melting_point, qty!(300 K));
+    }
+
+    #[test]
+    fn lists() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Cuts {
+            #[serde(with = "crate::with_units")]
+            lengths: Vec<Length>,
+        }
+
+        let cuts = Cuts {
+            lengths: vec![qty!(40 mm), qty!(40 mm)],
+        };
+        let json = serde_json::to_string(&cuts).unwrap();
+        assert_eq!(json, r#"{"lengths":["40 mm","40 mm"]}"#);
+        assert_eq!(serde_json::from_str::<Cuts>(&json).unwrap(), cuts);
+    }
+
+    #[test]
+    fn errors() {
+        let error = serde_json::from_str::<Part>(
+            r#"{ "length": "3 m2", "angle": "0 rad", "melting_point": "0 K" }"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`m2` is a unit of Area, expected a unit of Length"));
+    }
+}